@@ -0,0 +1,102 @@
+use actix_web::{web, FromRequest, HttpRequest};
+use diesel::prelude::*;
+use futures_util::future::{ready, Ready};
+
+use crate::config::AppConfig;
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::models::session;
+use crate::schema::users;
+
+/// Resolves the caller's user id, preferring a real bearer token over the
+/// original placeholder header:
+///
+/// - `Authorization: Bearer <token>` — looked up against
+///   [`session::find_active`], which also enforces
+///   `AppConfig::session_ttl_minutes`; rejected if the token is unknown,
+///   expired, or was revoked by [`crate::handlers::users::logout`].
+/// - `X-User-Id: <id>` — the original placeholder from before sessions
+///   existed, kept for handlers/tests that never sent a token. Trusts
+///   whatever a client (or upstream proxy) puts there, so it should keep
+///   shrinking in favor of the bearer path as callers are touched.
+pub struct AuthUser(pub i32);
+
+impl FromRequest for AuthUser {
+    type Error = AppError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut actix_web::dev::Payload) -> Self::Future {
+        if let Some(token) = bearer_token(req) {
+            let Some(pool) = req.app_data::<web::Data<DbPool>>() else {
+                return ready(Err(AppError::Internal("db pool not configured".into())));
+            };
+            let Some(config) = req.app_data::<web::Data<AppConfig>>() else {
+                return ready(Err(AppError::Internal("app config not configured".into())));
+            };
+            let mut conn = match cpool(pool) {
+                Ok(conn) => conn,
+                Err(e) => return ready(Err(e)),
+            };
+            return ready(match session::find_active(&mut conn, &token, config.session_ttl_minutes) {
+                Ok(Some(active_session)) => {
+                    let ip_address = req.connection_info().realip_remote_addr().unwrap_or("unknown").to_string();
+                    // Best-effort: a failed touch shouldn't fail the request it's riding along with.
+                    let _ = session::touch(&mut conn, active_session.id, &ip_address);
+                    Ok(AuthUser(active_session.user_id))
+                }
+                Ok(None) => Err(AppError::Unauthorized("session token is invalid or revoked".into())),
+                Err(e) => Err(AppError::Internal(e.to_string())),
+            });
+        }
+
+        let user_id = req
+            .headers()
+            .get("X-User-Id")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<i32>().ok());
+
+        ready(match user_id {
+            Some(id) => Ok(AuthUser(id)),
+            None => Err(AppError::Unauthorized("missing bearer token or X-User-Id header".into())),
+        })
+    }
+}
+
+fn bearer_token(req: &HttpRequest) -> Option<String> {
+    let header = req.headers().get("Authorization")?.to_str().ok()?;
+    header.strip_prefix("Bearer ").map(str::to_string)
+}
+
+/// Like [`AuthUser`], but rejects the request unless `users.is_admin` is
+/// set, for `/api/admin/*` routes. Layered on top of [`AuthUser`] instead
+/// of duplicating its token/placeholder resolution — an extra
+/// `users.is_admin` lookup once that's settled.
+pub struct AdminUser(pub i32);
+
+impl FromRequest for AdminUser {
+    type Error = AppError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut actix_web::dev::Payload) -> Self::Future {
+        let auth = match AuthUser::from_request(req, payload).into_inner() {
+            Ok(auth) => auth,
+            Err(e) => return ready(Err(e)),
+        };
+
+        let Some(pool) = req.app_data::<web::Data<DbPool>>() else {
+            return ready(Err(AppError::Internal("db pool not configured".into())));
+        };
+        let mut conn = match cpool(pool) {
+            Ok(conn) => conn,
+            Err(e) => return ready(Err(e)),
+        };
+
+        let is_admin = users::table
+            .find(auth.0)
+            .select(users::is_admin)
+            .first::<bool>(&mut conn)
+            .unwrap_or(false);
+
+        ready(if is_admin { Ok(AdminUser(auth.0)) } else { Err(AppError::Unauthorized("admin access required".into())) })
+    }
+}
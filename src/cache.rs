@@ -0,0 +1,25 @@
+//! Conditional-response helper for read-mostly aggregate endpoints
+//! (reports, statistics): computes a strong ETag from the serialized body
+//! and honors `If-None-Match` with a bodyless 304 instead of resending
+//! results the client already has.
+
+use actix_web::{HttpRequest, HttpResponse};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+pub fn conditional_json<T: Serialize>(req: &HttpRequest, body: &T) -> HttpResponse {
+    let json = serde_json::to_vec(body).unwrap_or_default();
+    let etag = format!("\"{:x}\"", Sha256::digest(&json));
+
+    if let Some(if_none_match) = req.headers().get("If-None-Match").and_then(|v| v.to_str().ok()) {
+        if if_none_match == etag {
+            return HttpResponse::NotModified().finish();
+        }
+    }
+
+    HttpResponse::Ok()
+        .insert_header(("ETag", etag))
+        .insert_header(("Cache-Control", "private, max-age=60"))
+        .content_type("application/json")
+        .body(json)
+}
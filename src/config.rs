@@ -0,0 +1,221 @@
+//! Process-wide configuration, loaded once from the environment at startup
+//! and passed around as `web::Data<AppConfig>`.
+
+use std::path::PathBuf;
+
+/// How [`crate::logging::init_logger`] rolls the file it writes to, when
+/// `AppConfig::log_dir` is set at all.
+#[derive(Clone, Copy, Debug)]
+pub enum LogRotation {
+    /// A new file per calendar day, named with that day's date.
+    Daily,
+    /// A new file once the current one reaches this many bytes.
+    SizeBytes(u64),
+}
+
+/// Controls who [`crate::handlers::users::register`] lets create an
+/// account. Defaults to [`RegistrationMode::Disabled`] — a self-hosted
+/// install has to opt into public sign-up (open or invite-gated) rather
+/// than accidentally exposing one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegistrationMode {
+    /// Anyone who can reach the endpoint can create an account.
+    Open,
+    /// The request must carry one of `AppConfig::invite_codes`.
+    InviteCode,
+    /// `register` always fails with [`crate::error::AppError::Validation`].
+    Disabled,
+}
+
+#[derive(Clone, Debug)]
+pub struct AppConfig {
+    /// When set, handlers reject data that merely looks wrong instead of
+    /// coercing it — e.g. a negative `amount`, or a `rate_to_fixed` of
+    /// zero. Off by default because existing single-user installs may have
+    /// legacy data that wouldn't pass.
+    pub strict_mode: bool,
+    /// Local directory [`crate::storage`] reads and writes receipt
+    /// attachments under. Defaults to `./attachments`.
+    pub attachments_dir: PathBuf,
+    /// Rejects attachment uploads larger than this. Defaults to 10 MiB.
+    pub max_attachment_bytes: usize,
+    /// Base URL of the exchange-rate provider (e.g. `https://api.exchangerate.host`)
+    /// used by [`crate::jobs::exchange_rates`]. `None` disables rate
+    /// refreshing entirely.
+    pub rate_provider_url: Option<String>,
+    /// SMTP relay host for [`crate::mail::SmtpMailer`], used to email
+    /// password-reset links. `None` falls back to
+    /// [`crate::mail::LoggingMailer`], same as leaving `rate_provider_url`
+    /// unset disables rate refreshing instead of failing outright.
+    pub smtp_host: Option<String>,
+    pub smtp_port: u16,
+    pub smtp_username: String,
+    pub smtp_password: String,
+    pub smtp_from: String,
+    /// How long a `POST /password-reset/request` link stays valid.
+    pub password_reset_ttl_minutes: i64,
+    /// `RUST_LOG`-style filter string for [`crate::logging::init_logger`].
+    /// Defaults to `info`.
+    pub log_level: String,
+    /// When set, [`crate::logging::init_logger`] also writes access and
+    /// application logs to rotating files under this directory, since a
+    /// self-hosted install often has nothing collecting stdout. `None`
+    /// (the default) logs to stdout only.
+    pub log_dir: Option<PathBuf>,
+    /// Only consulted when `log_dir` is set.
+    pub log_rotation: LogRotation,
+    /// Who `POST /register` lets create an account. Defaults to
+    /// [`RegistrationMode::Disabled`].
+    pub registration_mode: RegistrationMode,
+    /// Valid invite codes when `registration_mode` is
+    /// [`RegistrationMode::InviteCode`]. Read from the comma-separated
+    /// `REGISTRATION_INVITE_CODES` env var; unused (but harmless to set)
+    /// in every other mode.
+    pub invite_codes: Vec<String>,
+    /// `host:port` of a `clamd` daemon for [`crate::scanning::ClamdScanner`]
+    /// to scan attachment uploads against. `None` falls back to
+    /// [`crate::scanning::NoopScanner`], same as leaving `smtp_host` unset
+    /// falls back to [`crate::mail::LoggingMailer`].
+    pub clamd_address: Option<String>,
+    /// Issuer base URL of an external OpenID Connect provider (e.g. an
+    /// Authelia or Keycloak instance) for [`crate::oidc`]. `None` disables
+    /// `/login/oidc/start` and `/login/oidc/callback` entirely, same as
+    /// leaving `smtp_host`/`clamd_address`/`rate_provider_url` unset
+    /// disables their respective features.
+    pub oidc_issuer: Option<String>,
+    pub oidc_client_id: String,
+    pub oidc_client_secret: String,
+    /// Must exactly match the redirect URI registered with the provider.
+    pub oidc_redirect_url: String,
+    /// How long a bearer token from [`crate::models::session::NewSession`]
+    /// stays accepted by [`crate::models::session::find_active`], counted
+    /// from `created_at` rather than sliding on each use. Defaults to 30
+    /// days. Sessions are opaque DB-backed tokens, not JWTs (see
+    /// [`crate::models::session::Session`]'s doc comment) — there's no
+    /// signing key to rotate, so this TTL is the closest real analogue to
+    /// "configurable token expiry" the codebase actually has.
+    pub session_ttl_minutes: i64,
+    /// Base URL of an LLM completion provider for
+    /// [`crate::llm::HttpLlmProvider`], used by `POST /api/entry/parse` to
+    /// propose entry fields from a free-text line once the historical-match
+    /// heuristic comes up empty. `None` disables the LLM fallback entirely,
+    /// same as leaving `rate_provider_url`/`smtp_host` unset disables their
+    /// respective features — the endpoint still works off history alone.
+    pub llm_provider_url: Option<String>,
+    pub llm_api_key: String,
+    /// Bot token from `@BotFather` for [`crate::telegram`]. `None`
+    /// disables the `/api/telegram/webhook` ingestion endpoint entirely,
+    /// same "unset disables the feature" convention as `smtp_host`/
+    /// `oidc_issuer`/`llm_provider_url`.
+    pub telegram_bot_token: Option<String>,
+    /// Secret token passed to Telegram's `setWebhook` call and checked
+    /// against the `X-Telegram-Bot-Api-Secret-Token` header on every
+    /// `POST /api/telegram/webhook` request — without it, anyone who
+    /// guesses or observes a linked `chat_id` could forge updates straight
+    /// into the endpoint without ever going through Telegram. `None`
+    /// leaves the endpoint unprotected, same as leaving `telegram_bot_token`
+    /// unset disables it outright; set both before exposing it publicly.
+    pub telegram_webhook_secret: Option<String>,
+    /// Base URL of a GoCardless/Nordigen-style bank account data API for
+    /// [`crate::jobs::bank_sync::HttpBankProvider`]. `None` disables
+    /// `POST /api/source/{name}/bank-sync` entirely, same "unset disables
+    /// the feature" convention as `llm_provider_url`/`telegram_bot_token`.
+    pub bank_provider_url: Option<String>,
+    /// `host:port` [`crate::grpc`] binds its `SyncService` to, for
+    /// mobile/CLI clients that prefer protobuf over JSON. `None` disables
+    /// the gRPC server entirely, same "unset disables the feature"
+    /// convention as `bank_provider_url`/`telegram_bot_token` — the REST
+    /// API is unaffected either way.
+    pub grpc_bind_address: Option<String>,
+}
+
+impl AppConfig {
+    pub fn from_env() -> Self {
+        let strict_mode = std::env::var("STRICT_MODE")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        let attachments_dir = std::env::var("ATTACHMENTS_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from("./attachments"));
+        let max_attachment_bytes = std::env::var("MAX_ATTACHMENT_BYTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(10 * 1024 * 1024);
+        let rate_provider_url = std::env::var("RATE_PROVIDER_URL").ok();
+        let smtp_host = std::env::var("SMTP_HOST").ok();
+        let smtp_port = std::env::var("SMTP_PORT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(587);
+        let smtp_username = std::env::var("SMTP_USERNAME").unwrap_or_default();
+        let smtp_password = std::env::var("SMTP_PASSWORD").unwrap_or_default();
+        let smtp_from = std::env::var("SMTP_FROM").unwrap_or_else(|_| "no-reply@money-rs.local".into());
+        let password_reset_ttl_minutes = std::env::var("PASSWORD_RESET_TTL_MINUTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30);
+        let log_level = std::env::var("LOG_LEVEL").unwrap_or_else(|_| "info".into());
+        let log_dir = std::env::var("LOG_DIR").ok().map(PathBuf::from);
+        let log_rotation = match std::env::var("LOG_ROTATION").ok() {
+            Some(v) => match v.strip_prefix("size:").and_then(|n| n.parse().ok()) {
+                Some(bytes) => LogRotation::SizeBytes(bytes),
+                None => LogRotation::Daily,
+            },
+            None => LogRotation::Daily,
+        };
+        let registration_mode = match std::env::var("REGISTRATION_MODE").ok().as_deref() {
+            Some("open") => RegistrationMode::Open,
+            Some("invite-code") => RegistrationMode::InviteCode,
+            _ => RegistrationMode::Disabled,
+        };
+        let invite_codes = std::env::var("REGISTRATION_INVITE_CODES")
+            .ok()
+            .map(|v| v.split(',').map(str::trim).filter(|c| !c.is_empty()).map(String::from).collect())
+            .unwrap_or_default();
+        let clamd_address = std::env::var("CLAMD_ADDRESS").ok();
+        let oidc_issuer = std::env::var("OIDC_ISSUER").ok();
+        let oidc_client_id = std::env::var("OIDC_CLIENT_ID").unwrap_or_default();
+        let oidc_client_secret = std::env::var("OIDC_CLIENT_SECRET").unwrap_or_default();
+        let oidc_redirect_url = std::env::var("OIDC_REDIRECT_URL").unwrap_or_default();
+        let session_ttl_minutes = std::env::var("SESSION_TTL_MINUTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30 * 24 * 60);
+        let llm_provider_url = std::env::var("LLM_PROVIDER_URL").ok();
+        let llm_api_key = std::env::var("LLM_API_KEY").unwrap_or_default();
+        let telegram_bot_token = std::env::var("TELEGRAM_BOT_TOKEN").ok();
+        let telegram_webhook_secret = std::env::var("TELEGRAM_WEBHOOK_SECRET").ok();
+        let bank_provider_url = std::env::var("BANK_PROVIDER_URL").ok();
+        let grpc_bind_address = std::env::var("GRPC_BIND_ADDRESS").ok();
+
+        Self {
+            strict_mode,
+            attachments_dir,
+            max_attachment_bytes,
+            rate_provider_url,
+            smtp_host,
+            smtp_port,
+            smtp_username,
+            smtp_password,
+            smtp_from,
+            password_reset_ttl_minutes,
+            log_level,
+            log_dir,
+            log_rotation,
+            registration_mode,
+            invite_codes,
+            clamd_address,
+            oidc_issuer,
+            oidc_client_id,
+            oidc_client_secret,
+            oidc_redirect_url,
+            session_ttl_minutes,
+            llm_provider_url,
+            llm_api_key,
+            telegram_bot_token,
+            telegram_webhook_secret,
+            bank_provider_url,
+            grpc_bind_address,
+        }
+    }
+}
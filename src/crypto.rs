@@ -0,0 +1,52 @@
+//! At-rest encryption for free-text entry fields (`description`, `notes`)
+//! under the per-user privacy mode. The key is never stored: it is derived
+//! from the user's password (or an explicit passphrase) and only lives for
+//! the duration of a request that carries an unlock header.
+
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use rand::RngCore;
+
+pub const UNLOCK_HEADER: &str = "X-Session-Unlock";
+
+/// Derives a 256-bit key from a passphrase and the user's salt, under the
+/// same Argon2id cost parameters [`crate::password`] hashes logins with —
+/// this is the one place in the app that protects data from whoever holds
+/// the disk, so it gets the same deliberately-expensive KDF a login does,
+/// not a fast general-purpose hash.
+pub fn derive_key(passphrase: &str, salt: &[u8]) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    crate::password::argon2()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .expect("argon2id derivation does not fail for well-formed input");
+    key
+}
+
+/// Encrypts `plaintext`, returning `nonce || ciphertext` so both can be
+/// stored in a single text column.
+pub fn encrypt(key: &[u8; 32], plaintext: &str) -> Vec<u8> {
+    let cipher = Aes256Gcm::new_from_slice(key).expect("key is 32 bytes");
+    let mut nonce_bytes = [0u8; 12];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let mut ciphertext = cipher
+        .encrypt(nonce, plaintext.as_bytes())
+        .expect("encryption does not fail for well-formed input");
+
+    let mut out = nonce_bytes.to_vec();
+    out.append(&mut ciphertext);
+    out
+}
+
+/// Reverses [`encrypt`]. Returns `None` if the key is wrong or the blob is
+/// malformed rather than panicking, since a bad unlock header is user error.
+pub fn decrypt(key: &[u8; 32], blob: &[u8]) -> Option<String> {
+    if blob.len() < 12 {
+        return None;
+    }
+    let (nonce_bytes, ciphertext) = blob.split_at(12);
+    let cipher = Aes256Gcm::new_from_slice(key).ok()?;
+    let plaintext = cipher.decrypt(Nonce::from_slice(nonce_bytes), ciphertext).ok()?;
+    String::from_utf8(plaintext).ok()
+}
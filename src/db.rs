@@ -0,0 +1,76 @@
+//! Scoped out of this pool/connection layer: a full port to `diesel-async`
+//! (bb8/deadpool-backed, non-blocking queries) so handlers stop parking an
+//! actix worker thread on every DB call.
+//!
+//! Filed as TODO(75) against `AppState`/`get_impls!` — neither exists in
+//! this codebase; the real equivalents are [`DbPool`]/[`ReportsPool`] here
+//! and [`cpool`]/[`crate::stateful::StatefulTryFrom`] for the connection
+//! handoff. Even accounting for the naming drift, porting those properly
+//! means rewriting every handler's `&mut PgConnection` parameter to an
+//! async connection type, swapping r2d2 for bb8 or deadpool-diesel, and
+//! re-threading every `StatefulTryFrom` impl and `diesel::sql_query` call
+//! through `.await` — on the order of every file under `src/handlers` and
+//! `src/models`, not something one commit can safely do blind in a tree
+//! with no `Cargo.toml`/compiler to check the result against. Queued as
+//! its own tracked migration rather than attempted here half-finished;
+//! [`crate::db::cpool`]'s `DB_POOL_ACQUIRE_TIMEOUT_MS`-bounded wait and
+//! `AppError::Unavailable` result keep blocking acquisition from panicking
+//! a worker in the meantime.
+
+use std::time::Duration;
+
+use diesel::pg::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool, PooledConnection};
+
+use crate::error::AppError;
+
+pub type DbPool = Pool<ConnectionManager<PgConnection>>;
+pub type DbConn = PooledConnection<ConnectionManager<PgConnection>>;
+
+pub fn build_pool(database_url: &str) -> DbPool {
+    let max_size = std::env::var("CRUD_POOL_SIZE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(10);
+    build_pool_sized(database_url, max_size)
+}
+
+fn build_pool_sized(database_url: &str, max_size: u32) -> DbPool {
+    let acquire_timeout_ms = std::env::var("DB_POOL_ACQUIRE_TIMEOUT_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(5_000);
+    let manager = ConnectionManager::<PgConnection>::new(database_url);
+    Pool::builder()
+        .max_size(max_size)
+        .connection_timeout(Duration::from_millis(acquire_timeout_ms))
+        .build(manager)
+        .expect("failed to create db connection pool")
+}
+
+/// A second, separately-sized pool for heavy report/export queries (large
+/// table scans, aggregations) so a burst of that traffic can't starve
+/// `DbPool`, which cheap CRUD handlers depend on to stay responsive.
+///
+/// Wrapped rather than reusing `DbPool` so the two can be registered as
+/// distinct `web::Data` extractors; existing handlers keep using `DbPool`
+/// unchanged.
+#[derive(Clone)]
+pub struct ReportsPool(pub DbPool);
+
+pub fn build_reports_pool(database_url: &str) -> ReportsPool {
+    let max_size = std::env::var("REPORTS_POOL_SIZE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(2);
+    ReportsPool(build_pool_sized(database_url, max_size))
+}
+
+/// Grabs a connection from the pool, waiting up to `DB_POOL_ACQUIRE_TIMEOUT_MS`
+/// (default 5s) before giving up. Returns [`AppError::Unavailable`] instead
+/// of panicking on exhaustion/a dead database, so a burst of traffic or a
+/// blip in Postgres surfaces to the caller as a 503 it can retry rather
+/// than killing the worker.
+pub fn cpool(pool: &DbPool) -> Result<DbConn, AppError> {
+    pool.get().map_err(|e| AppError::Unavailable(format!("database connection pool exhausted: {e}")))
+}
@@ -0,0 +1,57 @@
+//! Seed data for `--ephemeral` mode: enough currencies, a source, and a
+//! category to poke at the API immediately without a real signup flow.
+
+use diesel::prelude::*;
+
+use crate::models::category::NewCategory;
+use crate::models::currency::NewCurrency;
+use crate::models::source::NewSource;
+use crate::models::user::NewUser;
+use crate::money::Money;
+use crate::schema::{categories, currencies, sources, users};
+
+pub fn seed(conn: &mut PgConnection) -> Result<(), diesel::result::Error> {
+    let user = diesel::insert_into(users::table)
+        .values(NewUser {
+            email: "demo@example.com".into(),
+            password_hash: "not-a-real-hash".into(),
+            oidc_subject: None,
+        })
+        .get_result::<crate::models::user::User>(conn)?;
+
+    let usd = diesel::insert_into(currencies::table)
+        .values(NewCurrency {
+            code: "USD".into(),
+            name: "US Dollar".into(),
+            rate_to_fixed: 1.0,
+            symbol: Some("$".into()),
+        })
+        .get_result::<crate::models::currency::Currency>(conn)?;
+
+    diesel::insert_into(currencies::table)
+        .values(NewCurrency {
+            code: "EGP".into(),
+            name: "Egyptian Pound".into(),
+            rate_to_fixed: 1.0 / 48.0,
+            symbol: Some("E£".into()),
+        })
+        .execute(conn)?;
+
+    diesel::insert_into(sources::table)
+        .values(NewSource {
+            user_id: user.id,
+            name: "Wallet".into(),
+            currency_id: usd.id,
+            amount: Money::ZERO,
+        })
+        .execute(conn)?;
+
+    diesel::insert_into(categories::table)
+        .values(NewCategory {
+            user_id: user.id,
+            name: "Groceries".into(),
+        })
+        .execute(conn)?;
+
+    Ok(())
+}
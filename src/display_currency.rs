@@ -0,0 +1,66 @@
+//! Resolves the currency statistics endpoints should convert their
+//! aggregates into: an explicit `display_currency` query override, an
+//! `X-Display-Currency` header (see [`header_override`]), or the
+//! requesting user's `fixed_currency_id` (see
+//! [`crate::jobs::fixed_currency`]) when neither is set. None of the three
+//! being present means "show figures in their own native currency" —
+//! callers get `None` back and skip conversion.
+
+use actix_web::HttpRequest;
+use diesel::prelude::*;
+
+use crate::error::AppError;
+use crate::models::currency::Currency;
+use crate::models::user::User;
+use crate::money::Money;
+use crate::schema::{currencies, users};
+
+/// Reads `X-Display-Currency`, for a client-side currency switcher that
+/// wants a one-request override without persisting anything — sending a
+/// header on every request it's already making, rather than a
+/// `PATCH /api/me` round-trip to change `fixed_currency_id` and another to
+/// change it back. Takes lower precedence than an explicit
+/// `display_currency` query param, same as [`resolve`] takes lower
+/// precedence over the stored preference than either.
+pub fn header_override(req: &HttpRequest) -> Option<String> {
+    req.headers().get("X-Display-Currency")?.to_str().ok().map(str::to_string)
+}
+
+pub fn resolve(
+    conn: &mut PgConnection,
+    user_id: i32,
+    override_code: Option<&str>,
+) -> Result<Option<Currency>, AppError> {
+    if let Some(code) = override_code {
+        let currency = currencies::table
+            .filter(currencies::code.eq(code))
+            .filter(currencies::archived.eq(false))
+            .select(Currency::as_select())
+            .first::<Currency>(conn)
+            .map_err(|_| AppError::NotFound(format!("currency {code} not found")))?;
+        return Ok(Some(currency));
+    }
+
+    let user = users::table
+        .find(user_id)
+        .select(User::as_select())
+        .first::<User>(conn)
+        .map_err(|_| AppError::NotFound(format!("user {user_id} not found")))?;
+
+    match user.fixed_currency_id {
+        Some(currency_id) => currencies::table
+            .find(currency_id)
+            .select(Currency::as_select())
+            .first::<Currency>(conn)
+            .optional()
+            .map_err(AppError::from),
+        None => Ok(None),
+    }
+}
+
+/// Converts `amount`, denominated in `from`, into `to`. Returns a plain
+/// `f64`: this is a read-only display figure, never persisted, so it
+/// doesn't need `Money`'s exactness — only the ledger itself does.
+pub fn convert(from: &Currency, to: &Currency, amount: Money) -> f64 {
+    amount.to_f64_lossy() * from.rate_to_fixed / to.rate_to_fixed
+}
@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+use crate::models::alert::AlertDirection;
+
+/// Body of `POST /api/alerts`.
+#[derive(Deserialize, Debug)]
+pub struct CreateAlertRequest {
+    pub user_id: i32,
+    pub base_currency_id: i32,
+    pub quote_currency_id: i32,
+    pub threshold: f64,
+    pub direction: AlertDirection,
+}
@@ -0,0 +1,90 @@
+//! Wire format for `/api/export/full` and `/api/import/full`. Deliberately
+//! narrower than the full schema — splits and attachments aren't carried
+//! yet — so a restore lands the ledger a user actually cares about
+//! recovering without dragging in every ancillary table on day one.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::category::Category;
+use crate::models::currency::Currency;
+use crate::models::entry::{Entry, EntryType};
+use crate::models::source::Source;
+use crate::money::Money;
+
+pub const BACKUP_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FullBackup {
+    pub version: u32,
+    pub currencies: Vec<CurrencyBackup>,
+    pub sources: Vec<SourceBackup>,
+    pub categories: Vec<CategoryBackup>,
+    pub entries: Vec<EntryBackup>,
+}
+
+/// Currencies are global, not per-user, so they're keyed by `code` (their
+/// active-unique key) rather than re-exported id.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CurrencyBackup {
+    pub code: String,
+    pub name: String,
+    pub rate_to_fixed: f64,
+}
+
+impl From<&Currency> for CurrencyBackup {
+    fn from(c: &Currency) -> Self {
+        Self { code: c.code.clone(), name: c.name.clone(), rate_to_fixed: c.rate_to_fixed }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SourceBackup {
+    /// Id as it appeared at export time; only used to resolve
+    /// `entries[].source_id` within this document, not stored verbatim.
+    pub id: i32,
+    pub name: String,
+    pub currency_code: String,
+    pub amount: Money,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CategoryBackup {
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EntryBackup {
+    pub source_id: i32,
+    pub secondary_source_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub currency_code: String,
+    pub entry_type: EntryType,
+    pub amount: Money,
+    pub description: Option<String>,
+    pub notes: Option<String>,
+    pub entry_date: DateTime<Utc>,
+}
+
+pub fn source_backup(source: &Source, currency_code: &str) -> SourceBackup {
+    SourceBackup { id: source.id, name: source.name.clone(), currency_code: currency_code.to_string(), amount: source.amount }
+}
+
+pub fn category_backup(category: &Category) -> CategoryBackup {
+    CategoryBackup { id: category.id, name: category.name.clone() }
+}
+
+pub fn entry_backup(entry: &Entry, currency_code: &str) -> EntryBackup {
+    EntryBackup {
+        source_id: entry.source_id,
+        secondary_source_id: entry.secondary_source_id,
+        category_id: entry.category_id,
+        currency_code: currency_code.to_string(),
+        entry_type: entry.entry_type,
+        amount: entry.amount,
+        description: entry.description.clone(),
+        notes: entry.notes.clone(),
+        entry_date: entry.entry_date,
+    }
+}
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::models::entry::EntryType;
+use crate::models::entry_split::SplitAllocation;
+use crate::money::Money;
+
+/// Body of `POST /api/entries`.
+///
+/// Deliberately has no `conversion_rate` / `conversion_rate_to_fixed`
+/// fields: those are always derived server-side, never trusted from the
+/// client.
+#[derive(Deserialize, Debug)]
+pub struct CreateEntryRequest {
+    pub user_id: i32,
+    pub source_id: i32,
+    pub secondary_source_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub currency_id: i32,
+    pub entry_type: EntryType,
+    pub amount: Money,
+    /// Deprecated: prefer `counterparty_id`. Kept for older clients; new
+    /// entries should resolve a [`crate::models::counterparty::Counterparty`]
+    /// instead of storing free text.
+    pub target: Option<String>,
+    pub counterparty_id: Option<i32>,
+    /// Who paid this entry, for `EntryType::Income` only — see
+    /// [`crate::models::payer::Payer`]. Rejected on any other entry type.
+    pub payer_id: Option<i32>,
+    pub description: Option<String>,
+    pub notes: Option<String>,
+    pub entry_date: DateTime<Utc>,
+    /// Splits this entry's `amount` across multiple categories. When
+    /// present, `category_id` is ignored for aggregation purposes in favor
+    /// of these allocations, which must sum to `amount`.
+    pub splits: Option<Vec<SplitAllocation>>,
+    /// Values for this user's [`crate::models::custom_field::CustomFieldDefinition`]s,
+    /// keyed by definition `key`. Each value is validated against its
+    /// definition's `field_type` and rejected if the key isn't one this
+    /// user has configured.
+    #[serde(default)]
+    pub custom: HashMap<String, serde_json::Value>,
+}
@@ -0,0 +1,6 @@
+pub mod alert;
+pub mod backup;
+pub mod entry;
+pub mod print_view;
+pub mod simulate;
+pub mod user;
@@ -0,0 +1,21 @@
+use serde::Deserialize;
+
+/// Body of `POST /api/views/print`. `columns` selects and orders which
+/// entry fields appear in the rendered table.
+#[derive(Deserialize, Debug)]
+pub struct PrintViewRequest {
+    pub user_id: i32,
+    pub columns: Vec<String>,
+    #[serde(default = "default_page")]
+    pub page: i64,
+    #[serde(default = "default_per_page")]
+    pub per_page: i64,
+}
+
+fn default_page() -> i64 {
+    1
+}
+
+fn default_per_page() -> i64 {
+    50
+}
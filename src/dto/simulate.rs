@@ -0,0 +1,68 @@
+//! Wire format for `POST /api/simulate` (`handlers::simulate::simulate`).
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::entry::EntryType;
+use crate::money::Money;
+
+#[derive(Deserialize, Debug)]
+pub struct SimulateRequest {
+    pub user_id: i32,
+    /// How many calendar months forward to project, starting with the
+    /// current one.
+    pub months: u32,
+    #[serde(default)]
+    pub hypothetical_entries: Vec<HypotheticalEntry>,
+    #[serde(default)]
+    pub budget_changes: Vec<HypotheticalBudgetChange>,
+}
+
+/// An entry that doesn't exist yet — never written to `entries`, only fed
+/// into [`crate::jobs::simulate::project`] alongside the user's real
+/// recurring templates.
+#[derive(Deserialize, Debug, Clone)]
+pub struct HypotheticalEntry {
+    pub source_id: i32,
+    pub category_id: Option<i32>,
+    pub entry_type: EntryType,
+    pub amount: Money,
+    pub entry_date: DateTime<Utc>,
+    /// When `true`, this entry is projected into every month from
+    /// `entry_date` onward instead of just the one it falls in — for
+    /// modeling "what if I started a $50/month subscription".
+    #[serde(default)]
+    pub repeat_monthly: bool,
+}
+
+/// Overrides an existing monthly budget's limit (or adds one that doesn't
+/// exist yet) for the purposes of this simulation only — never persisted.
+#[derive(Deserialize, Debug, Clone)]
+pub struct HypotheticalBudgetChange {
+    pub category_id: i32,
+    pub new_limit: Money,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BudgetProjection {
+    pub category_id: i32,
+    pub limit: Money,
+    pub projected_spent: Money,
+    pub remaining: Money,
+}
+
+#[derive(Serialize, Debug)]
+pub struct MonthProjection {
+    pub month_index: u32,
+    pub month_start: DateTime<Utc>,
+    pub projected_balances: HashMap<i32, Money>,
+    pub category_totals: HashMap<i32, Money>,
+    pub budget_projection: Vec<BudgetProjection>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SimulationReport {
+    pub months: Vec<MonthProjection>,
+}
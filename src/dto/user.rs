@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::models::user::User;
+use crate::schema::users;
+
+/// Body of `GET /api/me`'s response. A dedicated DTO rather than
+/// serializing [`User`] directly so the wire shape stays deliberate even
+/// if the row grows more internal-only columns later (`privacy_salt` is
+/// already `#[serde(skip_serializing)]` on `User`, but new fields default
+/// to serialized, not hidden).
+#[derive(Serialize, Debug)]
+pub struct UserResponse {
+    pub id: i32,
+    pub email: String,
+    pub privacy_mode: bool,
+    pub created_at: DateTime<Utc>,
+    pub fixed_currency_id: Option<i32>,
+    pub enabled: bool,
+}
+
+impl From<&User> for UserResponse {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.id,
+            email: user.email.clone(),
+            privacy_mode: user.privacy_mode,
+            created_at: user.created_at,
+            fixed_currency_id: user.fixed_currency_id,
+            enabled: !user.disabled,
+        }
+    }
+}
+
+/// Body of `POST /login`.
+#[derive(Deserialize, Debug)]
+pub struct LoginRequest {
+    pub email: String,
+    pub password: String,
+    /// A caller-chosen label ("Sarah's iPhone", "work laptop") shown back
+    /// by `GET /api/me/sessions` — there's no way to infer one server-side
+    /// from a bearer token the way a User-Agent sniff might guess at.
+    #[serde(default)]
+    pub device_name: Option<String>,
+}
+
+/// One row of `GET /api/me/sessions`'s response.
+#[derive(Serialize, Debug)]
+pub struct SessionResponse {
+    pub id: i32,
+    pub device_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub last_used_ip: Option<String>,
+    pub revoked: bool,
+    pub is_current: bool,
+}
+
+/// Body of `POST /login`'s successful response: the account plus a bearer
+/// token for [`crate::auth::AuthUser`], revocable via `POST /logout`.
+#[derive(Serialize, Debug)]
+pub struct LoginResponse {
+    #[serde(flatten)]
+    pub user: UserResponse,
+    pub token: String,
+}
+
+/// Body of `POST /password-reset/request`.
+#[derive(Deserialize, Debug)]
+pub struct RequestPasswordResetRequest {
+    pub email: String,
+}
+
+/// Body of `POST /password-reset/confirm`.
+#[derive(Deserialize, Debug)]
+pub struct ConfirmPasswordResetRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+/// Body of `PATCH /api/me`. Fields left `None` are left unchanged.
+/// Deliberately excludes `password_hash` (its own reset flow),
+/// `fixed_currency_id` (its own endpoint, since changing it triggers a
+/// system-wide rebase — see [`crate::jobs::fixed_currency`]), and
+/// `disabled` (administrative, not self-service).
+#[derive(Deserialize, AsChangeset, Debug)]
+#[diesel(table_name = users)]
+pub struct UpdateProfileRequest {
+    pub email: Option<String>,
+    pub privacy_mode: Option<bool>,
+}
@@ -0,0 +1,246 @@
+use actix_web::{HttpResponse, ResponseError};
+use serde::Serialize;
+use std::fmt;
+
+use crate::validation::FieldError;
+
+/// Top-level error type returned by handlers. Every variant maps to a
+/// concrete HTTP status in `ResponseError::error_response`.
+#[derive(Debug)]
+pub enum AppError {
+    NotFound(String),
+    Validation(String),
+    Conflict(String),
+    Unauthorized(String),
+    Internal(String),
+    /// The server can't serve this request right now but might shortly —
+    /// pool exhaustion ([`crate::db::cpool`]) or a database the instance
+    /// can't currently reach. Distinct from `Internal` because a client
+    /// (or a load balancer) should retry this one instead of giving up.
+    Unavailable(String),
+    /// One or more request fields failed [`crate::validation::Validator`]
+    /// checks. Distinct from the single-message `Validation` above: this
+    /// carries every failing field at once (so a client doesn't have to
+    /// fix one, resubmit, and hit the next) and maps to 422 rather than
+    /// 400, since the request is well-formed JSON that just fails
+    /// field-level checks.
+    FieldValidation(Vec<FieldError>),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::NotFound(msg) => write!(f, "not found: {msg}"),
+            AppError::Validation(msg) => write!(f, "validation error: {msg}"),
+            AppError::Conflict(msg) => write!(f, "conflict: {msg}"),
+            AppError::Unauthorized(msg) => write!(f, "unauthorized: {msg}"),
+            AppError::Internal(msg) => write!(f, "internal error: {msg}"),
+            AppError::Unavailable(msg) => write!(f, "service unavailable: {msg}"),
+            AppError::FieldValidation(errors) => {
+                write!(f, "validation error: ")?;
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}: {}", error.field, error.message)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+#[derive(Serialize)]
+struct FieldValidationBody<'a> {
+    error: String,
+    errors: &'a [FieldError],
+}
+
+impl ResponseError for AppError {
+    fn error_response(&self) -> HttpResponse {
+        match self {
+            AppError::NotFound(_) => HttpResponse::NotFound().json(ErrorBody { error: self.to_string() }),
+            AppError::Validation(_) => HttpResponse::BadRequest().json(ErrorBody { error: self.to_string() }),
+            AppError::Conflict(_) => HttpResponse::Conflict().json(ErrorBody { error: self.to_string() }),
+            AppError::Unauthorized(_) => HttpResponse::Unauthorized().json(ErrorBody { error: self.to_string() }),
+            AppError::Internal(_) => HttpResponse::InternalServerError().json(ErrorBody { error: self.to_string() }),
+            AppError::Unavailable(_) => HttpResponse::ServiceUnavailable()
+                .insert_header(("Retry-After", "1"))
+                .json(ErrorBody { error: self.to_string() }),
+            AppError::FieldValidation(errors) => {
+                HttpResponse::UnprocessableEntity().json(FieldValidationBody { error: self.to_string(), errors })
+            }
+        }
+    }
+}
+
+/// Unique indexes whose name doesn't follow Postgres's default
+/// `<table>_<column>_key`, because the migration that created them used
+/// `CREATE UNIQUE INDEX` with an explicit name instead of an inline
+/// `UNIQUE` column constraint. Keyed by index name, mapping to the field
+/// a client should be told conflicted.
+fn named_unique_index_field(constraint: &str) -> Option<&'static str> {
+    match constraint {
+        "currencies_code_active_key" => Some("code"),
+        "currency_rates_currency_date_key" => Some("effective_date"),
+        "sync_client_mutations_client_id_idx" => Some("client_id"),
+        "password_reset_tokens_token_idx" => Some("token"),
+        "entry_custom_field_values_entry_definition_idx" => Some("definition_id"),
+        "custom_field_definitions_user_key_idx" => Some("key"),
+        "sessions_token_idx" => Some("token"),
+        "saved_queries_user_name_idx" => Some("name"),
+        _ => None,
+    }
+}
+
+/// Best-effort field name for a `UniqueViolation`: first the explicitly
+/// named indexes above, then Postgres's own default naming for a plain
+/// inline `UNIQUE` column (`<table>_<column>_key`) using the table and
+/// constraint names Postgres reports alongside the error.
+fn unique_violation_field(table: Option<&str>, constraint: Option<&str>) -> Option<String> {
+    let constraint = constraint?;
+    if let Some(field) = named_unique_index_field(constraint) {
+        return Some(field.to_string());
+    }
+    let table = table?;
+    constraint.strip_prefix(&format!("{table}_"))?.strip_suffix("_key").map(String::from)
+}
+
+/// Best-effort field name for a `ForeignKeyViolation`: every `REFERENCES`
+/// in `migrations/*/up.sql` relies on Postgres's default FK naming
+/// (`<table>_<column>_fkey`) rather than an explicit `CONSTRAINT` name, so
+/// the column can be recovered the same way as the unique-index default
+/// case above.
+fn foreign_key_violation_field(table: Option<&str>, constraint: Option<&str>) -> Option<String> {
+    let table = table?;
+    let constraint = constraint?;
+    constraint.strip_prefix(&format!("{table}_"))?.strip_suffix("_fkey").map(String::from)
+}
+
+/// There's no `create_handler!` macro in this codebase for this to hook
+/// into generically (handlers call `diesel::insert_into`/`diesel::update`
+/// directly and rely on `?` converting the resulting
+/// `diesel::result::Error` through this impl) — so the friendlier message
+/// is produced here, once, rather than at each of the ~20 create/update
+/// call sites across `src/handlers/`.
+impl From<diesel::result::Error> for AppError {
+    fn from(err: diesel::result::Error) -> Self {
+        match err {
+            diesel::result::Error::NotFound => AppError::NotFound(err.to_string()),
+            diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, ref info) => {
+                match unique_violation_field(info.table_name(), info.constraint_name()) {
+                    Some(field) => AppError::Conflict(format!("{field} already exists")),
+                    None => AppError::Conflict(info.message().to_string()),
+                }
+            }
+            diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::ForeignKeyViolation, ref info) => {
+                match foreign_key_violation_field(info.table_name(), info.constraint_name()) {
+                    Some(field) => AppError::Validation(format!("{field} references a record that doesn't exist")),
+                    None => AppError::Validation(info.message().to_string()),
+                }
+            }
+            other => AppError::Internal(other.to_string()),
+        }
+    }
+}
+
+/// Maps the error from an update-or-archive lookup (`diesel::update(table.find(id))
+/// .get_result()`) to a precise status instead of collapsing everything into 404:
+/// a genuinely missing row is `NotFound`, but a constraint violation triggered by
+/// the write itself (e.g. an update that would duplicate a unique column) means the
+/// row *was* found — that's a [`AppError::Conflict`] or [`AppError::Validation`],
+/// not a 404. `not_found_msg` names the specific resource for the `NotFound` case.
+pub fn map_update_error(err: diesel::result::Error, not_found_msg: impl Into<String>) -> AppError {
+    match err {
+        diesel::result::Error::NotFound => AppError::NotFound(not_found_msg.into()),
+        other => other.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use diesel::result::{DatabaseErrorInformation, DatabaseErrorKind, Error as DieselError};
+
+    use super::*;
+
+    struct FakeDbError {
+        message: String,
+        table_name: Option<String>,
+        constraint_name: Option<String>,
+    }
+
+    impl DatabaseErrorInformation for FakeDbError {
+        fn message(&self) -> &str {
+            &self.message
+        }
+        fn details(&self) -> Option<&str> {
+            None
+        }
+        fn hint(&self) -> Option<&str> {
+            None
+        }
+        fn table_name(&self) -> Option<&str> {
+            self.table_name.as_deref()
+        }
+        fn column_name(&self) -> Option<&str> {
+            None
+        }
+        fn constraint_name(&self) -> Option<&str> {
+            self.constraint_name.as_deref()
+        }
+        fn statement_position(&self) -> Option<i32> {
+            None
+        }
+    }
+
+    fn db_error(kind: DatabaseErrorKind, info: FakeDbError) -> DieselError {
+        DieselError::DatabaseError(kind, Box::new(info))
+    }
+
+    #[test]
+    fn map_update_error_missing_row_is_404() {
+        let err = map_update_error(DieselError::NotFound, "user 1 not found");
+        assert_eq!(err.error_response().status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn map_update_error_unique_violation_is_409() {
+        let info = FakeDbError {
+            message: "duplicate key".into(),
+            table_name: Some("currencies".into()),
+            constraint_name: Some("currencies_code_active_key".into()),
+        };
+        let err = map_update_error(db_error(DatabaseErrorKind::UniqueViolation, info), "currency not found");
+        assert_eq!(err.error_response().status(), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn map_update_error_foreign_key_violation_is_400() {
+        let info = FakeDbError {
+            message: "fk violation".into(),
+            table_name: Some("entries".into()),
+            constraint_name: Some("entries_source_id_fkey".into()),
+        };
+        let err = map_update_error(db_error(DatabaseErrorKind::ForeignKeyViolation, info), "entry not found");
+        assert_eq!(err.error_response().status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn unique_violation_derives_field_name_from_named_index() {
+        let info = FakeDbError {
+            message: "duplicate key".into(),
+            table_name: Some("currencies".into()),
+            constraint_name: Some("currencies_code_active_key".into()),
+        };
+        let err: AppError = db_error(DatabaseErrorKind::UniqueViolation, info).into();
+        assert!(matches!(err, AppError::Conflict(ref msg) if msg == "code already exists"));
+    }
+}
@@ -0,0 +1,69 @@
+//! Renders the ledger as ledger-cli plain text: same account mapping as
+//! [`crate::export::beancount`] (sources under `Assets:`, categories under
+//! `Expenses:`) but ledger-cli's own transaction/posting syntax, for users
+//! whose plaintext-accounting tooling is `ledger`/`hledger` rather than
+//! `beancount`.
+
+use std::collections::HashMap;
+
+use crate::models::category::Category;
+use crate::models::currency::Currency;
+use crate::models::entry::{Entry, EntryType};
+use crate::models::source::Source;
+
+fn account_name(kind: &str, name: &str) -> String {
+    format!("{kind}:{}", name.replace(' ', "-"))
+}
+
+pub fn render(
+    entries: &[Entry],
+    sources: &[Source],
+    categories: &[Category],
+    currencies: &[Currency],
+) -> String {
+    let source_names: HashMap<i32, &str> = sources.iter().map(|s| (s.id, s.name.as_str())).collect();
+    let category_names: HashMap<i32, &str> = categories.iter().map(|c| (c.id, c.name.as_str())).collect();
+    let currency_codes: HashMap<i32, &str> = currencies.iter().map(|c| (c.id, c.code.as_str())).collect();
+
+    let mut out = String::new();
+    for entry in entries {
+        let Some(&source) = source_names.get(&entry.source_id) else { continue };
+        let Some(&code) = currency_codes.get(&entry.currency_id) else { continue };
+        let source_account = account_name("Assets", source);
+        let date = entry.entry_date.format("%Y/%m/%d");
+        let narration = entry.description.clone().unwrap_or_else(|| "(no description)".to_string());
+
+        out.push_str(&format!("{date} {narration}\n"));
+        match entry.entry_type {
+            EntryType::Spend => {
+                let category = entry
+                    .category_id
+                    .and_then(|id| category_names.get(&id))
+                    .map(|c| account_name("Expenses", c))
+                    .unwrap_or_else(|| "Expenses:Uncategorized".to_string());
+                out.push_str(&format!("    {category}  {code} {:.2}\n", entry.amount));
+                out.push_str(&format!("    {source_account}\n\n"));
+            }
+            EntryType::Income => {
+                out.push_str(&format!("    Income:Unknown  {code} -{:.2}\n", entry.amount));
+                out.push_str(&format!("    {source_account}\n\n"));
+            }
+            EntryType::Convert => {
+                let Some(secondary) = entry.secondary_source_id.and_then(|id| source_names.get(&id)) else {
+                    continue;
+                };
+                let secondary_account = account_name("Assets", secondary);
+                out.push_str(&format!(
+                    "    {source_account}  {code} -{:.2} @ {code} {:.6}\n",
+                    entry.amount, entry.conversion_rate
+                ));
+                out.push_str(&format!("    {secondary_account}\n\n"));
+            }
+            EntryType::Lend | EntryType::Borrow | EntryType::Adjust => {
+                out.push_str(&format!("    Equity:Adjustments  {code} {:.2}\n", -entry.amount));
+                out.push_str(&format!("    {source_account}\n\n"));
+            }
+        }
+    }
+    out
+}
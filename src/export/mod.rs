@@ -0,0 +1,5 @@
+pub mod beancount;
+pub mod encryption;
+pub mod ledger;
+pub mod pdf;
+pub mod xlsx;
@@ -0,0 +1,99 @@
+//! Renders report/entry data as an `.xlsx` workbook via `rust_xlsxwriter`,
+//! for users who want a spreadsheet rather than the plaintext-accounting
+//! syntax [`crate::export::beancount`]/[`crate::export::ledger`] produce.
+
+use std::collections::HashMap;
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+use crate::error::AppError;
+use crate::models::category::Category;
+use crate::models::currency::Currency;
+use crate::models::entry::{Entry, EntryType};
+use crate::models::source::Source;
+
+impl From<XlsxError> for AppError {
+    fn from(err: XlsxError) -> Self {
+        AppError::Internal(err.to_string())
+    }
+}
+
+/// One "Entries" sheet (every row as-is) and one "Summary" sheet (total
+/// income/spend per category), mirroring what
+/// [`crate::handlers::reports::category_breakdown`] computes in JSON.
+pub fn render_entries(entries: &[Entry], sources: &[Source], categories: &[Category], currencies: &[Currency]) -> Result<Vec<u8>, AppError> {
+    let source_names: HashMap<i32, &str> = sources.iter().map(|s| (s.id, s.name.as_str())).collect();
+    let category_names: HashMap<i32, &str> = categories.iter().map(|c| (c.id, c.name.as_str())).collect();
+    let currency_codes: HashMap<i32, &str> = currencies.iter().map(|c| (c.id, c.code.as_str())).collect();
+
+    let mut workbook = Workbook::new();
+
+    let entries_sheet = workbook.add_worksheet().set_name("Entries")?;
+    for (col, header) in ["Date", "Source", "Category", "Type", "Amount", "Currency", "Target", "Description"].iter().enumerate() {
+        entries_sheet.write(0, col as u16, *header)?;
+    }
+    for (row, entry) in entries.iter().enumerate() {
+        let row = row as u32 + 1;
+        entries_sheet.write(row, 0, entry.entry_date.to_rfc3339())?;
+        entries_sheet.write(row, 1, source_names.get(&entry.source_id).copied().unwrap_or(""))?;
+        entries_sheet.write(row, 2, entry.category_id.and_then(|id| category_names.get(&id).copied()).unwrap_or(""))?;
+        entries_sheet.write(row, 3, entry.entry_type.as_str())?;
+        entries_sheet.write(row, 4, entry.source_amount.to_f64_lossy())?;
+        entries_sheet.write(row, 5, currency_codes.get(&entry.currency_id).copied().unwrap_or(""))?;
+        entries_sheet.write(row, 6, entry.target.as_deref().unwrap_or(""))?;
+        entries_sheet.write(row, 7, entry.description.as_deref().unwrap_or(""))?;
+    }
+
+    let mut by_category: HashMap<&str, (f64, f64)> = HashMap::new();
+    for entry in entries {
+        let name = entry.category_id.and_then(|id| category_names.get(&id).copied()).unwrap_or("Uncategorized");
+        let totals = by_category.entry(name).or_insert((0.0, 0.0));
+        match entry.entry_type {
+            EntryType::Income => totals.0 += entry.source_amount.to_f64_lossy(),
+            EntryType::Spend => totals.1 += entry.source_amount.to_f64_lossy(),
+            _ => {}
+        }
+    }
+
+    let summary_sheet = workbook.add_worksheet().set_name("Summary")?;
+    for (col, header) in ["Category", "Income", "Spend"].iter().enumerate() {
+        summary_sheet.write(0, col as u16, *header)?;
+    }
+    for (row, (name, (income, spend))) in by_category.into_iter().enumerate() {
+        let row = row as u32 + 1;
+        summary_sheet.write(row, 0, name)?;
+        summary_sheet.write(row, 1, income)?;
+        summary_sheet.write(row, 2, spend)?;
+    }
+
+    Ok(workbook.save_to_buffer()?)
+}
+
+/// One row per month, for `?format=xlsx` on
+/// [`crate::handlers::reports::monthly`]. Its own plain struct rather than
+/// that handler's `MonthlyTotal` so this module doesn't depend on
+/// `handlers`.
+pub struct MonthlyRow {
+    pub month: i32,
+    pub income: f64,
+    pub spend: f64,
+    pub net: f64,
+}
+
+pub fn render_monthly(year: i32, months: &[MonthlyRow]) -> Result<Vec<u8>, AppError> {
+    let mut workbook = Workbook::new();
+
+    let sheet = workbook.add_worksheet().set_name(&format!("{year}"))?;
+    for (col, header) in ["Month", "Income", "Spend", "Net"].iter().enumerate() {
+        sheet.write(0, col as u16, *header)?;
+    }
+    for (row, month) in months.iter().enumerate() {
+        let row = row as u32 + 1;
+        sheet.write(row, 0, month.month)?;
+        sheet.write(row, 1, month.income)?;
+        sheet.write(row, 2, month.spend)?;
+        sheet.write(row, 3, month.net)?;
+    }
+
+    Ok(workbook.save_to_buffer()?)
+}
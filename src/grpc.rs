@@ -0,0 +1,246 @@
+//! A tonic-based gRPC mirror of the entries surface of the REST API, for
+//! native mobile/CLI clients that prefer protobuf over JSON. Shares
+//! [`crate::db::DbPool`] and the session lookup [`crate::auth::AuthUser`]
+//! uses, but can't reuse `AuthUser` itself since it's an Actix
+//! `FromRequest` extractor — [`authenticate`] below does the same
+//! `Authorization: Bearer <token>` → [`session::find_active`] lookup
+//! against a request's gRPC metadata instead of HTTP headers.
+//!
+//! Scoped to what `src/handlers/entries.rs` itself supports: entries are
+//! create-only, so there's no Update/Delete RPC here either. All entry
+//! creation still goes through
+//! [`crate::handlers::entries::insert_entry_with_splits`] — the one safe
+//! transactional path that applies balance math and the audit log — same
+//! as every other ingestion surface (Telegram, email receipts, bank sync)
+//! added alongside this one.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use tonic::{Request, Response, Status};
+
+use crate::config::AppConfig;
+use crate::db::{cpool, DbPool};
+use crate::dto::entry::CreateEntryRequest;
+use crate::handlers::entries::insert_entry_with_splits;
+use crate::models::entry::{Entry, EntryType};
+use crate::models::session;
+use crate::money::Money;
+use crate::schema::entries;
+
+pub mod pb {
+    tonic::include_proto!("money_rs.sync.v1");
+}
+
+use pb::sync_service_server::SyncService as SyncServiceTrait;
+
+pub struct SyncService {
+    pool: DbPool,
+    config: AppConfig,
+}
+
+impl SyncService {
+    pub fn new(pool: DbPool, config: AppConfig) -> Self {
+        SyncService { pool, config }
+    }
+}
+
+/// The `Authorization: Bearer <token>` → [`session::find_active`] lookup
+/// [`crate::auth::AuthUser`] does for REST requests, applied to a gRPC
+/// request's metadata instead of HTTP headers. There's no
+/// `X-User-Id`-style placeholder fallback here — every RPC requires a real
+/// session token.
+fn authenticate<T>(conn: &mut crate::db::DbConn, req: &Request<T>, session_ttl_minutes: i64) -> Result<i32, Status> {
+    let token = req
+        .metadata()
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .ok_or_else(|| Status::unauthenticated("missing bearer token"))?;
+
+    match session::find_active(conn, token, session_ttl_minutes) {
+        Ok(Some(active_session)) => Ok(active_session.user_id),
+        Ok(None) => Err(Status::unauthenticated("session token is invalid or revoked")),
+        Err(e) => Err(Status::internal(e.to_string())),
+    }
+}
+
+fn entry_type_from_pb(entry_type: i32) -> Result<EntryType, Status> {
+    match pb::EntryType::try_from(entry_type).unwrap_or(pb::EntryType::Unspecified) {
+        pb::EntryType::Spend => Ok(EntryType::Spend),
+        pb::EntryType::Income => Ok(EntryType::Income),
+        pb::EntryType::Convert => Ok(EntryType::Convert),
+        pb::EntryType::Lend => Ok(EntryType::Lend),
+        pb::EntryType::Borrow => Ok(EntryType::Borrow),
+        pb::EntryType::Adjust => Ok(EntryType::Adjust),
+        pb::EntryType::Unspecified => Err(Status::invalid_argument("entry_type is required")),
+    }
+}
+
+fn entry_type_to_pb(entry_type: EntryType) -> pb::EntryType {
+    match entry_type {
+        EntryType::Spend => pb::EntryType::Spend,
+        EntryType::Income => pb::EntryType::Income,
+        EntryType::Convert => pb::EntryType::Convert,
+        EntryType::Lend => pb::EntryType::Lend,
+        EntryType::Borrow => pb::EntryType::Borrow,
+        EntryType::Adjust => pb::EntryType::Adjust,
+    }
+}
+
+fn money_from_pb(amount: &str) -> Result<Money, Status> {
+    rust_decimal::Decimal::from_str(amount)
+        .map(Money)
+        .map_err(|e| Status::invalid_argument(format!("invalid amount: {e}")))
+}
+
+fn timestamp_from_pb(value: &str) -> Result<DateTime<Utc>, Status> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| Status::invalid_argument(format!("invalid timestamp: {e}")))
+}
+
+fn entry_to_pb(entry: Entry) -> pb::Entry {
+    pb::Entry {
+        id: entry.id,
+        user_id: entry.user_id,
+        source_id: entry.source_id,
+        secondary_source_id: entry.secondary_source_id,
+        category_id: entry.category_id,
+        currency_id: entry.currency_id,
+        entry_type: entry_type_to_pb(entry.entry_type) as i32,
+        amount: entry.amount.to_string(),
+        source_amount: entry.source_amount.to_string(),
+        conversion_rate: entry.conversion_rate,
+        conversion_rate_to_fixed: entry.conversion_rate_to_fixed,
+        counterparty_id: entry.counterparty_id,
+        payer_id: entry.payer_id,
+        description: entry.description,
+        notes: entry.notes,
+        entry_date: entry.entry_date.to_rfc3339(),
+        created_at: entry.created_at.to_rfc3339(),
+    }
+}
+
+impl From<crate::error::AppError> for Status {
+    fn from(err: crate::error::AppError) -> Self {
+        match err {
+            crate::error::AppError::NotFound(msg) => Status::not_found(msg),
+            crate::error::AppError::Validation(msg) => Status::invalid_argument(msg),
+            crate::error::AppError::Conflict(msg) => Status::already_exists(msg),
+            crate::error::AppError::Unauthorized(msg) => Status::unauthenticated(msg),
+            crate::error::AppError::Internal(msg) => Status::internal(msg),
+            crate::error::AppError::Unavailable(msg) => Status::unavailable(msg),
+            err @ crate::error::AppError::FieldValidation(_) => Status::invalid_argument(err.to_string()),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl SyncServiceTrait for SyncService {
+    async fn create_entry(&self, request: Request<pb::CreateEntryRequest>) -> Result<Response<pb::Entry>, Status> {
+        let mut conn = cpool(&self.pool)?;
+        let user_id = authenticate(&mut conn, &request, self.config.session_ttl_minutes)?;
+        let body = request.into_inner();
+        if body.user_id != user_id {
+            return Err(Status::permission_denied("user_id does not match the authenticated session"));
+        }
+
+        let entry = insert_entry_with_splits(
+            &mut conn,
+            CreateEntryRequest {
+                user_id: body.user_id,
+                source_id: body.source_id,
+                secondary_source_id: body.secondary_source_id,
+                category_id: body.category_id,
+                currency_id: body.currency_id,
+                entry_type: entry_type_from_pb(body.entry_type)?,
+                amount: money_from_pb(&body.amount)?,
+                target: None,
+                counterparty_id: body.counterparty_id,
+                payer_id: body.payer_id,
+                description: body.description,
+                notes: body.notes,
+                entry_date: timestamp_from_pb(&body.entry_date)?,
+                splits: None,
+                custom: Default::default(),
+            },
+        )?;
+
+        Ok(Response::new(entry_to_pb(entry)))
+    }
+
+    async fn get_entry(&self, request: Request<pb::GetEntryRequest>) -> Result<Response<pb::Entry>, Status> {
+        let mut conn = cpool(&self.pool)?;
+        let user_id = authenticate(&mut conn, &request, self.config.session_ttl_minutes)?;
+        let body = request.into_inner();
+
+        let entry = entries::table
+            .find(body.entry_id)
+            .filter(entries::user_id.eq(user_id))
+            .select(Entry::as_select())
+            .first::<Entry>(&mut conn)
+            .map_err(|_| Status::not_found("entry not found"))?;
+
+        Ok(Response::new(entry_to_pb(entry)))
+    }
+
+    async fn list_entries(&self, request: Request<pb::ListEntriesRequest>) -> Result<Response<pb::ListEntriesResponse>, Status> {
+        let mut conn = cpool(&self.pool)?;
+        let user_id = authenticate(&mut conn, &request, self.config.session_ttl_minutes)?;
+        let body = request.into_inner();
+        if body.user_id != user_id {
+            return Err(Status::permission_denied("user_id does not match the authenticated session"));
+        }
+
+        let results = entries::table
+            .filter(entries::user_id.eq(body.user_id))
+            .select(Entry::as_select())
+            .load::<Entry>(&mut conn)
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(pb::ListEntriesResponse { entries: results.into_iter().map(entry_to_pb).collect() }))
+    }
+
+    type ExportEntriesStream = futures_util::stream::Iter<std::vec::IntoIter<Result<pb::Entry, Status>>>;
+
+    async fn export_entries(&self, request: Request<pb::ExportEntriesRequest>) -> Result<Response<Self::ExportEntriesStream>, Status> {
+        let mut conn = cpool(&self.pool)?;
+        let user_id = authenticate(&mut conn, &request, self.config.session_ttl_minutes)?;
+        let body = request.into_inner();
+        if body.user_id != user_id {
+            return Err(Status::permission_denied("user_id does not match the authenticated session"));
+        }
+
+        // Loaded eagerly rather than streamed off the connection: `DbConn`
+        // is checked out for the whole RPC either way, and this keeps the
+        // connection-pool usage identical to every other handler instead
+        // of holding a cursor open across `.await` points.
+        let results = entries::table
+            .filter(entries::user_id.eq(body.user_id))
+            .order(entries::entry_date.asc())
+            .select(Entry::as_select())
+            .load::<Entry>(&mut conn)
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        let items: Vec<Result<pb::Entry, Status>> = results.into_iter().map(|e| Ok(entry_to_pb(e))).collect();
+        Ok(Response::new(futures_util::stream::iter(items)))
+    }
+}
+
+/// Builds the gRPC server future for `main` to run alongside the existing
+/// Actix `HttpServer`, or `None` when `AppConfig::grpc_bind_address` is
+/// unset — same "unset disables the feature" convention as
+/// `bank_provider_url`/`telegram_bot_token`.
+pub fn build(
+    pool: DbPool,
+    config: AppConfig,
+    bind_address: &str,
+) -> Result<impl std::future::Future<Output = Result<(), tonic::transport::Error>>, std::net::AddrParseError> {
+    let addr = bind_address.parse()?;
+    let service = SyncService::new(pool, config);
+    Ok(tonic::transport::Server::builder()
+        .add_service(pb::sync_service_server::SyncServiceServer::new(service))
+        .serve(addr))
+}
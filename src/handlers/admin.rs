@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use actix_web::{web, HttpResponse};
+use chrono::{DateTime, Utc};
+use diesel::dsl::{count_star, max, sum};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::AdminUser;
+use crate::config::AppConfig;
+use crate::db::{cpool, DbPool, ReportsPool};
+use crate::error::AppError;
+use crate::jobs::{networth, purge, recalculate, recompute_fixed_rates};
+use crate::list_query::{ListQuery, Page};
+use crate::models::audit_log;
+use crate::models::user::User;
+use crate::schema::{attachments, entries, login_history, users};
+
+#[derive(Deserialize)]
+pub struct RecalculateRequest {
+    pub since: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+pub struct RecalculateReport {
+    pub sources_recalculated: usize,
+}
+
+/// Manual trigger for [`recalculate::recalculate_since`], for use after a
+/// back-dated entry edit or a bulk import until this runs on a schedule.
+pub async fn recalculate(
+    _admin: AdminUser,
+    pool: web::Data<DbPool>,
+    body: web::Json<RecalculateRequest>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let sources_recalculated = recalculate::recalculate_since(&mut conn, body.since)?;
+
+    Ok(HttpResponse::Ok().json(RecalculateReport { sources_recalculated }))
+}
+
+#[derive(Deserialize)]
+pub struct RecomputeFixedRatesQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// `POST /api/admin/recompute-fixed-rates`: re-derives every entry's
+/// `conversion_rate_to_fixed` from [`crate::models::currency_rate`]'s
+/// history, for use after importing historical data or correcting a
+/// badly entered rate. `?dry_run=true` returns the
+/// [`recompute_fixed_rates::RecomputeFixedRatesReport`] summary without
+/// writing anything, so an admin can see how aggregates would shift
+/// before committing to it.
+pub async fn recompute_fixed_rates(
+    _admin: AdminUser,
+    pool: web::Data<DbPool>,
+    query: web::Query<RecomputeFixedRatesQuery>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let report = recompute_fixed_rates::recompute_fixed_rates(&mut conn, query.dry_run)?;
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+#[derive(Serialize)]
+pub struct NetworthSnapshotReport {
+    pub snapshots_recorded: usize,
+}
+
+/// `POST /api/admin/networth-snapshot`: runs
+/// [`networth::record_all_daily_snapshots`] for every user, for use until
+/// this runs on a schedule (see that module's doc comment).
+pub async fn record_networth_snapshots(_admin: AdminUser, pool: web::Data<DbPool>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let snapshots_recorded = networth::record_all_daily_snapshots(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(NetworthSnapshotReport { snapshots_recorded }))
+}
+
+/// `GET /api/admin/users`: every account, searchable (`?q=` matches
+/// `email`), sortable (`?sort=email|created_at`, `-` prefix for
+/// descending), paginated — see [`crate::list_query`]. Gated by
+/// [`AdminUser`]; `User`'s own `#[serde(skip_serializing)]` on
+/// `password_hash`/`privacy_salt` makes it safe to return as-is.
+pub async fn list_users(_admin: AdminUser, pool: web::Data<DbPool>, query: web::Query<ListQuery>) -> Result<HttpResponse, AppError> {
+    use diesel::pg::PgTextExpressionMethods;
+
+    let mut conn = cpool(&pool)?;
+    let pagination = query.pagination();
+
+    let count_filter = || {
+        let mut q = users::table.into_boxed();
+        if let Some(term) = &query.q {
+            q = q.filter(users::email.ilike(format!("%{term}%")));
+        }
+        q
+    };
+
+    let total = count_filter().count().get_result::<i64>(&mut conn)?;
+
+    let mut selection = count_filter();
+    let (sort_column, ascending) = query.sort_direction("email");
+    selection = match (sort_column, ascending) {
+        ("email", true) => selection.order(users::email.asc()),
+        ("email", false) => selection.order(users::email.desc()),
+        ("created_at", true) => selection.order(users::created_at.asc()),
+        ("created_at", false) => selection.order(users::created_at.desc()),
+        _ => return Err(AppError::Validation(format!("cannot sort users by {sort_column}"))),
+    };
+
+    let items = selection
+        .limit(pagination.limit)
+        .offset(pagination.offset)
+        .select(User::as_select())
+        .load::<User>(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(Page {
+        items,
+        page: query.page.max(1),
+        per_page: pagination.limit,
+        total,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct SetUserDisabledRequest {
+    pub disabled: bool,
+}
+
+/// `PATCH /api/admin/users/{id}`: flips `users.disabled`, the same column
+/// [`crate::handlers::users::login`] checks before issuing a session — a
+/// disabled account's data stays intact, only its ability to log in is
+/// cut off.
+pub async fn set_user_disabled(
+    _admin: AdminUser,
+    pool: web::Data<DbPool>,
+    user_id: web::Path<i32>,
+    body: web::Json<SetUserDisabledRequest>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let user = diesel::update(users::table.find(user_id.into_inner()))
+        .set(users::disabled.eq(body.disabled))
+        .get_result::<User>(&mut conn)
+        .map_err(|e| crate::error::map_update_error(e, "user not found"))?;
+
+    Ok(HttpResponse::Ok().json(user))
+}
+
+/// `DELETE /api/admin/users/{id}`: hard-deletes the account and everything
+/// it owns via [`purge::purge_user`] — see that module's doc comment for
+/// what's deliberately left behind (`currencies`, `audit_log`).
+pub async fn delete_user(
+    _admin: AdminUser,
+    pool: web::Data<DbPool>,
+    config: web::Data<AppConfig>,
+    user_id: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let user_id = user_id.into_inner();
+
+    conn.transaction::<_, AppError, _>(|conn| Ok(purge::purge_user(conn, &config, user_id)?))?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// `GET /api/admin/integrity/chain`: walks [`audit_log::verify_chain`] over
+/// the whole table and reports whether the hash chain [`audit_log::record`]
+/// maintains is still intact, and if not, the id of the first row where it
+/// broke.
+pub async fn verify_integrity_chain(_admin: AdminUser, pool: web::Data<DbPool>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let report = audit_log::verify_chain(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+#[derive(Serialize)]
+pub struct UserStats {
+    pub user_id: i32,
+    pub email: String,
+    pub entry_count: i64,
+    pub storage_bytes: i64,
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+pub struct StatsReport {
+    pub users: Vec<UserStats>,
+    pub total_users: i64,
+    pub total_entries: i64,
+    pub total_storage_bytes: i64,
+}
+
+/// `GET /api/admin/stats`: per-user entry counts, attachment storage use,
+/// and last successful login, plus global totals, for self-hosted
+/// operators to gauge growth and spot abandoned accounts. Each dimension
+/// is its own `GROUP BY` query — one per table, like
+/// [`crate::handlers::entries::aggregate_entries`] — rather than a single
+/// join, since `entries` and `attachments` would otherwise fan out
+/// `login_history` rows (or vice versa) and skew the counts.
+pub async fn stats(_admin: AdminUser, pool: web::Data<ReportsPool>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool.0)?;
+
+    let all_users = users::table.select(User::as_select()).load::<User>(&mut conn)?;
+
+    let entry_counts: HashMap<i32, i64> = entries::table
+        .group_by(entries::user_id)
+        .select((entries::user_id, count_star()))
+        .load::<(i32, i64)>(&mut conn)?
+        .into_iter()
+        .collect();
+
+    let storage_bytes: HashMap<i32, i64> = entries::table
+        .inner_join(attachments::table)
+        .group_by(entries::user_id)
+        .select((entries::user_id, sum(attachments::size_bytes)))
+        .load::<(i32, Option<i64>)>(&mut conn)?
+        .into_iter()
+        .map(|(user_id, total)| (user_id, total.unwrap_or(0)))
+        .collect();
+
+    let last_login_at: HashMap<i32, DateTime<Utc>> = login_history::table
+        .filter(login_history::success.eq(true))
+        .group_by(login_history::user_id)
+        .select((login_history::user_id, max(login_history::created_at)))
+        .load::<(i32, Option<DateTime<Utc>>)>(&mut conn)?
+        .into_iter()
+        .filter_map(|(user_id, last)| last.map(|last| (user_id, last)))
+        .collect();
+
+    let total_entries: i64 = entries::table.count().get_result(&mut conn)?;
+    let total_storage_bytes: i64 = attachments::table
+        .select(sum(attachments::size_bytes))
+        .first::<Option<i64>>(&mut conn)?
+        .unwrap_or(0);
+
+    let users: Vec<UserStats> = all_users
+        .into_iter()
+        .map(|user| UserStats {
+            entry_count: entry_counts.get(&user.id).copied().unwrap_or(0),
+            storage_bytes: storage_bytes.get(&user.id).copied().unwrap_or(0),
+            last_login_at: last_login_at.get(&user.id).copied(),
+            user_id: user.id,
+            email: user.email,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(StatsReport {
+        total_users: users.len() as i64,
+        users,
+        total_entries,
+        total_storage_bytes,
+    }))
+}
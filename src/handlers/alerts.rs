@@ -0,0 +1,55 @@
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+
+use crate::db::{cpool, DbPool};
+use crate::dto::alert::CreateAlertRequest;
+use crate::error::AppError;
+use crate::models::alert::{Alert, NewAlert};
+use crate::models::tombstone;
+use crate::schema::alerts;
+
+pub async fn create_alert(
+    pool: web::Data<DbPool>,
+    body: web::Json<CreateAlertRequest>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let body = body.into_inner();
+
+    let new_alert = NewAlert {
+        user_id: body.user_id,
+        base_currency_id: body.base_currency_id,
+        quote_currency_id: body.quote_currency_id,
+        threshold: body.threshold,
+        direction: body.direction,
+    };
+
+    let alert = diesel::insert_into(alerts::table)
+        .values(&new_alert)
+        .get_result::<Alert>(&mut conn)?;
+
+    Ok(HttpResponse::Created().json(alert))
+}
+
+pub async fn list_alerts(pool: web::Data<DbPool>, user_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let results = alerts::table
+        .filter(alerts::user_id.eq(user_id.into_inner()))
+        .select(Alert::as_select())
+        .load(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(results))
+}
+
+pub async fn delete_alert(pool: web::Data<DbPool>, alert_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let alert_id = alert_id.into_inner();
+
+    let deleted = diesel::delete(alerts::table.find(alert_id)).execute(&mut conn)?;
+    if deleted == 0 {
+        return Err(AppError::NotFound("alert not found".into()));
+    }
+    tombstone::record_deletion(&mut conn, tombstone::ALERT, alert_id)?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,182 @@
+use actix_multipart::Multipart;
+use actix_web::{web, HttpResponse};
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use futures_util::{StreamExt, TryStreamExt};
+use serde::Deserialize;
+
+use std::time::Duration;
+
+use crate::auth::AuthUser;
+use crate::config::AppConfig;
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::magic_bytes;
+use crate::models::attachment::{Attachment, NewAttachment};
+use crate::models::tombstone;
+use crate::scanning::{ClamdScanner, FileScanner, NoopScanner};
+use crate::schema::{attachments, entries};
+use crate::storage;
+
+/// Confirms `entry_id` belongs to `user_id` before any attachment
+/// operation touches it — attachments have no `user_id` of their own, so
+/// ownership is only knowable by following `entry_id` back to `entries`.
+fn require_entry_owner(conn: &mut PgConnection, entry_id: i32, user_id: i32) -> Result<(), AppError> {
+    let owner = entries::table
+        .find(entry_id)
+        .select(entries::user_id)
+        .first::<i32>(conn)
+        .map_err(|_| AppError::NotFound(format!("entry {entry_id} not found")))?;
+    if owner != user_id {
+        return Err(AppError::Unauthorized("entry does not belong to the authenticated session".into()));
+    }
+    Ok(())
+}
+
+fn build_scanner(config: &AppConfig) -> Box<dyn FileScanner> {
+    match &config.clamd_address {
+        Some(address) => Box::new(ClamdScanner { address: address.clone(), timeout: Duration::from_secs(10) }),
+        None => Box::new(NoopScanner),
+    }
+}
+
+/// Reads the (single) file field of a multipart upload into memory,
+/// enforcing `max_attachment_bytes` as it goes rather than buffering an
+/// oversized upload first and rejecting it after the fact.
+async fn read_file_field(
+    mut payload: Multipart,
+    max_bytes: usize,
+) -> Result<(String, String, Vec<u8>), AppError> {
+    while let Some(field) = payload.try_next().await.map_err(|e| AppError::Validation(e.to_string()))? {
+        let filename = field
+            .content_disposition()
+            .and_then(|cd| cd.get_filename())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "upload.bin".to_string());
+        let content_type = field.content_type().map(|m| m.to_string()).unwrap_or_else(|| "application/octet-stream".to_string());
+
+        let mut bytes = Vec::new();
+        let mut field = field;
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|e| AppError::Validation(e.to_string()))?;
+            if bytes.len() + chunk.len() > max_bytes {
+                return Err(AppError::Validation(format!("attachment exceeds {max_bytes} bytes")));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        return Ok((filename, content_type, bytes));
+    }
+
+    Err(AppError::Validation("no file field in multipart body".into()))
+}
+
+pub async fn upload_attachment(
+    pool: web::Data<DbPool>,
+    config: web::Data<AppConfig>,
+    auth: AuthUser,
+    entry_id: web::Path<i32>,
+    payload: Multipart,
+) -> Result<HttpResponse, AppError> {
+    let entry_id = entry_id.into_inner();
+    let mut conn = cpool(&pool)?;
+
+    require_entry_owner(&mut conn, entry_id, auth.0)?;
+
+    let (filename, content_type, bytes) = read_file_field(payload, config.max_attachment_bytes).await?;
+
+    if !magic_bytes::is_allowed(&bytes) {
+        return Err(AppError::Validation("attachment does not match an allowed file type".into()));
+    }
+
+    // Scanning is a network call to clamd (or a no-op), but either way it's
+    // blocking — run it on actix's blocking pool same as `password::verify`
+    // does for its own CPU/IO-bound work, rather than stalling this task.
+    let scanner = build_scanner(&config);
+    let scan_bytes = bytes.clone();
+    web::block(move || scanner.scan(&scan_bytes))
+        .await
+        .map_err(|_| AppError::Internal("attachment scan worker panicked".into()))?
+        .map_err(AppError::Validation)?;
+
+    let storage_key = format!("{entry_id}/{}-{filename}", chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default());
+
+    storage::save(&config, &storage_key, &bytes).map_err(|e| AppError::Internal(e.to_string()))?;
+
+    let attachment = diesel::insert_into(attachments::table)
+        .values(&NewAttachment {
+            entry_id,
+            filename,
+            content_type,
+            size_bytes: bytes.len() as i32,
+            storage_key,
+        })
+        .get_result::<Attachment>(&mut conn)?;
+
+    Ok(HttpResponse::Created().json(attachment))
+}
+
+pub async fn list_attachments(pool: web::Data<DbPool>, auth: AuthUser, entry_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let entry_id = entry_id.into_inner();
+    let mut conn = cpool(&pool)?;
+
+    require_entry_owner(&mut conn, entry_id, auth.0)?;
+
+    let results = attachments::table
+        .filter(attachments::entry_id.eq(entry_id))
+        .select(Attachment::as_select())
+        .load::<Attachment>(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(results))
+}
+
+pub async fn download_attachment(
+    pool: web::Data<DbPool>,
+    config: web::Data<AppConfig>,
+    auth: AuthUser,
+    attachment_id: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let attachment = attachments::table
+        .find(attachment_id.into_inner())
+        .select(Attachment::as_select())
+        .first::<Attachment>(&mut conn)
+        .map_err(|_| AppError::NotFound("attachment not found".into()))?;
+    require_entry_owner(&mut conn, attachment.entry_id, auth.0)?;
+
+    let bytes = storage::read(&config, &attachment.storage_key).map_err(|e| AppError::Internal(e.to_string()))?;
+
+    Ok(HttpResponse::Ok().content_type(attachment.content_type).body(bytes))
+}
+
+#[derive(Deserialize)]
+pub struct DeleteAttachmentQuery {
+    pub attachment_id: i32,
+}
+
+/// Path is `/api/entry/{entry_id}/attachment` for symmetry with upload and
+/// list, but deleting a specific attachment (an entry may have several)
+/// needs its id — passed as a query param rather than a second path
+/// segment.
+pub async fn delete_attachment(
+    pool: web::Data<DbPool>,
+    config: web::Data<AppConfig>,
+    auth: AuthUser,
+    query: web::Query<DeleteAttachmentQuery>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let attachment = attachments::table
+        .find(query.attachment_id)
+        .select(Attachment::as_select())
+        .first::<Attachment>(&mut conn)
+        .map_err(|_| AppError::NotFound("attachment not found".into()))?;
+    require_entry_owner(&mut conn, attachment.entry_id, auth.0)?;
+
+    diesel::delete(attachments::table.find(attachment.id)).execute(&mut conn)?;
+    tombstone::record_deletion(&mut conn, tombstone::ATTACHMENT, attachment.id)?;
+    let _ = storage::delete(&config, &attachment.storage_key);
+
+    Ok(HttpResponse::NoContent().finish())
+}
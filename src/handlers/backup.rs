@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use serde::Deserialize;
+
+use crate::auth::AuthUser;
+use crate::db::{cpool, DbConn, DbPool};
+use crate::dto::backup::{self, CurrencyBackup, FullBackup, BACKUP_VERSION};
+use crate::error::AppError;
+use crate::export::encryption::{self, EncryptedBackup};
+use crate::models::category::{Category, NewCategory};
+use crate::models::currency::{Currency, NewCurrency};
+use crate::models::entry::{Entry, NewEntry};
+use crate::models::source::{NewSource, Source};
+use crate::schema::{categories, currencies, entries, sources};
+use crate::validation::{Validator, NAME_MAX_LEN};
+
+#[derive(Deserialize)]
+pub struct ExportFullQuery {
+    pub user_id: i32,
+    /// When set, the response body is an [`EncryptedBackup`] instead of a
+    /// plain [`FullBackup`] — encrypted under a key derived from this
+    /// passphrase, which is never itself stored anywhere.
+    #[serde(default)]
+    pub passphrase: Option<String>,
+}
+
+/// Builds the [`FullBackup`] for `user_id`, shared by [`export_full`] and
+/// [`crate::handlers::users::delete_me`] (which returns one as the final
+/// export blob before purging the account).
+pub fn build_full_backup(conn: &mut DbConn, user_id: i32) -> Result<FullBackup, AppError> {
+    let user_sources = sources::table
+        .filter(sources::user_id.eq(user_id))
+        .select(Source::as_select())
+        .load::<Source>(conn)?;
+    let user_categories = categories::table
+        .filter(categories::user_id.eq(user_id))
+        .select(Category::as_select())
+        .load::<Category>(conn)?;
+    let user_entries = entries::table
+        .filter(entries::user_id.eq(user_id))
+        .select(Entry::as_select())
+        .load::<Entry>(conn)?;
+
+    let currency_ids: std::collections::HashSet<i32> = user_sources
+        .iter()
+        .map(|s| s.currency_id)
+        .chain(user_entries.iter().map(|e| e.currency_id))
+        .collect();
+    let used_currencies = currencies::table
+        .filter(currencies::id.eq_any(&currency_ids))
+        .select(Currency::as_select())
+        .load::<Currency>(conn)?;
+    let currency_codes: HashMap<i32, String> = used_currencies.iter().map(|c| (c.id, c.code.clone())).collect();
+
+    Ok(FullBackup {
+        version: BACKUP_VERSION,
+        currencies: used_currencies.iter().map(CurrencyBackup::from).collect(),
+        sources: user_sources
+            .iter()
+            .map(|s| backup::source_backup(s, &currency_codes[&s.currency_id]))
+            .collect(),
+        categories: user_categories.iter().map(backup::category_backup).collect(),
+        entries: user_entries
+            .iter()
+            .map(|e| backup::entry_backup(e, &currency_codes[&e.currency_id]))
+            .collect(),
+    })
+}
+
+pub async fn export_full(pool: web::Data<DbPool>, auth: AuthUser, query: web::Query<ExportFullQuery>) -> Result<HttpResponse, AppError> {
+    if query.user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    let mut conn = cpool(&pool)?;
+    let backup = build_full_backup(&mut conn, query.user_id)?;
+
+    match &query.passphrase {
+        Some(passphrase) => {
+            let plaintext = serde_json::to_string(&backup).map_err(|e| AppError::Internal(e.to_string()))?;
+            Ok(HttpResponse::Ok().json(encryption::encrypt(passphrase, &plaintext)))
+        }
+        None => Ok(HttpResponse::Ok().json(backup)),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ImportFullRequest {
+    pub user_id: i32,
+    /// Exactly one of `backup` or (`encrypted_backup` and `passphrase`)
+    /// must be present, mirroring [`export_full`]'s two response shapes.
+    #[serde(default)]
+    pub backup: Option<FullBackup>,
+    #[serde(default)]
+    pub encrypted_backup: Option<EncryptedBackup>,
+    #[serde(default)]
+    pub passphrase: Option<String>,
+}
+
+/// Restores a [`FullBackup`] into `user_id`'s account, remapping every id
+/// (currencies are matched/created by code; sources and categories always
+/// get fresh rows, even if a same-named one already exists, since merging
+/// into existing data is a distinct, harder problem than restoring into an
+/// empty account).
+pub async fn import_full(pool: web::Data<DbPool>, auth: AuthUser, body: web::Json<ImportFullRequest>) -> Result<HttpResponse, AppError> {
+    if body.user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    let body = body.into_inner();
+    let user_id = body.user_id;
+
+    let backup = match (body.backup, body.encrypted_backup, body.passphrase) {
+        (Some(backup), None, _) => backup,
+        (None, Some(encrypted), Some(passphrase)) => {
+            let plaintext = encryption::decrypt(&passphrase, &encrypted)
+                .ok_or_else(|| AppError::Validation("wrong passphrase or corrupted backup".into()))?;
+            serde_json::from_str(&plaintext).map_err(|e| AppError::Validation(format!("decrypted backup is malformed: {e}")))?
+        }
+        _ => return Err(AppError::Validation("provide either `backup`, or `encrypted_backup` and `passphrase`".into())),
+    };
+
+    if backup.version != BACKUP_VERSION {
+        return Err(AppError::Validation(format!(
+            "unsupported backup version {}, expected {}",
+            backup.version, BACKUP_VERSION
+        )));
+    }
+
+    let mut validator = Validator::new();
+    for (i, s) in backup.sources.iter().enumerate() {
+        validator = validator.require_max_len(&format!("sources[{i}].name"), &s.name, NAME_MAX_LEN);
+    }
+    for (i, c) in backup.categories.iter().enumerate() {
+        validator = validator.require_max_len(&format!("categories[{i}].name"), &c.name, NAME_MAX_LEN);
+    }
+    validator.finish()?;
+
+    let mut conn = cpool(&pool)?;
+
+    conn.transaction::<_, AppError, _>(|conn| {
+        let mut currency_id_by_code: HashMap<String, i32> = HashMap::new();
+        for c in &backup.currencies {
+            let existing = currencies::table
+                .filter(currencies::code.eq(&c.code))
+                .filter(currencies::archived.eq(false))
+                .select(currencies::id)
+                .first::<i32>(conn)
+                .optional()?;
+            let id = match existing {
+                Some(id) => id,
+                None => diesel::insert_into(currencies::table)
+                    .values(&NewCurrency {
+                        code: c.code.clone(),
+                        name: c.name.clone(),
+                        rate_to_fixed: c.rate_to_fixed,
+                        symbol: None,
+                    })
+                    .returning(currencies::id)
+                    .get_result::<i32>(conn)?,
+            };
+            currency_id_by_code.insert(c.code.clone(), id);
+        }
+
+        let mut source_id_map: HashMap<i32, i32> = HashMap::new();
+        for s in &backup.sources {
+            let new_id = diesel::insert_into(sources::table)
+                .values(&NewSource {
+                    user_id,
+                    name: s.name.clone(),
+                    currency_id: currency_id_by_code[&s.currency_code],
+                    amount: s.amount,
+                })
+                .returning(sources::id)
+                .get_result::<i32>(conn)?;
+            source_id_map.insert(s.id, new_id);
+        }
+
+        let mut category_id_map: HashMap<i32, i32> = HashMap::new();
+        for c in &backup.categories {
+            let new_id = diesel::insert_into(categories::table)
+                .values(&NewCategory { user_id, name: c.name.clone() })
+                .returning(categories::id)
+                .get_result::<i32>(conn)?;
+            category_id_map.insert(c.id, new_id);
+        }
+
+        for e in &backup.entries {
+            let currency_id = currency_id_by_code[&e.currency_code];
+            // TODO: recompute conversion_rate/source_amount against the
+            // destination source's currency like NewEntry's normal
+            // StatefulTryFrom path does; a backup restored into an account
+            // whose currencies moved since export will have a slightly
+            // stale source_amount until the next recalculate job run.
+            let new_entry = NewEntry {
+                user_id,
+                source_id: source_id_map[&e.source_id],
+                secondary_source_id: e.secondary_source_id.map(|id| source_id_map[&id]),
+                category_id: e.category_id.map(|id| category_id_map[&id]),
+                currency_id,
+                entry_type: e.entry_type,
+                amount: e.amount,
+                source_amount: e.amount,
+                conversion_rate: 1.0,
+                conversion_rate_to_fixed: 1.0,
+                target: None,
+                description: e.description.clone(),
+                notes: e.notes.clone(),
+                entry_date: e.entry_date,
+                created_by: Some(user_id),
+                updated_by: Some(user_id),
+                counterparty_id: None,
+                payer_id: None,
+            };
+            diesel::insert_into(entries::table).values(&new_entry).execute(conn)?;
+        }
+
+        Ok(())
+    })?;
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,171 @@
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::config::AppConfig;
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::jobs::bank_sync;
+use crate::models::bank_connection::{BankConnection, NewBankConnection};
+use crate::models::bank_transaction::BankTransaction;
+use crate::models::entry::EntryType;
+use crate::models::tombstone;
+use crate::money::Money;
+use crate::schema::{bank_connections, bank_transactions, sources};
+
+pub async fn create_bank_connection(pool: web::Data<DbPool>, body: web::Json<NewBankConnection>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let connection = diesel::insert_into(bank_connections::table)
+        .values(&body.into_inner())
+        .get_result::<BankConnection>(&mut conn)?;
+
+    Ok(HttpResponse::Created().json(connection))
+}
+
+pub async fn list_bank_connections(pool: web::Data<DbPool>, user_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let results = bank_connections::table
+        .filter(bank_connections::user_id.eq(user_id.into_inner()))
+        .order(bank_connections::created_at.desc())
+        .select(BankConnection::as_select())
+        .load::<BankConnection>(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(results))
+}
+
+pub async fn delete_bank_connection(pool: web::Data<DbPool>, connection_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let connection_id = connection_id.into_inner();
+
+    let deleted = diesel::delete(bank_connections::table.find(connection_id)).execute(&mut conn)?;
+    if deleted == 0 {
+        return Err(AppError::NotFound("bank connection not found".into()));
+    }
+    tombstone::record_deletion(&mut conn, tombstone::BANK_CONNECTION, connection_id)?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Deserialize)]
+pub struct BankSyncQuery {
+    pub user_id: i32,
+}
+
+#[derive(Serialize)]
+pub struct BankSyncResult {
+    pub pulled: usize,
+}
+
+/// `POST /api/source/{name}/bank-sync`: manually triggers
+/// [`bank_sync::pull_transactions`] for whichever [`BankConnection`] is
+/// linked to this source, the same "no scheduler, manually triggered"
+/// shape as `POST /api/currency/refresh-rates`.
+pub async fn trigger_bank_sync(pool: web::Data<DbPool>, config: web::Data<AppConfig>, name: web::Path<String>, query: web::Query<BankSyncQuery>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let Some(provider) = bank_sync::build(&config) else {
+        return Err(AppError::Validation("bank sync is not configured".into()));
+    };
+
+    let source_id = sources::table
+        .filter(sources::name.eq(name.into_inner()))
+        .filter(sources::user_id.eq(query.user_id))
+        .select(sources::id)
+        .first::<i32>(&mut conn)
+        .map_err(|_| AppError::NotFound("source not found".into()))?;
+
+    let connection = bank_connections::table
+        .filter(bank_connections::source_id.eq(source_id))
+        .select(BankConnection::as_select())
+        .first::<BankConnection>(&mut conn)
+        .map_err(|_| AppError::NotFound("no bank connection linked to this source".into()))?;
+
+    let pulled = bank_sync::pull_transactions(&mut conn, provider.as_ref(), &connection).map_err(AppError::Internal)?;
+
+    Ok(HttpResponse::Ok().json(BankSyncResult { pulled }))
+}
+
+/// `GET /api/bank-transactions/connection/{id}`: the pulled transactions
+/// still awaiting review (`entry_id IS NULL`).
+pub async fn list_pending_bank_transactions(pool: web::Data<DbPool>, connection_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let results = bank_transactions::table
+        .filter(bank_transactions::bank_connection_id.eq(connection_id.into_inner()))
+        .filter(bank_transactions::entry_id.is_null())
+        .order(bank_transactions::booked_date.desc())
+        .select(BankTransaction::as_select())
+        .load::<BankTransaction>(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(results))
+}
+
+#[derive(Deserialize)]
+pub struct ConfirmBankTransactionRequest {
+    pub category_id: Option<i32>,
+}
+
+/// `POST /api/bank-transactions/{id}/confirm`: turns a pulled transaction
+/// into a real [`crate::models::entry::Entry`] via
+/// [`crate::handlers::entries::insert_entry_with_splits`], so the same
+/// balance-math/audit-log path applies as any other entry creation. A
+/// negative `amount` becomes `EntryType::Spend`, a positive one
+/// `EntryType::Income` — this endpoint only handles plain inflow/outflow,
+/// not transfers or conversions, since the feed has no notion of those.
+pub async fn confirm_bank_transaction(
+    pool: web::Data<DbPool>,
+    transaction_id: web::Path<i32>,
+    body: web::Json<ConfirmBankTransactionRequest>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let transaction_id = transaction_id.into_inner();
+
+    let transaction = bank_transactions::table
+        .find(transaction_id)
+        .select(BankTransaction::as_select())
+        .first::<BankTransaction>(&mut conn)
+        .map_err(|_| AppError::NotFound("bank transaction not found".into()))?;
+    if transaction.entry_id.is_some() {
+        return Err(AppError::Conflict("bank transaction already confirmed".into()));
+    }
+
+    let connection = bank_connections::table
+        .find(transaction.bank_connection_id)
+        .select(BankConnection::as_select())
+        .first::<BankConnection>(&mut conn)?;
+    let currency_id = sources::table
+        .find(connection.source_id)
+        .select(sources::currency_id)
+        .first::<i32>(&mut conn)?;
+
+    let entry_type = if transaction.amount < Money::ZERO { EntryType::Spend } else { EntryType::Income };
+
+    let entry = crate::handlers::entries::insert_entry_with_splits(
+        &mut conn,
+        crate::dto::entry::CreateEntryRequest {
+            user_id: connection.user_id,
+            source_id: connection.source_id,
+            secondary_source_id: None,
+            category_id: body.category_id,
+            currency_id,
+            entry_type,
+            amount: transaction.amount.abs(),
+            target: None,
+            counterparty_id: None,
+            payer_id: None,
+            description: transaction.description.clone(),
+            notes: None,
+            entry_date: transaction.booked_date,
+            splits: None,
+            custom: Default::default(),
+        },
+    )?;
+
+    diesel::update(bank_transactions::table.find(transaction_id))
+        .set(bank_transactions::entry_id.eq(entry.id))
+        .execute(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(entry))
+}
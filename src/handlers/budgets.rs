@@ -0,0 +1,108 @@
+use chrono::{Datelike, Utc};
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::auth::AuthUser;
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::models::budget::{Budget, BudgetPeriod, NewBudget};
+use crate::models::entry::{Entry, EntryType};
+use crate::models::entry_split::EntrySplit;
+use crate::money::Money;
+use crate::schema::{budgets, entries, entry_splits};
+use crate::validation::Validator;
+
+pub async fn create_budget(pool: web::Data<DbPool>, auth: AuthUser, body: web::Json<NewBudget>) -> Result<HttpResponse, AppError> {
+    let body = body.into_inner();
+    if body.user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    Validator::new().require_positive("amount", body.amount).finish()?;
+
+    let mut conn = cpool(&pool)?;
+
+    let budget = diesel::insert_into(budgets::table).values(&body).get_result::<Budget>(&mut conn)?;
+
+    Ok(HttpResponse::Created().json(budget))
+}
+
+pub async fn list_budgets(pool: web::Data<DbPool>, user_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let results = budgets::table
+        .filter(budgets::user_id.eq(user_id.into_inner()))
+        .select(Budget::as_select())
+        .load(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(results))
+}
+
+#[derive(Serialize)]
+pub struct BudgetStatus {
+    pub budget_id: i32,
+    pub category_id: i32,
+    pub budgeted: Money,
+    pub spent: Money,
+    pub remaining: Money,
+}
+
+/// Compares each of the user's budgets to actual spend in the period
+/// containing "now", using the existing entry ledger — no separate
+/// aggregation table to keep in sync.
+pub async fn budget_status(pool: web::Data<DbPool>, user_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let user_id = user_id.into_inner();
+
+    let user_budgets = budgets::table
+        .filter(budgets::user_id.eq(user_id))
+        .select(Budget::as_select())
+        .load::<Budget>(&mut conn)?;
+
+    let now = Utc::now();
+    let mut statuses = Vec::with_capacity(user_budgets.len());
+    for budget in user_budgets {
+        let period_start = match budget.period {
+            BudgetPeriod::Monthly => now.date_naive().with_day(1).unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc(),
+            BudgetPeriod::Yearly => now.date_naive().with_ordinal(1).unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc(),
+        };
+
+        // Split-aware: an entry with `entry_splits` rows is excluded from
+        // the direct sum (its `category_id` no longer represents where the
+        // money went) and its per-category slices are summed instead.
+        let period_entries = entries::table
+            .filter(entries::entry_type.eq(EntryType::Spend))
+            .filter(entries::entry_date.ge(period_start))
+            .select(Entry::as_select())
+            .load::<Entry>(&mut conn)?;
+        let period_entry_ids: Vec<i32> = period_entries.iter().map(|e| e.id).collect();
+
+        let splits = entry_splits::table
+            .filter(entry_splits::entry_id.eq_any(&period_entry_ids))
+            .select(EntrySplit::as_select())
+            .load::<EntrySplit>(&mut conn)?;
+        let split_entry_ids: std::collections::HashSet<i32> = splits.iter().map(|s| s.entry_id).collect();
+
+        let direct_spent: Money = period_entries
+            .iter()
+            .filter(|e| !split_entry_ids.contains(&e.id) && e.category_id == Some(budget.category_id))
+            .map(|e| e.source_amount)
+            .sum();
+        let split_spent: Money = splits
+            .iter()
+            .filter(|s| s.category_id == budget.category_id)
+            .map(|s| s.amount)
+            .sum();
+        let spent = direct_spent + split_spent;
+
+        statuses.push(BudgetStatus {
+            budget_id: budget.id,
+            category_id: budget.category_id,
+            budgeted: budget.amount,
+            spent,
+            remaining: budget.amount - spent,
+        });
+    }
+
+    Ok(HttpResponse::Ok().json(statuses))
+}
@@ -0,0 +1,122 @@
+use actix_web::{web, HttpResponse};
+use diesel::pg::PgTextExpressionMethods;
+use diesel::prelude::*;
+use serde::Deserialize;
+
+use crate::auth::AuthUser;
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::list_query::{ListQuery, Page};
+use crate::models::category::Category;
+use crate::schema::{budgets, categories, entries, entry_splits, recurring_entries};
+
+/// `GET /api/categories/user/{user_id}`: searchable (`?q=` matches
+/// `name`), sortable (`?sort=name|created_at`, `-` prefix for descending),
+/// paginated listing — see [`crate::list_query`].
+pub async fn list_categories(
+    pool: web::Data<DbPool>,
+    auth: AuthUser,
+    user_id: web::Path<i32>,
+    query: web::Query<ListQuery>,
+) -> Result<HttpResponse, AppError> {
+    let user_id = user_id.into_inner();
+    if user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    let mut conn = cpool(&pool)?;
+    let pagination = query.pagination();
+
+    let count_filter = || {
+        let mut q = categories::table.filter(categories::user_id.eq(user_id)).into_boxed();
+        if let Some(term) = &query.q {
+            q = q.filter(categories::name.ilike(format!("%{term}%")));
+        }
+        q
+    };
+
+    let total = count_filter().count().get_result::<i64>(&mut conn)?;
+
+    let mut selection = count_filter();
+    let (sort_column, ascending) = query.sort_direction("name");
+    selection = match (sort_column, ascending) {
+        ("name", true) => selection.order(categories::name.asc()),
+        ("name", false) => selection.order(categories::name.desc()),
+        ("created_at", true) => selection.order(categories::created_at.asc()),
+        ("created_at", false) => selection.order(categories::created_at.desc()),
+        _ => return Err(AppError::Validation(format!("cannot sort categories by {sort_column}"))),
+    };
+
+    let items = selection
+        .limit(pagination.limit)
+        .offset(pagination.offset)
+        .select(Category::as_select())
+        .load::<Category>(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(Page {
+        items,
+        page: query.page.max(1),
+        per_page: pagination.limit,
+        total,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct MergeRequest {
+    pub user_id: i32,
+    pub into: String,
+}
+
+/// `POST /api/category/{name}/merge`: repoints every entry, split, budget,
+/// and recurring template from `{name}` onto `body.into`, then drops the
+/// now-unreferenced `{name}` category — the categories analogue of
+/// [`crate::handlers::sources::merge_source`].
+pub async fn merge_category(
+    pool: web::Data<DbPool>,
+    auth: AuthUser,
+    name: web::Path<String>,
+    body: web::Json<MergeRequest>,
+) -> Result<HttpResponse, AppError> {
+    let body = body.into_inner();
+    if body.user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    let mut conn = cpool(&pool)?;
+
+    let from = categories::table
+        .filter(categories::name.eq(name.into_inner()))
+        .filter(categories::user_id.eq(body.user_id))
+        .select(Category::as_select())
+        .first::<Category>(&mut conn)
+        .map_err(|_| AppError::NotFound("category not found".into()))?;
+    let into = categories::table
+        .filter(categories::name.eq(&body.into))
+        .filter(categories::user_id.eq(body.user_id))
+        .select(Category::as_select())
+        .first::<Category>(&mut conn)
+        .map_err(|_| AppError::NotFound("target category not found".into()))?;
+
+    if from.id == into.id {
+        return Err(AppError::Validation("cannot merge a category into itself".into()));
+    }
+
+    let into = conn.transaction::<_, AppError, _>(|conn| {
+        diesel::update(entries::table.filter(entries::category_id.eq(from.id)))
+            .set(entries::category_id.eq(into.id))
+            .execute(conn)?;
+        diesel::update(entry_splits::table.filter(entry_splits::category_id.eq(from.id)))
+            .set(entry_splits::category_id.eq(into.id))
+            .execute(conn)?;
+        diesel::update(recurring_entries::table.filter(recurring_entries::category_id.eq(from.id)))
+            .set(recurring_entries::category_id.eq(into.id))
+            .execute(conn)?;
+        diesel::update(budgets::table.filter(budgets::category_id.eq(from.id)))
+            .set(budgets::category_id.eq(into.id))
+            .execute(conn)?;
+
+        diesel::delete(categories::table.find(from.id)).execute(conn)?;
+
+        categories::table.find(into.id).select(Category::as_select()).first::<Category>(conn)
+    })?;
+
+    Ok(HttpResponse::Ok().json(into))
+}
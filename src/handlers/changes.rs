@@ -0,0 +1,270 @@
+use actix_web::{web, HttpResponse};
+use chrono::{DateTime, Utc};
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::AuthUser;
+use crate::db::{cpool, DbPool};
+use crate::dto::entry::CreateEntryRequest;
+use crate::error::AppError;
+use crate::models::category::Category;
+use crate::models::entry::{Entry, EntryType};
+use crate::models::source::Source;
+use crate::models::sync_mutation::{self, ENTRY};
+use crate::models::tombstone::Tombstone;
+use crate::money::Money;
+use crate::schema::{categories, entries, sources, tombstones};
+
+#[derive(Deserialize)]
+pub struct ChangesQuery {
+    pub user_id: i32,
+    pub since: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+pub struct ChangesReport {
+    pub entries: Vec<Entry>,
+    pub sources: Vec<Source>,
+    pub categories: Vec<Category>,
+    /// Entities hard-deleted on or after `since`. Not user-scoped, unlike
+    /// the rest of this report: [`Tombstone`] doesn't carry a `user_id`
+    /// (see its module doc), so a client currently has to reconcile
+    /// deletions itself against the ids it actually holds.
+    pub tombstones: Vec<Tombstone>,
+}
+
+/// `GET /api/changes?user_id=<id>&since=<cursor>`: everything an
+/// offline-first client needs to catch up since its last sync — rows
+/// created or updated on or after `since`, plus tombstones for the entity
+/// types that get hard-deleted. `since` is just an echoed server
+/// timestamp from the client's last call, not an opaque cursor; there's
+/// no separate sync-cursor table.
+pub async fn list_changes(pool: web::Data<DbPool>, auth: AuthUser, query: web::Query<ChangesQuery>) -> Result<HttpResponse, AppError> {
+    if query.user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    let mut conn = cpool(&pool)?;
+
+    let entries = entries::table
+        .filter(entries::user_id.eq(query.user_id))
+        .filter(entries::updated_at.ge(query.since))
+        .select(Entry::as_select())
+        .load::<Entry>(&mut conn)?;
+
+    let sources = sources::table
+        .filter(sources::user_id.eq(query.user_id))
+        .filter(sources::updated_at.ge(query.since))
+        .select(Source::as_select())
+        .load::<Source>(&mut conn)?;
+
+    let categories = categories::table
+        .filter(categories::user_id.eq(query.user_id))
+        .filter(categories::updated_at.ge(query.since))
+        .select(Category::as_select())
+        .load::<Category>(&mut conn)?;
+
+    let tombstones = tombstones::table
+        .filter(tombstones::deleted_at.ge(query.since))
+        .order(tombstones::deleted_at.asc())
+        .select(Tombstone::as_select())
+        .load::<Tombstone>(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(ChangesReport { entries, sources, categories, tombstones }))
+}
+
+/// One offline-recorded write, batched into a `POST /api/changes` call.
+///
+/// The conflict-resolution strategy differs by entity, since "conflict"
+/// means something different for each:
+///
+/// - `Entry` is create-only, so there's nothing to merge field-by-field —
+///   its strategy is idempotency by `client_id` (see
+///   [`crate::models::sync_mutation`]): resubmitting a `client_id` that
+///   already landed returns the original result instead of duplicating
+///   the entry.
+/// - `SourceAmount` mutates a single scalar in place, so it uses
+///   last-writer-wins: `client_updated_at` (when the client made the
+///   edit) is compared against the server's current `updated_at`. If the
+///   server's `updated_at` differs from `base_updated_at` (what the
+///   client started from) and is at least as new as the client's own edit,
+///   the server's value wins and the client's write is dropped; otherwise the client's
+///   write applies. Either way a [`MutationOutcome::ConflictResolved`] is
+///   returned whenever `base_updated_at` didn't match what the server
+///   actually had, so the client knows a conflict occurred even when its
+///   write ended up winning.
+#[derive(Deserialize)]
+#[serde(tag = "entity_type", rename_all = "snake_case")]
+pub enum ClientMutation {
+    Entry {
+        client_id: String,
+        entry: CreateEntryRequest,
+    },
+    SourceAmount {
+        client_id: String,
+        source_id: i32,
+        base_updated_at: DateTime<Utc>,
+        client_updated_at: DateTime<Utc>,
+        amount: Money,
+    },
+}
+
+#[derive(Deserialize)]
+pub struct PushChangesRequest {
+    pub mutations: Vec<ClientMutation>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictWinner {
+    Client,
+    Server,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum MutationOutcome {
+    /// Applied cleanly — no other write touched this row since the
+    /// client's `base_updated_at`.
+    Applied { client_id: String, entity_id: i32 },
+    /// A conflicting write from elsewhere was detected and resolved
+    /// per-entity (see the [`ClientMutation`] doc comment); `winner`
+    /// says whose value the row now holds.
+    ConflictResolved { client_id: String, entity_id: i32, winner: ConflictWinner, server_updated_at: DateTime<Utc> },
+    Rejected { client_id: String, reason: String },
+}
+
+#[derive(Serialize)]
+pub struct PushChangesReport {
+    pub results: Vec<MutationOutcome>,
+}
+
+/// `POST /api/changes`: applies a batch of offline-recorded mutations,
+/// each independently, in the order given. One mutation failing or
+/// conflicting doesn't roll back the others.
+pub async fn push_changes(
+    pool: web::Data<DbPool>,
+    auth: AuthUser,
+    body: web::Json<PushChangesRequest>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let mut results = Vec::with_capacity(body.mutations.len());
+
+    for mutation in body.into_inner().mutations {
+        results.push(apply_mutation(&mut conn, auth.0, mutation));
+    }
+
+    Ok(HttpResponse::Ok().json(PushChangesReport { results }))
+}
+
+fn apply_mutation(conn: &mut PgConnection, user_id: i32, mutation: ClientMutation) -> MutationOutcome {
+    match mutation {
+        ClientMutation::Entry { client_id, entry } => apply_entry_mutation(conn, user_id, client_id, entry),
+        ClientMutation::SourceAmount { client_id, source_id, base_updated_at, client_updated_at, amount } => {
+            apply_source_amount_mutation(conn, user_id, client_id, source_id, base_updated_at, client_updated_at, amount)
+        }
+    }
+}
+
+/// Merge strategy: idempotent create by `client_id`. A retried submission
+/// (the client never saw the first response, or resent a queued batch)
+/// is answered with the entity created the first time, not a duplicate.
+fn apply_entry_mutation(conn: &mut PgConnection, user_id: i32, client_id: String, entry: CreateEntryRequest) -> MutationOutcome {
+    if entry.user_id != user_id {
+        return MutationOutcome::Rejected { client_id, reason: "user_id does not match the authenticated session".into() };
+    }
+
+    match sync_mutation::find_by_client_id(conn, &client_id) {
+        Ok(Some(existing)) => return MutationOutcome::Applied { client_id, entity_id: existing.entity_id },
+        Ok(None) => {}
+        Err(err) => return MutationOutcome::Rejected { client_id, reason: err.to_string() },
+    }
+
+    match super::entries::insert_entry_with_splits(conn, entry) {
+        Ok(entry) => {
+            if let Err(err) = sync_mutation::record(conn, &client_id, ENTRY, entry.id) {
+                return MutationOutcome::Rejected { client_id, reason: err.to_string() };
+            }
+            MutationOutcome::Applied { client_id, entity_id: entry.id }
+        }
+        Err(err) => MutationOutcome::Rejected { client_id, reason: err.to_string() },
+    }
+}
+
+/// Merge strategy: last-writer-wins. `base_updated_at` (what the client
+/// started editing from) is compared against the server's current
+/// `updated_at` to detect whether anything else wrote to this source in
+/// the meantime; if so, `client_updated_at` and the server's
+/// `updated_at` are compared to decide whose write is actually newer.
+fn apply_source_amount_mutation(
+    conn: &mut PgConnection,
+    user_id: i32,
+    client_id: String,
+    source_id: i32,
+    base_updated_at: DateTime<Utc>,
+    client_updated_at: DateTime<Utc>,
+    amount: Money,
+) -> MutationOutcome {
+    let source = match sources::table.find(source_id).select(Source::as_select()).first::<Source>(conn) {
+        Ok(source) => source,
+        Err(_) => return MutationOutcome::Rejected { client_id, reason: format!("source {source_id} not found") },
+    };
+    if source.user_id != user_id {
+        return MutationOutcome::Rejected { client_id, reason: "source does not belong to the authenticated session".into() };
+    }
+
+    let conflicted = source.updated_at != base_updated_at;
+    if conflicted && source.updated_at >= client_updated_at {
+        // The server's write is at least as new as the client's — the
+        // server keeps its value, and the client's write is dropped.
+        return MutationOutcome::ConflictResolved {
+            client_id,
+            entity_id: source_id,
+            winner: ConflictWinner::Server,
+            server_updated_at: source.updated_at,
+        };
+    }
+    let server_updated_at = source.updated_at;
+
+    let difference = amount - source.amount;
+    let now = Utc::now();
+
+    let result = conn.transaction::<_, AppError, _>(|conn| {
+        if difference != Money::ZERO {
+            let adjustment = crate::models::entry::NewEntry {
+                user_id: source.user_id,
+                source_id: source.id,
+                secondary_source_id: None,
+                category_id: None,
+                currency_id: source.currency_id,
+                entry_type: EntryType::Adjust,
+                amount: difference,
+                source_amount: difference,
+                conversion_rate: 1.0,
+                conversion_rate_to_fixed: 1.0,
+                target: None,
+                description: Some("Offline sync adjustment".into()),
+                notes: None,
+                entry_date: now,
+                created_by: Some(source.user_id),
+                updated_by: Some(source.user_id),
+                counterparty_id: None,
+                payer_id: None,
+            };
+            diesel::insert_into(entries::table).values(&adjustment).execute(conn)?;
+        }
+
+        diesel::update(sources::table.find(source_id)).set((sources::amount.eq(amount), sources::updated_at.eq(now))).execute(conn)?;
+
+        Ok(())
+    });
+    if let Err(err) = result {
+        return MutationOutcome::Rejected { client_id, reason: err.to_string() };
+    }
+
+    if conflicted {
+        MutationOutcome::ConflictResolved { client_id, entity_id: source_id, winner: ConflictWinner::Client, server_updated_at }
+    } else {
+        MutationOutcome::Applied { client_id, entity_id: source_id }
+    }
+}
@@ -0,0 +1,34 @@
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::models::counterparty::{Counterparty, NewCounterparty};
+use crate::schema::counterparties;
+use crate::validation::{Validator, NAME_MAX_LEN};
+
+pub async fn create_counterparty(
+    pool: web::Data<DbPool>,
+    body: web::Json<NewCounterparty>,
+) -> Result<HttpResponse, AppError> {
+    Validator::new().require_non_empty("name", &body.name).require_max_len("name", &body.name, NAME_MAX_LEN).finish()?;
+
+    let mut conn = cpool(&pool)?;
+
+    let counterparty = diesel::insert_into(counterparties::table)
+        .values(&body.into_inner())
+        .get_result::<Counterparty>(&mut conn)?;
+
+    Ok(HttpResponse::Created().json(counterparty))
+}
+
+pub async fn list_counterparties(pool: web::Data<DbPool>, user_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let results = counterparties::table
+        .filter(counterparties::user_id.eq(user_id.into_inner()))
+        .select(Counterparty::as_select())
+        .load(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(results))
+}
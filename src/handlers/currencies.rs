@@ -0,0 +1,416 @@
+use actix_web::{web, HttpResponse};
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::pg::PgTextExpressionMethods;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::config::AppConfig;
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::iso4217;
+use crate::jobs::exchange_rates::{HttpRateProvider, RateProvider};
+use crate::list_query::{ListQuery, Page};
+use crate::models::currency::{Currency, NewCurrency};
+use crate::models::currency_rate::{rate_effective_on, record_rate};
+use crate::schema::{currencies, entries};
+use crate::validation::{Validator, NAME_MAX_LEN};
+
+/// `GET /api/currencies`: searchable (`?q=` matches `code` or `name`),
+/// sortable (`?sort=code|name|created_at`, `-` prefix for descending),
+/// paginated listing — see [`crate::list_query`]. Currencies aren't scoped
+/// to a user, unlike sources/categories, so there's no id in the path.
+pub async fn list_currencies(pool: web::Data<DbPool>, query: web::Query<ListQuery>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let pagination = query.pagination();
+
+    let count_filter = || {
+        let mut q = currencies::table.into_boxed();
+        if let Some(term) = &query.q {
+            let pattern = format!("%{term}%");
+            q = q.filter(currencies::code.ilike(pattern.clone()).or(currencies::name.ilike(pattern)));
+        }
+        q
+    };
+
+    let total = count_filter().count().get_result::<i64>(&mut conn)?;
+
+    let mut selection = count_filter();
+    let (sort_column, ascending) = query.sort_direction("code");
+    selection = match (sort_column, ascending) {
+        ("code", true) => selection.order(currencies::code.asc()),
+        ("code", false) => selection.order(currencies::code.desc()),
+        ("name", true) => selection.order(currencies::name.asc()),
+        ("name", false) => selection.order(currencies::name.desc()),
+        ("created_at", true) => selection.order(currencies::created_at.asc()),
+        ("created_at", false) => selection.order(currencies::created_at.desc()),
+        _ => return Err(AppError::Validation(format!("cannot sort currencies by {sort_column}"))),
+    };
+
+    let items = selection
+        .limit(pagination.limit)
+        .offset(pagination.offset)
+        .select(Currency::as_select())
+        .load::<Currency>(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(Page {
+        items,
+        page: query.page.max(1),
+        per_page: pagination.limit,
+        total,
+    }))
+}
+
+/// Creates a currency by `code`, reviving an archived row with the same
+/// code instead of inserting a duplicate: `code` is only unique among
+/// non-archived rows (`currencies_code_active_key`), so a straight insert
+/// would otherwise leave an unreachable archived row and a live one with
+/// no history connecting them.
+pub async fn create_currency(pool: web::Data<DbPool>, body: web::Json<NewCurrency>) -> Result<HttpResponse, AppError> {
+    let body = body.into_inner();
+    Validator::new()
+        .require_non_empty("name", &body.name)
+        .require_max_len("name", &body.name, NAME_MAX_LEN)
+        .require_finite_positive_rate("rate_to_fixed", body.rate_to_fixed)
+        .finish()?;
+
+    let mut conn = cpool(&pool)?;
+    let currency = create_or_revive_currency(&mut conn, body)?;
+
+    Ok(HttpResponse::Created().json(currency))
+}
+
+/// The DB half of [`create_currency`], split out so the archived-name
+/// revive-on-create logic can be exercised directly in tests without an
+/// actix request around it.
+fn create_or_revive_currency(conn: &mut diesel::pg::PgConnection, body: NewCurrency) -> Result<Currency, AppError> {
+    let archived = currencies::table
+        .filter(currencies::code.eq(&body.code))
+        .filter(currencies::archived.eq(true))
+        .select(Currency::as_select())
+        .first::<Currency>(conn)
+        .optional()?;
+
+    if let Some(archived) = archived {
+        Ok(diesel::update(currencies::table.find(archived.id))
+            .set((
+                currencies::name.eq(&body.name),
+                currencies::rate_to_fixed.eq(body.rate_to_fixed),
+                currencies::archived.eq(false),
+            ))
+            .get_result::<Currency>(conn)?)
+    } else {
+        diesel::insert_into(currencies::table)
+            .values(&body)
+            .get_result::<Currency>(conn)
+            .map_err(|_| AppError::Conflict(format!("currency {} already exists", body.code)))
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ConvertQuery {
+    pub amount: f64,
+    pub from: String,
+    pub to: String,
+    /// When given, uses the `currency_rates` row effective on that day
+    /// instead of today's `rate_to_fixed` (see
+    /// `models::currency_rate::rate_effective_on`).
+    pub date: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+pub struct ConvertResult {
+    pub amount: f64,
+    pub from: String,
+    pub to: String,
+    pub converted: f64,
+    pub rate: f64,
+}
+
+/// One-off conversion for UI quick-entry boxes. Uses the rate effective on
+/// `date` if given, else whatever `rate_to_fixed` is currently on file for
+/// both currencies.
+pub async fn convert(pool: web::Data<DbPool>, query: web::Query<ConvertQuery>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let from = currencies::table
+        .filter(currencies::code.eq(&query.from))
+        .filter(currencies::archived.eq(false))
+        .select(Currency::as_select())
+        .first::<Currency>(&mut conn)
+        .map_err(|_| AppError::NotFound(format!("currency {} not found", query.from)))?;
+    let to = currencies::table
+        .filter(currencies::code.eq(&query.to))
+        .filter(currencies::archived.eq(false))
+        .select(Currency::as_select())
+        .first::<Currency>(&mut conn)
+        .map_err(|_| AppError::NotFound(format!("currency {} not found", query.to)))?;
+
+    let rate = match query.date {
+        Some(date) => {
+            let from_rate = rate_effective_on(&mut conn, &from, date.date_naive())?;
+            let to_rate = rate_effective_on(&mut conn, &to, date.date_naive())?;
+            from_rate / to_rate
+        }
+        None => from.rate_to_fixed / to.rate_to_fixed,
+    };
+
+    Ok(HttpResponse::Ok().json(ConvertResult {
+        amount: query.amount,
+        from: query.from.clone(),
+        to: query.to.clone(),
+        converted: query.amount * rate,
+        rate,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct RefreshRatesRequest {
+    pub fixed_currency_code: String,
+}
+
+#[derive(Serialize)]
+pub struct RefreshRatesReport {
+    pub updated: usize,
+}
+
+pub async fn refresh_rates(
+    pool: web::Data<DbPool>,
+    config: web::Data<AppConfig>,
+    body: web::Json<RefreshRatesRequest>,
+) -> Result<HttpResponse, AppError> {
+    let base_url = config
+        .rate_provider_url
+        .clone()
+        .ok_or_else(|| AppError::Validation("no RATE_PROVIDER_URL configured".into()))?;
+
+    let mut conn = cpool(&pool)?;
+    let provider = HttpRateProvider { base_url };
+    let updated = crate::jobs::exchange_rates::refresh_rates(&mut conn, &provider, &body.fixed_currency_code)
+        .map_err(AppError::Internal)?;
+
+    Ok(HttpResponse::Ok().json(RefreshRatesReport { updated }))
+}
+
+#[derive(Deserialize)]
+pub struct RateBackfillEntry {
+    pub effective_date: NaiveDate,
+    pub rate_to_fixed: f64,
+}
+
+#[derive(Deserialize)]
+pub struct BackfillRatesRequest {
+    /// Historical (date, rate) pairs to record. Pulling a range straight
+    /// from the configured provider isn't implemented yet — every provider
+    /// this codebase talks to (see [`crate::jobs::exchange_rates::HttpRateProvider`])
+    /// only exposes a `/latest` endpoint, so a caller wanting to backfill a
+    /// range has to supply the rates itself for now.
+    pub rates: Vec<RateBackfillEntry>,
+    /// When `true`, also rewrites `conversion_rate_to_fixed` on every entry
+    /// already recorded in this currency on one of `rates`' dates, so
+    /// entries created before the backfill (which fell back to whatever
+    /// rate was on hand at insert time) pick up the corrected history.
+    #[serde(default)]
+    pub recompute_entries: bool,
+}
+
+#[derive(Serialize)]
+pub struct BackfillRatesReport {
+    pub recorded: usize,
+    pub entries_recomputed: usize,
+}
+
+/// `POST /api/currency/{name}/rates/backfill`: appends historical
+/// `currency_rates` rows (see [`record_rate`]) for a currency looked up by
+/// code, optionally correcting past entries' `conversion_rate_to_fixed` to
+/// match.
+pub async fn backfill_rates(
+    pool: web::Data<DbPool>,
+    name: web::Path<String>,
+    body: web::Json<BackfillRatesRequest>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let body = body.into_inner();
+
+    let currency = currencies::table
+        .filter(currencies::code.eq(name.into_inner()))
+        .filter(currencies::archived.eq(false))
+        .select(Currency::as_select())
+        .first::<Currency>(&mut conn)
+        .map_err(|_| AppError::NotFound("currency not found".into()))?;
+
+    for entry in &body.rates {
+        if !entry.rate_to_fixed.is_finite() || entry.rate_to_fixed <= 0.0 {
+            return Err(AppError::Validation(format!("rate for {} must be a positive, finite number", entry.effective_date)));
+        }
+    }
+
+    let mut entries_recomputed = 0;
+    for entry in &body.rates {
+        record_rate(&mut conn, currency.id, entry.rate_to_fixed, entry.effective_date)?;
+
+        if body.recompute_entries {
+            let day_start = entry.effective_date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+            let day_end = day_start + chrono::Duration::days(1);
+            entries_recomputed += diesel::update(
+                entries::table
+                    .filter(entries::currency_id.eq(currency.id))
+                    .filter(entries::entry_date.ge(day_start))
+                    .filter(entries::entry_date.lt(day_end)),
+            )
+            .set(entries::conversion_rate_to_fixed.eq(entry.rate_to_fixed))
+            .execute(&mut conn)?;
+        }
+    }
+
+    Ok(HttpResponse::Ok().json(BackfillRatesReport { recorded: body.rates.len(), entries_recomputed }))
+}
+
+pub async fn archive_currency(pool: web::Data<DbPool>, currency_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let currency = diesel::update(currencies::table.find(currency_id.into_inner()))
+        .set(currencies::archived.eq(true))
+        .get_result::<Currency>(&mut conn)
+        .map_err(|e| crate::error::map_update_error(e, "currency not found"))?;
+
+    Ok(HttpResponse::Ok().json(currency))
+}
+
+#[derive(Deserialize)]
+pub struct FromIsoRequest {
+    pub codes: Vec<String>,
+    /// Base currency to fetch initial `rate_to_fixed` values against. Only
+    /// used when `RATE_PROVIDER_URL` is configured; otherwise every
+    /// created currency defaults to a `rate_to_fixed` of 1.0, same as a
+    /// manually created one.
+    pub fixed_currency_code: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct FromIsoReport {
+    pub created: Vec<Currency>,
+    /// Codes that aren't in the bundled [`iso4217`] catalog.
+    pub unknown_codes: Vec<String>,
+}
+
+/// Bulk-creates currencies from the bundled ISO 4217 catalog, reviving
+/// archived rows the same way [`create_currency`] does.
+pub async fn from_iso(
+    pool: web::Data<DbPool>,
+    config: web::Data<AppConfig>,
+    body: web::Json<FromIsoRequest>,
+) -> Result<HttpResponse, AppError> {
+    let body = body.into_inner();
+    let mut conn = cpool(&pool)?;
+
+    let rates = match (&config.rate_provider_url, &body.fixed_currency_code) {
+        (Some(base_url), Some(fixed_code)) => {
+            let provider = HttpRateProvider { base_url: base_url.clone() };
+            provider.fetch_rates(fixed_code).map_err(AppError::Internal)?
+        }
+        _ => Default::default(),
+    };
+
+    let mut created = Vec::new();
+    let mut unknown_codes = Vec::new();
+
+    for code in &body.codes {
+        let Some(entry) = iso4217::lookup(code) else {
+            unknown_codes.push(code.clone());
+            continue;
+        };
+
+        let rate_to_fixed = rates.get(entry.code).map(|per_fixed| 1.0 / per_fixed).unwrap_or(1.0);
+
+        let archived = currencies::table
+            .filter(currencies::code.eq(entry.code))
+            .filter(currencies::archived.eq(true))
+            .select(Currency::as_select())
+            .first::<Currency>(&mut conn)
+            .optional()?;
+
+        let currency = if let Some(archived) = archived {
+            diesel::update(currencies::table.find(archived.id))
+                .set((
+                    currencies::name.eq(entry.name),
+                    currencies::symbol.eq(entry.symbol),
+                    currencies::decimal_places.eq(entry.decimal_places),
+                    currencies::rate_to_fixed.eq(rate_to_fixed),
+                    currencies::archived.eq(false),
+                ))
+                .get_result::<Currency>(&mut conn)?
+        } else {
+            diesel::insert_into(currencies::table)
+                .values((
+                    currencies::code.eq(entry.code),
+                    currencies::name.eq(entry.name),
+                    currencies::symbol.eq(entry.symbol),
+                    currencies::decimal_places.eq(entry.decimal_places),
+                    currencies::rate_to_fixed.eq(rate_to_fixed),
+                ))
+                .get_result::<Currency>(&mut conn)
+                .map_err(|_| AppError::Conflict(format!("currency {} already exists", entry.code)))?
+        };
+
+        created.push(currency);
+    }
+
+    Ok(HttpResponse::Created().json(FromIsoReport { created, unknown_codes }))
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::Connection;
+
+    use super::*;
+
+    /// Requires `DATABASE_URL` to point at a migrated test database; each
+    /// test rolls its own writes back via `test_transaction` rather than
+    /// relying on external cleanup.
+    fn test_conn() -> diesel::pg::PgConnection {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must point at a migrated test database");
+        diesel::pg::PgConnection::establish(&url).expect("failed to connect to test database")
+    }
+
+    #[test]
+    fn create_or_revive_currency_revives_archived_row_with_same_code() {
+        let mut conn = test_conn();
+        conn.test_transaction::<_, AppError, _>(|conn| {
+            let archived = diesel::insert_into(currencies::table)
+                .values(&NewCurrency { code: "XTC".into(), name: "Old Test Coin".into(), rate_to_fixed: 1.0, symbol: None })
+                .get_result::<Currency>(conn)?;
+            diesel::update(currencies::table.find(archived.id)).set(currencies::archived.eq(true)).execute(conn)?;
+
+            let revived = create_or_revive_currency(
+                conn,
+                NewCurrency { code: "XTC".into(), name: "Test Coin".into(), rate_to_fixed: 2.0, symbol: None },
+            )?;
+
+            assert_eq!(revived.id, archived.id);
+            assert_eq!(revived.name, "Test Coin");
+            assert!(!revived.archived);
+
+            let rows_with_code = currencies::table.filter(currencies::code.eq("XTC")).count().get_result::<i64>(conn)?;
+            assert_eq!(rows_with_code, 1);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn create_or_revive_currency_rejects_duplicate_of_a_live_code() {
+        let mut conn = test_conn();
+        conn.test_transaction::<_, AppError, _>(|conn| {
+            create_or_revive_currency(conn, NewCurrency { code: "XTL".into(), name: "Test Coin".into(), rate_to_fixed: 1.0, symbol: None })?;
+
+            let result = create_or_revive_currency(
+                conn,
+                NewCurrency { code: "XTL".into(), name: "Another Test Coin".into(), rate_to_fixed: 1.0, symbol: None },
+            );
+
+            assert!(matches!(result, Err(AppError::Conflict(_))));
+
+            Ok(())
+        });
+    }
+}
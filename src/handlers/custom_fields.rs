@@ -0,0 +1,48 @@
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use serde::Deserialize;
+
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::models::custom_field::{self, CustomFieldDefinition, CustomFieldType, NewCustomFieldDefinition};
+use crate::schema::custom_field_definitions;
+
+#[derive(Deserialize)]
+pub struct CreateCustomFieldDefinitionRequest {
+    pub user_id: i32,
+    pub key: String,
+    pub field_type: CustomFieldType,
+}
+
+/// Configures a field a user can later attach to entries via
+/// `POST /api/entries`'s `custom: {}` map. `key` is unique per user
+/// (`custom_field_definitions_user_key_idx`).
+pub async fn create_custom_field_definition(
+    pool: web::Data<DbPool>,
+    body: web::Json<CreateCustomFieldDefinitionRequest>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let body = body.into_inner();
+
+    let definition = diesel::insert_into(custom_field_definitions::table)
+        .values(&NewCustomFieldDefinition {
+            user_id: body.user_id,
+            key: body.key,
+            field_type: body.field_type,
+        })
+        .get_result::<CustomFieldDefinition>(&mut conn)
+        .map_err(|e| match e {
+            diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _) => {
+                AppError::Conflict("a custom field with that key already exists".into())
+            }
+            other => other.into(),
+        })?;
+
+    Ok(HttpResponse::Created().json(definition))
+}
+
+pub async fn list_custom_field_definitions(pool: web::Data<DbPool>, user_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let definitions = custom_field::definitions_for_user(&mut conn, user_id.into_inner())?;
+    Ok(HttpResponse::Ok().json(definitions))
+}
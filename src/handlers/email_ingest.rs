@@ -0,0 +1,164 @@
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::config::AppConfig;
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::handlers::entries::{build_entry_draft, insert_entry_with_splits};
+use crate::models::email_ingest::{self, EmailIngestToken, EmailReceipt, NewEmailIngestToken, NewEmailReceipt};
+use crate::schema::{email_ingest_tokens, email_receipts};
+
+#[derive(Serialize)]
+pub struct EmailIngestTokenResponse {
+    pub token: String,
+}
+
+/// `POST /api/email-ingest/token/{user_id}`: issues the secret a user
+/// configures their inbox to forward receipt emails to (as
+/// `POST /ingest/email/{token}`, typically via their email provider's
+/// inbound-parse webhook). A user can hold several tokens at once, same
+/// as [`crate::models::session::Session`] allows several logged-in
+/// devices — there's no reason forwarding from a second address should
+/// require invalidating the first.
+pub async fn create_ingest_token(pool: web::Data<DbPool>, user_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let token = email_ingest::generate_token();
+    diesel::insert_into(email_ingest_tokens::table)
+        .values(&NewEmailIngestToken { user_id: user_id.into_inner(), token: token.clone() })
+        .execute(&mut conn)?;
+
+    Ok(HttpResponse::Created().json(EmailIngestTokenResponse { token }))
+}
+
+/// The subset of an inbound-parse webhook payload (Mailgun/SendGrid/etc.
+/// all expose roughly this shape) this endpoint needs — just enough to
+/// run [`build_entry_draft`] over the body text.
+#[derive(Deserialize, Debug)]
+pub struct InboundEmailPayload {
+    pub subject: Option<String>,
+    pub from: Option<String>,
+    pub text: String,
+}
+
+/// `POST /ingest/email/{token}`: accepts a forwarded receipt email and
+/// creates a draft [`EmailReceipt`] for review, using the same field
+/// resolution [`crate::telegram`]'s message ingestion uses — the only
+/// difference is that a chat message gets inserted immediately while a
+/// forwarded email waits for `POST /api/email-receipts/{id}/confirm`,
+/// since there's no one on the other end of an email to ask "does this
+/// look right?" before committing it.
+pub async fn receive_email(pool: web::Data<DbPool>, config: web::Data<AppConfig>, token: web::Path<String>, body: web::Json<InboundEmailPayload>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let body = body.into_inner();
+
+    let ingest_token = email_ingest_tokens::table
+        .filter(email_ingest_tokens::token.eq(token.into_inner()))
+        .select(EmailIngestToken::as_select())
+        .first::<EmailIngestToken>(&mut conn)
+        .map_err(|_| AppError::NotFound("unknown ingest token".into()))?;
+
+    let text = match &body.subject {
+        Some(subject) => format!("{subject} {}", body.text),
+        None => body.text.clone(),
+    };
+    let draft = build_entry_draft(&mut conn, &config, ingest_token.user_id, &text)?;
+
+    let receipt = diesel::insert_into(email_receipts::table)
+        .values(&NewEmailReceipt {
+            user_id: ingest_token.user_id,
+            ingest_token_id: ingest_token.id,
+            subject: body.subject,
+            sender: body.from,
+            raw_text: body.text,
+            source_id: draft.source_id,
+            category_id: draft.category_id,
+            currency_id: draft.currency_id,
+            entry_type: draft.entry_type,
+            amount: draft.amount,
+            description: draft.description,
+            entry_date: draft.entry_date,
+        })
+        .get_result::<EmailReceipt>(&mut conn)?;
+
+    Ok(HttpResponse::Created().json(receipt))
+}
+
+/// `GET /api/email-receipts/user/{user_id}`: the parsed receipts still
+/// awaiting review (`entry_id IS NULL`).
+pub async fn list_pending_receipts(pool: web::Data<DbPool>, user_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let results = email_receipts::table
+        .filter(email_receipts::user_id.eq(user_id.into_inner()))
+        .filter(email_receipts::entry_id.is_null())
+        .order(email_receipts::created_at.desc())
+        .select(EmailReceipt::as_select())
+        .load::<EmailReceipt>(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(results))
+}
+
+#[derive(Deserialize)]
+pub struct ConfirmReceiptRequest {
+    pub source_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub currency_id: Option<i32>,
+    pub entry_type: Option<crate::models::entry::EntryType>,
+    pub amount: Option<crate::money::Money>,
+}
+
+/// `POST /api/email-receipts/{id}/confirm`: turns a reviewed receipt into
+/// a real entry via
+/// [`crate::handlers::entries::insert_entry_with_splits`]. Any field the
+/// parser couldn't resolve (`source_id`/`currency_id`/`entry_type`/
+/// `amount` are the ones [`crate::dto::entry::CreateEntryRequest`]
+/// requires) must be supplied in the request body — the caller is
+/// expected to have shown the receipt's draft fields to a human first.
+pub async fn confirm_receipt(pool: web::Data<DbPool>, receipt_id: web::Path<i32>, body: web::Json<ConfirmReceiptRequest>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let receipt_id = receipt_id.into_inner();
+    let body = body.into_inner();
+
+    let receipt = email_receipts::table
+        .find(receipt_id)
+        .select(EmailReceipt::as_select())
+        .first::<EmailReceipt>(&mut conn)
+        .map_err(|_| AppError::NotFound("email receipt not found".into()))?;
+    if receipt.entry_id.is_some() {
+        return Err(AppError::Conflict("email receipt already confirmed".into()));
+    }
+
+    let source_id = body.source_id.or(receipt.source_id).ok_or_else(|| AppError::Validation("source_id could not be resolved; supply one".into()))?;
+    let currency_id = body.currency_id.or(receipt.currency_id).ok_or_else(|| AppError::Validation("currency_id could not be resolved; supply one".into()))?;
+    let entry_type = body.entry_type.or(receipt.entry_type).ok_or_else(|| AppError::Validation("entry_type could not be resolved; supply one".into()))?;
+    let amount = body.amount.or(receipt.amount).ok_or_else(|| AppError::Validation("amount could not be resolved; supply one".into()))?;
+
+    let entry = insert_entry_with_splits(
+        &mut conn,
+        crate::dto::entry::CreateEntryRequest {
+            user_id: receipt.user_id,
+            source_id,
+            secondary_source_id: None,
+            category_id: body.category_id.or(receipt.category_id),
+            currency_id,
+            entry_type,
+            amount,
+            target: None,
+            counterparty_id: None,
+            payer_id: None,
+            description: receipt.description.clone(),
+            notes: None,
+            entry_date: receipt.entry_date,
+            splits: None,
+            custom: Default::default(),
+        },
+    )?;
+
+    diesel::update(email_receipts::table.find(receipt_id))
+        .set(email_receipts::entry_id.eq(entry.id))
+        .execute(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(entry))
+}
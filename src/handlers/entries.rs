@@ -0,0 +1,845 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::{DateTime, Duration, Utc};
+use diesel::pg::{PgConnection, PgTextExpressionMethods};
+use diesel::prelude::*;
+use diesel::sql_types::{Double, Float, Integer, Text};
+use rand::RngCore;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::AuthUser;
+use crate::config::AppConfig;
+use crate::crypto;
+use crate::db::{cpool, DbPool, ReportsPool};
+use crate::display_currency;
+use crate::dto::entry::CreateEntryRequest;
+use crate::error::AppError;
+use crate::llm;
+use crate::models::audit_log;
+use crate::models::category::get_or_create_uncategorized;
+use crate::models::custom_field;
+use crate::models::entry::{Entry, EntryType, NewEntry};
+use crate::models::currency::Currency;
+use crate::models::entry_split::{EntrySplit, NewEntrySplit, SplitAllocation};
+use crate::models::user::User;
+use crate::money::Money;
+use crate::schema::{categories, currencies, custom_field_definitions, entries, entry_splits, sources, users};
+use crate::stateful::StatefulTryInto;
+use crate::validation::Validator;
+
+/// Resolves the AES key for `user_id`'s privacy-mode fields from the
+/// [`crypto::UNLOCK_HEADER`] request header, generating and persisting
+/// `users.privacy_salt` on first use if the account doesn't have one yet.
+/// `None` when the account doesn't have privacy mode on. `Err` when it
+/// does but the request carries no unlock header, since there is then no
+/// key to encrypt a new entry's fields under.
+fn privacy_key(conn: &mut PgConnection, user_id: i32, req: &HttpRequest) -> Result<Option<[u8; 32]>, AppError> {
+    let user = users::table
+        .find(user_id)
+        .select(User::as_select())
+        .first::<User>(conn)
+        .map_err(|_| AppError::NotFound(format!("user {user_id} not found")))?;
+    if !user.privacy_mode {
+        return Ok(None);
+    }
+
+    let passphrase = req
+        .headers()
+        .get(crypto::UNLOCK_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| AppError::Validation(format!("{} header is required while privacy mode is enabled", crypto::UNLOCK_HEADER)))?;
+
+    let salt = match user.privacy_salt {
+        Some(salt) => salt,
+        None => {
+            let mut salt = vec![0u8; 16];
+            rand::rngs::OsRng.fill_bytes(&mut salt);
+            diesel::update(users::table.find(user_id)).set(users::privacy_salt.eq(&salt)).execute(conn)?;
+            salt
+        }
+    };
+
+    Ok(Some(crypto::derive_key(passphrase, &salt)))
+}
+
+/// Encrypts `description`/`notes` under `key`, storing `nonce || ciphertext`
+/// hex-encoded in the same text columns plaintext would otherwise occupy.
+fn encrypt_privacy_fields(key: &[u8; 32], description: Option<String>, notes: Option<String>) -> (Option<String>, Option<String>) {
+    (description.map(|d| hex::encode(crypto::encrypt(key, &d))), notes.map(|n| hex::encode(crypto::encrypt(key, &n))))
+}
+
+/// Reverses [`encrypt_privacy_fields`] on an already-loaded [`Entry`]. Left
+/// untouched (still the stored ciphertext) if decryption fails, e.g.
+/// because the request's unlock header doesn't match the user's key.
+fn decrypt_privacy_fields(entry: &mut Entry, key: &[u8; 32]) {
+    if let Some(description) = &entry.description {
+        if let Some(plain) = hex::decode(description).ok().and_then(|blob| crypto::decrypt(key, &blob)) {
+            entry.description = Some(plain);
+        }
+    }
+    if let Some(notes) = &entry.notes {
+        if let Some(plain) = hex::decode(notes).ok().and_then(|blob| crypto::decrypt(key, &blob)) {
+            entry.notes = Some(plain);
+        }
+    }
+}
+
+/// An [`Entry`] plus its `custom: {}` map, the shape every entry-returning
+/// endpoint responds with.
+#[derive(Serialize)]
+pub struct EntryWithCustomFields {
+    #[serde(flatten)]
+    pub entry: Entry,
+    pub custom: HashMap<String, serde_json::Value>,
+}
+
+pub async fn create_entry(
+    req: HttpRequest,
+    pool: web::Data<DbPool>,
+    config: web::Data<AppConfig>,
+    auth: AuthUser,
+    body: web::Json<CreateEntryRequest>,
+) -> Result<HttpResponse, AppError> {
+    if body.user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    if config.strict_mode && body.amount < Money::ZERO {
+        return Err(AppError::Validation("amount must be non-negative in strict mode".into()));
+    }
+    // Unlike the strict_mode check above (an opt-in rejection of merely
+    // suspicious-looking legacy-compatible data), a zero or negative
+    // amount is never meaningful outside `Adjust`, whose sign carries the
+    // direction of a correction — see `reconcile_source`, which books
+    // those directly and never goes through this handler.
+    if body.entry_type != EntryType::Adjust {
+        Validator::new().require_positive("amount", body.amount).finish()?;
+    }
+
+    let mut conn = cpool(&pool)?;
+    let privacy_key = privacy_key(&mut conn, auth.0, &req)?;
+    let mut body = body.into_inner();
+    if let Some(key) = &privacy_key {
+        (body.description, body.notes) = encrypt_privacy_fields(key, body.description, body.notes);
+    }
+
+    let mut entry = insert_entry_with_splits(&mut conn, body)?;
+    if let Some(key) = &privacy_key {
+        decrypt_privacy_fields(&mut entry, key);
+    }
+    let custom = load_custom_fields(&mut conn, entry.id)?;
+
+    Ok(HttpResponse::Created().json(EntryWithCustomFields { entry, custom }))
+}
+
+/// Shared by [`create_entry`] and [`duplicate_entry`]: validates split
+/// totals, inserts the entry and its splits, resolves `custom` against the
+/// user's field definitions, and applies the resulting balance deltas, all
+/// in one transaction.
+pub(crate) fn insert_entry_with_splits(conn: &mut PgConnection, body: CreateEntryRequest) -> Result<Entry, AppError> {
+    if let Some(splits) = &body.splits {
+        let total: Money = splits.iter().map(|s| s.amount).sum();
+        if total != body.amount {
+            return Err(AppError::Validation(format!(
+                "splits sum to {total}, expected {}",
+                body.amount
+            )));
+        }
+    }
+    let splits = body.splits.clone();
+    let custom = body.custom.clone();
+
+    conn.transaction::<_, AppError, _>(|conn| {
+        let new_entry: NewEntry = body.stateful_try_into(conn)?;
+
+        let entry = diesel::insert_into(entries::table)
+            .values(&new_entry)
+            .get_result::<Entry>(conn)?;
+
+        apply_source_deltas(conn, &entry)?;
+
+        if let Some(splits) = splits {
+            let new_splits: Vec<NewEntrySplit> = splits
+                .into_iter()
+                .map(|s| NewEntrySplit {
+                    entry_id: entry.id,
+                    category_id: s.category_id,
+                    amount: s.amount,
+                })
+                .collect();
+            diesel::insert_into(entry_splits::table).values(&new_splits).execute(conn)?;
+        }
+
+        for (key, value) in custom {
+            let definition = custom_field::find_by_key(conn, entry.user_id, &key)?
+                .ok_or_else(|| AppError::Validation(format!("unknown custom field \"{key}\"")))?;
+            let stored = custom_field::validate_and_stringify(definition.field_type, &value)
+                .map_err(|e| AppError::Validation(format!("custom field \"{key}\": {e}")))?;
+            custom_field::upsert_value(conn, entry.id, definition.id, &stored)?;
+        }
+
+        audit_log::record(conn, entry.user_id, "create", "entry", entry.id)?;
+
+        Ok(entry)
+    })
+}
+
+/// Loads an entry's custom field values, joined against their definitions
+/// to know how to parse each one back to JSON.
+fn load_custom_fields(conn: &mut PgConnection, entry_id: i32) -> Result<HashMap<String, serde_json::Value>, AppError> {
+    use crate::models::custom_field::CustomFieldDefinition;
+
+    let values = custom_field::values_for_entry(conn, entry_id)?;
+    let mut out = HashMap::with_capacity(values.len());
+    for value in values {
+        let definition = custom_field_definitions::table
+            .find(value.definition_id)
+            .select(CustomFieldDefinition::as_select())
+            .first::<CustomFieldDefinition>(conn)?;
+        out.insert(definition.key, custom_field::parse(definition.field_type, &value.value));
+    }
+    Ok(out)
+}
+
+#[derive(Deserialize, Default)]
+pub struct DuplicateEntryRequest {
+    pub entry_date: Option<DateTime<Utc>>,
+    pub amount: Option<Money>,
+}
+
+/// Recreates an existing entry as a new one, for the "same as last month"
+/// workflow. Splits are carried over verbatim, so an `amount` override on a
+/// split entry is rejected rather than silently left inconsistent with its
+/// (unscaled) splits.
+pub async fn duplicate_entry(
+    pool: web::Data<DbPool>,
+    auth: AuthUser,
+    entry_id: web::Path<i32>,
+    body: web::Json<DuplicateEntryRequest>,
+) -> Result<HttpResponse, AppError> {
+    let body = body.into_inner();
+    let mut conn = cpool(&pool)?;
+
+    let original = entries::table
+        .find(entry_id.into_inner())
+        .select(Entry::as_select())
+        .first::<Entry>(&mut conn)
+        .map_err(|_| AppError::NotFound("entry not found".into()))?;
+    if original.user_id != auth.0 {
+        return Err(AppError::Unauthorized("entry does not belong to the authenticated session".into()));
+    }
+
+    let original_splits = entry_splits::table
+        .filter(entry_splits::entry_id.eq(original.id))
+        .select(EntrySplit::as_select())
+        .load::<EntrySplit>(&mut conn)?;
+
+    if !original_splits.is_empty() && body.amount.is_some() {
+        return Err(AppError::Validation(
+            "cannot override amount when duplicating a split entry".into(),
+        ));
+    }
+
+    let amount = body.amount.unwrap_or(original.amount);
+    if original.entry_type != EntryType::Adjust {
+        Validator::new().require_positive("amount", amount).finish()?;
+    }
+
+    let original_custom = load_custom_fields(&mut conn, original.id)?;
+
+    let request = CreateEntryRequest {
+        user_id: original.user_id,
+        source_id: original.source_id,
+        secondary_source_id: original.secondary_source_id,
+        category_id: original.category_id,
+        currency_id: original.currency_id,
+        entry_type: original.entry_type,
+        amount,
+        target: original.target.clone(),
+        counterparty_id: original.counterparty_id,
+        payer_id: original.payer_id,
+        description: original.description.clone(),
+        notes: original.notes.clone(),
+        entry_date: body.entry_date.unwrap_or(original.entry_date),
+        splits: if original_splits.is_empty() {
+            None
+        } else {
+            Some(
+                original_splits
+                    .into_iter()
+                    .map(|s| SplitAllocation { category_id: s.category_id, amount: s.amount })
+                    .collect(),
+            )
+        },
+        custom: original_custom,
+    };
+
+    let entry = insert_entry_with_splits(&mut conn, request)?;
+    let custom = load_custom_fields(&mut conn, entry.id)?;
+
+    Ok(HttpResponse::Created().json(EntryWithCustomFields { entry, custom }))
+}
+
+#[derive(Deserialize)]
+pub struct FindEntriesQuery {
+    pub user_id: i32,
+    /// When `true`, restricts to entries in the user's "Uncategorized"
+    /// category (or with no category at all, for entry types that were
+    /// never given the fallback).
+    pub uncategorized: Option<bool>,
+    /// Currency code to convert `amount` into for display. Falls back to
+    /// an `X-Display-Currency` header, then the user's `fixed_currency_id`
+    /// (see [`crate::display_currency`]); if none of those are set,
+    /// `display_amount` is omitted.
+    pub display_currency: Option<String>,
+    /// Ranks entries by `pg_trgm` similarity against `description` instead
+    /// of the prefix matching `GET /api/entry/distinct` uses or the substring
+    /// matching `/api/search` uses, so a typo'd search term still finds close
+    /// matches. Results are ordered by similarity score, most similar
+    /// first, which takes priority over any other ordering.
+    pub description_fuzzy: Option<String>,
+    /// Minimum `similarity()` score (0.0-1.0) a row must clear to be
+    /// included when `description_fuzzy` is set. Defaults to Postgres'
+    /// own `pg_trgm.similarity_threshold` of `0.3`.
+    pub fuzzy_threshold: Option<f32>,
+    /// Catches every other query param, so `?custom.project=Vacation`
+    /// filters to entries whose `custom` map has `project` stored as
+    /// exactly `Vacation`. Anything not prefixed `custom.` is ignored.
+    #[serde(flatten)]
+    pub custom_filters: HashMap<String, String>,
+}
+
+#[derive(QueryableByName)]
+struct FuzzyMatchRow {
+    #[diesel(sql_type = Integer)]
+    id: i32,
+}
+
+#[derive(Serialize)]
+pub struct EntryOut {
+    #[serde(flatten)]
+    pub entry: Entry,
+    pub display_amount: Option<f64>,
+    pub custom: HashMap<String, serde_json::Value>,
+}
+
+pub async fn find_entries(
+    req: HttpRequest,
+    pool: web::Data<DbPool>,
+    auth: AuthUser,
+    query: web::Query<FindEntriesQuery>,
+) -> Result<HttpResponse, AppError> {
+    if query.user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    let mut conn = cpool(&pool)?;
+
+    let mut results = if let Some(term) = &query.description_fuzzy {
+        let threshold = query.fuzzy_threshold.unwrap_or(0.3);
+        let ranked = diesel::sql_query(
+            "SELECT id FROM entries \
+             WHERE user_id = $1 AND description IS NOT NULL AND similarity(description, $2) >= $3 \
+             ORDER BY similarity(description, $2) DESC",
+        )
+        .bind::<Integer, _>(query.user_id)
+        .bind::<Text, _>(term)
+        .bind::<Float, _>(threshold)
+        .load::<FuzzyMatchRow>(&mut conn)?;
+
+        let mut by_id: HashMap<i32, Entry> = entries::table
+            .filter(entries::id.eq_any(ranked.iter().map(|row| row.id)))
+            .select(Entry::as_select())
+            .load::<Entry>(&mut conn)?
+            .into_iter()
+            .map(|entry| (entry.id, entry))
+            .collect();
+        ranked.into_iter().filter_map(|row| by_id.remove(&row.id)).collect()
+    } else {
+        entries::table
+            .filter(entries::user_id.eq(query.user_id))
+            .select(Entry::as_select())
+            .load::<Entry>(&mut conn)?
+    };
+
+    if query.uncategorized.unwrap_or(false) {
+        let uncategorized = get_or_create_uncategorized(&mut conn, query.user_id)?;
+        results.retain(|e| e.category_id.is_none() || e.category_id == Some(uncategorized.id));
+    }
+
+    let display_currency_override = query.display_currency.clone().or_else(|| display_currency::header_override(&req));
+    let target = display_currency::resolve(&mut conn, query.user_id, display_currency_override.as_deref())?;
+
+    // A missing/wrong unlock header just leaves `description`/`notes` as
+    // the stored ciphertext rather than failing the whole listing — unlike
+    // `create_entry`, reading isn't the place to demand a key up front.
+    let privacy_key = privacy_key(&mut conn, query.user_id, &req).unwrap_or(None);
+
+    let mut currency_cache: HashMap<i32, Currency> = HashMap::new();
+    let mut out = Vec::with_capacity(results.len());
+    for mut entry in results.drain(..) {
+        if let Some(key) = &privacy_key {
+            decrypt_privacy_fields(&mut entry, key);
+        }
+        let display_amount = match &target {
+            Some(target) => {
+                if !currency_cache.contains_key(&entry.currency_id) {
+                    let currency = currencies::table
+                        .find(entry.currency_id)
+                        .select(Currency::as_select())
+                        .first::<Currency>(&mut conn)?;
+                    currency_cache.insert(entry.currency_id, currency);
+                }
+                Some(display_currency::convert(&currency_cache[&entry.currency_id], target, entry.amount))
+            }
+            None => None,
+        };
+        let custom = load_custom_fields(&mut conn, entry.id)?;
+        out.push(EntryOut { entry, display_amount, custom });
+    }
+
+    if !query.custom_filters.is_empty() {
+        out.retain(|row| matches_custom_filters(&row.custom, &query.custom_filters));
+    }
+
+    Ok(HttpResponse::Ok().json(out))
+}
+
+/// `custom.<key>=<value>` params match by exact string comparison against
+/// the JSON custom value's string form.
+fn matches_custom_filters(custom: &HashMap<String, serde_json::Value>, filters: &HashMap<String, String>) -> bool {
+    filters.iter().all(|(param, expected)| {
+        let Some(key) = param.strip_prefix("custom.") else { return true };
+        custom.get(key).is_some_and(|actual| match actual {
+            serde_json::Value::String(s) => s == expected,
+            other => &other.to_string() == expected,
+        })
+    })
+}
+
+/// Keeps `sources.amount` in sync with the ledger. Runs inside the same
+/// transaction as the insert in [`create_entry`] so a failure here rolls
+/// back the entry too, rather than leaving the balance drifted. `pub(crate)`
+/// so [`crate::jobs::recurring::run_due`] can apply the same deltas for
+/// materialized recurring entries instead of inserting a row that never
+/// touches `sources.amount`.
+pub(crate) fn apply_source_deltas(conn: &mut PgConnection, entry: &Entry) -> Result<(), AppError> {
+    match entry.entry_type {
+        EntryType::Spend | EntryType::Lend => {
+            adjust_source(conn, entry.source_id, -entry.source_amount)?;
+        }
+        EntryType::Income | EntryType::Borrow | EntryType::Adjust => {
+            adjust_source(conn, entry.source_id, entry.source_amount)?;
+        }
+        EntryType::Convert => {
+            adjust_source(conn, entry.source_id, -entry.source_amount)?;
+            if let Some(secondary_id) = entry.secondary_source_id {
+                let converted = convert_to_source_currency(conn, secondary_id, entry.currency_id, entry.amount)?;
+                adjust_source(conn, secondary_id, converted)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn adjust_source(conn: &mut PgConnection, source_id: i32, delta: Money) -> Result<(), AppError> {
+    diesel::update(sources::table.find(source_id))
+        .set((sources::amount.eq(sources::amount + delta), sources::updated_at.eq(Utc::now())))
+        .execute(conn)?;
+    Ok(())
+}
+
+fn convert_to_source_currency(
+    conn: &mut PgConnection,
+    source_id: i32,
+    from_currency_id: i32,
+    amount: Money,
+) -> Result<Money, AppError> {
+    let source = sources::table
+        .find(source_id)
+        .select(crate::models::source::Source::as_select())
+        .first(conn)
+        .map_err(|_| AppError::NotFound(format!("source {source_id} not found")))?;
+
+    let from_currency = currencies::table
+        .find(from_currency_id)
+        .select(Currency::as_select())
+        .first::<Currency>(conn)?;
+    let to_currency = currencies::table
+        .find(source.currency_id)
+        .select(Currency::as_select())
+        .first::<Currency>(conn)?;
+
+    Ok(amount * from_currency.rate_to_fixed / to_currency.rate_to_fixed)
+}
+
+#[derive(Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AggregateMetric {
+    #[default]
+    Sum,
+    Count,
+    Avg,
+}
+
+#[derive(Deserialize)]
+pub struct AggregateEntriesQuery {
+    pub user_id: i32,
+    /// Comma-separated dimensions to `GROUP BY`: `category`, `source`,
+    /// `currency`, `entry_type`, `month`, or `year`.
+    pub group_by: String,
+    #[serde(default)]
+    pub metric: AggregateMetric,
+}
+
+#[derive(QueryableByName)]
+struct AggregateSqlRow {
+    #[diesel(sql_type = Text)]
+    key: String,
+    #[diesel(sql_type = Double)]
+    value: f64,
+}
+
+#[derive(Serialize)]
+pub struct AggregateBucket {
+    pub key: serde_json::Value,
+    pub value: f64,
+}
+
+/// Maps a `group_by` dimension name to its `(jsonb key, SQL expression)`
+/// pair, rejecting anything not on this list so `group_by` can never
+/// inject arbitrary SQL.
+fn group_by_expr(field: &str) -> Result<(&'static str, &'static str), AppError> {
+    match field {
+        "category" => Ok(("category", "category_id")),
+        "source" => Ok(("source", "source_id")),
+        "currency" => Ok(("currency", "currency_id")),
+        "entry_type" => Ok(("entry_type", "entry_type")),
+        "month" => Ok(("month", "EXTRACT(MONTH FROM entry_date)::int")),
+        "year" => Ok(("year", "EXTRACT(YEAR FROM entry_date)::int")),
+        other => Err(AppError::Validation(format!("cannot group by {other}"))),
+    }
+}
+
+/// `GET /api/entry/aggregate?group_by=category,month&metric=sum`: the SQL
+/// `GROUP BY` counterpart to the in-memory `HashMap` totals every report
+/// handler in [`crate::handlers::reports`] builds by hand — useful when
+/// the caller wants an ad-hoc cross-tab instead of a fixed report shape.
+/// Each bucket's `key` is a JSON object with one entry per `group_by`
+/// dimension, e.g. `{"category": 3, "month": 7}`.
+pub async fn aggregate_entries(pool: web::Data<ReportsPool>, query: web::Query<AggregateEntriesQuery>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool.0)?;
+
+    let dimensions: Vec<(&'static str, &'static str)> = query
+        .group_by
+        .split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(group_by_expr)
+        .collect::<Result<_, _>>()?;
+    if dimensions.is_empty() {
+        return Err(AppError::Validation("group_by must name at least one dimension".into()));
+    }
+
+    let key_object = dimensions.iter().map(|(name, expr)| format!("'{name}', {expr}")).collect::<Vec<_>>().join(", ");
+    let group_by_clause = dimensions.iter().map(|(_, expr)| *expr).collect::<Vec<_>>().join(", ");
+    let metric_expr = match query.metric {
+        AggregateMetric::Sum => "SUM(source_amount)::float8",
+        AggregateMetric::Count => "COUNT(*)::float8",
+        AggregateMetric::Avg => "AVG(source_amount)::float8",
+    };
+
+    let sql = format!(
+        "SELECT jsonb_build_object({key_object})::text AS key, {metric_expr} AS value \
+         FROM entries \
+         WHERE user_id = $1 \
+         GROUP BY {group_by_clause} \
+         ORDER BY value DESC"
+    );
+
+    let rows = diesel::sql_query(sql).bind::<Integer, _>(query.user_id).load::<AggregateSqlRow>(&mut conn)?;
+
+    let buckets: Vec<AggregateBucket> = rows
+        .into_iter()
+        .map(|row| AggregateBucket {
+            key: serde_json::from_str(&row.key).unwrap_or(serde_json::Value::Null),
+            value: row.value,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(buckets))
+}
+
+#[derive(Deserialize)]
+pub struct DistinctValuesQuery {
+    pub user_id: i32,
+    /// `description` or `target` — the two free-text columns the
+    /// frontend's combo-box autocomplete needs suggestions for.
+    pub field: String,
+    /// Only return values starting with this prefix (case-insensitive).
+    pub prefix: Option<String>,
+    #[serde(default = "default_distinct_limit")]
+    pub limit: i64,
+}
+
+fn default_distinct_limit() -> i64 {
+    20
+}
+
+#[derive(QueryableByName)]
+struct DistinctValueRow {
+    #[diesel(sql_type = Text)]
+    value: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    count: i64,
+}
+
+#[derive(Serialize)]
+pub struct DistinctValue {
+    pub value: String,
+    pub count: i64,
+}
+
+/// Maps an autocomplete `field` name to its column, rejecting anything
+/// not on this list so `field` can never inject arbitrary SQL — the same
+/// whitelist pattern [`group_by_expr`] uses for `group_by`.
+fn distinct_field_column(field: &str) -> Result<&'static str, AppError> {
+    match field {
+        "description" => Ok("description"),
+        "target" => Ok("target"),
+        other => Err(AppError::Validation(format!("cannot list distinct values for {other}"))),
+    }
+}
+
+/// `GET /api/entry/distinct?field=description|target`: the most frequent
+/// distinct values of a free-text entry column for this user, optionally
+/// narrowed by `prefix` — powers the description/target combo-box
+/// autocomplete rather than making the frontend fetch every entry just to
+/// build its own suggestion list.
+pub async fn distinct_values(pool: web::Data<ReportsPool>, query: web::Query<DistinctValuesQuery>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool.0)?;
+
+    let column = distinct_field_column(&query.field)?;
+    let limit = query.limit.clamp(1, 100);
+
+    let sql = format!(
+        "SELECT {column} AS value, COUNT(*) AS count \
+         FROM entries \
+         WHERE user_id = $1 AND {column} IS NOT NULL AND {column} ILIKE $2 \
+         GROUP BY {column} \
+         ORDER BY count DESC, {column} ASC \
+         LIMIT $3"
+    );
+
+    let prefix_pattern = format!("{}%", query.prefix.as_deref().unwrap_or("").replace('%', "\\%").replace('_', "\\_"));
+
+    let rows = diesel::sql_query(sql)
+        .bind::<Integer, _>(query.user_id)
+        .bind::<Text, _>(prefix_pattern)
+        .bind::<diesel::sql_types::BigInt, _>(limit)
+        .load::<DistinctValueRow>(&mut conn)?;
+
+    let values: Vec<DistinctValue> = rows.into_iter().map(|row| DistinctValue { value: row.value, count: row.count }).collect();
+
+    Ok(HttpResponse::Ok().json(values))
+}
+
+#[derive(Deserialize)]
+pub struct ParseEntryRequest {
+    pub user_id: i32,
+    pub text: String,
+}
+
+/// A proposed, never-inserted [`CreateEntryRequest`] for the caller to
+/// review and `POST /api/entries` themselves — see [`parse_entry`]. `None`
+/// fields are left for the caller to fill in; nothing here is ever
+/// trusted automatically the way a real entry's fields are.
+#[derive(Serialize)]
+pub struct EntryDraft {
+    pub user_id: i32,
+    pub source_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub currency_id: Option<i32>,
+    pub entry_type: Option<EntryType>,
+    pub amount: Option<Money>,
+    pub description: Option<String>,
+    pub entry_date: DateTime<Utc>,
+    /// How `category_id`/`source_id`/`entry_type` were resolved, so the
+    /// frontend can show "matched your usual Starbucks entry" vs. "LLM
+    /// guess" vs. "couldn't tell, please fill in".
+    pub resolved_by: &'static str,
+}
+
+#[derive(QueryableByName)]
+struct HistoricalMatchRow {
+    #[diesel(sql_type = Integer)]
+    id: i32,
+}
+
+/// Splits `text` into whitespace tokens and picks off the ones that look
+/// like an amount, a currency code, or `today`/`yesterday`; whatever's
+/// left (in original order) becomes the cleaned description. Deterministic
+/// fields like these don't need history or an LLM to extract.
+fn local_parse(text: &str) -> (Option<Money>, Option<String>, DateTime<Utc>, String) {
+    let mut amount = None;
+    let mut currency_code = None;
+    let mut entry_date = Utc::now();
+    let mut description_words = Vec::new();
+
+    for token in text.split_whitespace() {
+        if amount.is_none() {
+            if let Ok(decimal) = Decimal::from_str(token) {
+                amount = Some(Money(decimal));
+                continue;
+            }
+        }
+        if currency_code.is_none() && token.len() == 3 && token.chars().all(|c| c.is_ascii_alphabetic()) {
+            currency_code = Some(token.to_uppercase());
+            continue;
+        }
+        match token.to_lowercase().as_str() {
+            "today" => {
+                entry_date = Utc::now();
+                continue;
+            }
+            "yesterday" => {
+                entry_date = Utc::now() - Duration::days(1);
+                continue;
+            }
+            _ => {}
+        }
+        description_words.push(token);
+    }
+
+    (amount, currency_code, entry_date, description_words.join(" "))
+}
+
+/// `POST /api/entry/parse`: turns a free-text line like "coffee 4.50 EUR
+/// yesterday cash" into a draft entry for the caller to confirm, never
+/// inserting anything itself. Delegates the actual field resolution to
+/// [`build_entry_draft`], shared with [`crate::telegram`]'s message
+/// ingestion so both entry points propose fields the same way.
+pub async fn parse_entry(pool: web::Data<ReportsPool>, body: web::Json<ParseEntryRequest>, config: web::Data<AppConfig>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool.0)?;
+    let body = body.into_inner();
+
+    let draft = build_entry_draft(&mut conn, &config, body.user_id, &body.text)?;
+
+    Ok(HttpResponse::Ok().json(draft))
+}
+
+/// Amount/currency/date are extracted locally ([`local_parse`]);
+/// `category_id`/`source_id`/`entry_type` come from whichever of the
+/// user's past entries has the most `pg_trgm`-similar description (see
+/// the migration backing
+/// [`crate::handlers::entries::FindEntriesQuery::description_fuzzy`]), or
+/// failing that, from [`crate::llm`]'s configured provider, if any.
+pub(crate) fn build_entry_draft(conn: &mut PgConnection, config: &AppConfig, user_id: i32, text: &str) -> Result<EntryDraft, AppError> {
+    let (amount, currency_code, entry_date, description) = local_parse(text);
+
+    let mut currency_id = None;
+    if let Some(code) = &currency_code {
+        currency_id = currencies::table
+            .filter(currencies::code.eq(code))
+            .filter(currencies::archived.eq(false))
+            .select(currencies::id)
+            .first::<i32>(conn)
+            .optional()?;
+    }
+
+    let mut category_id = None;
+    let mut source_id = None;
+    let mut entry_type = None;
+    let mut description = description;
+    let mut resolved_by = "none";
+
+    if !description.is_empty() {
+        let historical_match = diesel::sql_query(
+            "SELECT id FROM entries \
+             WHERE user_id = $1 AND description IS NOT NULL AND similarity(description, $2) >= 0.4 \
+             ORDER BY similarity(description, $2) DESC \
+             LIMIT 1",
+        )
+        .bind::<Integer, _>(user_id)
+        .bind::<Text, _>(&description)
+        .load::<HistoricalMatchRow>(conn)?
+        .into_iter()
+        .next();
+
+        if let Some(row) = historical_match {
+            let matched = entries::table.find(row.id).select(Entry::as_select()).first::<Entry>(conn)?;
+            category_id = matched.category_id;
+            source_id = Some(matched.source_id);
+            entry_type = Some(matched.entry_type);
+            if currency_id.is_none() {
+                currency_id = Some(matched.currency_id);
+            }
+            resolved_by = "history";
+        }
+    }
+
+    if resolved_by == "none" {
+        if let Some(provider) = llm::build(config) {
+            let proposal = provider.parse(text).map_err(AppError::Internal)?;
+
+            if let Some(name) = &proposal.category_name {
+                category_id = categories::table
+                    .filter(categories::user_id.eq(user_id))
+                    .filter(categories::name.ilike(name))
+                    .select(categories::id)
+                    .first::<i32>(conn)
+                    .optional()?;
+            }
+            if let Some(name) = &proposal.source_name {
+                source_id = sources::table
+                    .filter(sources::user_id.eq(user_id))
+                    .filter(sources::name.ilike(name))
+                    .select(sources::id)
+                    .first::<i32>(conn)
+                    .optional()?;
+            }
+            entry_type = proposal.entry_type.as_deref().and_then(parse_entry_type_name);
+            if let Some(cleaned) = proposal.description {
+                description = cleaned;
+            }
+            resolved_by = "llm";
+        }
+    }
+
+    // A last-resort keyword guess so a first-ever message like "spent 20
+    // USD groceries" (no history, no LLM configured) still resolves an
+    // `entry_type` instead of leaving the draft impossible to act on.
+    if entry_type.is_none() {
+        let lower = text.to_lowercase();
+        if lower.contains("spent") || lower.contains("paid") || lower.contains("bought") {
+            entry_type = Some(EntryType::Spend);
+        } else if lower.contains("received") || lower.contains("earned") {
+            entry_type = Some(EntryType::Income);
+        }
+    }
+
+    Ok(EntryDraft {
+        user_id,
+        source_id,
+        category_id,
+        currency_id,
+        entry_type,
+        amount,
+        description: if description.is_empty() { None } else { Some(description) },
+        entry_date,
+        resolved_by,
+    })
+}
+
+fn parse_entry_type_name(name: &str) -> Option<EntryType> {
+    match name.to_lowercase().as_str() {
+        "spend" => Some(EntryType::Spend),
+        "income" => Some(EntryType::Income),
+        "convert" => Some(EntryType::Convert),
+        "lend" => Some(EntryType::Lend),
+        "borrow" => Some(EntryType::Borrow),
+        "adjust" => Some(EntryType::Adjust),
+        _ => None,
+    }
+}
@@ -0,0 +1,71 @@
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use serde::Deserialize;
+
+use crate::db::{cpool, ReportsPool};
+use crate::error::AppError;
+use crate::export::{beancount, ledger, xlsx};
+use crate::models::category::Category;
+use crate::models::currency::Currency;
+use crate::models::entry::Entry;
+use crate::models::source::Source;
+use crate::schema::{categories, currencies, entries, sources};
+
+pub async fn export_beancount(pool: web::Data<ReportsPool>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool.0)?;
+
+    let all_entries = entries::table.select(Entry::as_select()).load(&mut conn)?;
+    let all_sources = sources::table.select(Source::as_select()).load(&mut conn)?;
+    let all_categories = categories::table.select(Category::as_select()).load(&mut conn)?;
+    let all_currencies = currencies::table.select(Currency::as_select()).load(&mut conn)?;
+
+    let body = beancount::render(&all_entries, &all_sources, &all_categories, &all_currencies);
+
+    Ok(HttpResponse::Ok().content_type("text/plain; charset=utf-8").body(body))
+}
+
+#[derive(Deserialize)]
+pub struct ExportLedgerQuery {
+    #[serde(default)]
+    pub format: LedgerFormat,
+}
+
+#[derive(Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LedgerFormat {
+    #[default]
+    Beancount,
+    Ledger,
+    Xlsx,
+}
+
+/// Same underlying data as [`export_beancount`], but lets the caller pick
+/// beancount or ledger-cli syntax via `?format=`, or `?format=xlsx` for a
+/// two-sheet workbook (see [`xlsx::render_entries`]) streamed as a
+/// download instead of plain text.
+pub async fn export_ledger(pool: web::Data<ReportsPool>, query: web::Query<ExportLedgerQuery>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool.0)?;
+
+    let all_entries = entries::table.select(Entry::as_select()).load(&mut conn)?;
+    let all_sources = sources::table.select(Source::as_select()).load(&mut conn)?;
+    let all_categories = categories::table.select(Category::as_select()).load(&mut conn)?;
+    let all_currencies = currencies::table.select(Currency::as_select()).load(&mut conn)?;
+
+    match query.format {
+        LedgerFormat::Beancount => {
+            let body = beancount::render(&all_entries, &all_sources, &all_categories, &all_currencies);
+            Ok(HttpResponse::Ok().content_type("text/plain; charset=utf-8").body(body))
+        }
+        LedgerFormat::Ledger => {
+            let body = ledger::render(&all_entries, &all_sources, &all_categories, &all_currencies);
+            Ok(HttpResponse::Ok().content_type("text/plain; charset=utf-8").body(body))
+        }
+        LedgerFormat::Xlsx => {
+            let body = xlsx::render_entries(&all_entries, &all_sources, &all_categories, &all_currencies)?;
+            Ok(HttpResponse::Ok()
+                .content_type("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+                .insert_header(("Content-Disposition", "attachment; filename=\"entries.xlsx\""))
+                .body(body))
+        }
+    }
+}
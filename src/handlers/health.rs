@@ -0,0 +1,44 @@
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use diesel_migrations::MigrationHarness;
+use serde::Serialize;
+
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::MIGRATIONS;
+
+/// `GET /healthz`: the process is up and able to handle requests at all —
+/// doesn't touch the database, so it stays green even while Postgres is
+/// unreachable. An orchestrator should use this for liveness (restart the
+/// container if it stops responding), not readiness.
+pub async fn healthz() -> HttpResponse {
+    HttpResponse::Ok().finish()
+}
+
+#[derive(Serialize)]
+struct ReadyBody {
+    database: &'static str,
+    pending_migrations: Vec<String>,
+}
+
+/// `GET /readyz`: can this instance actually serve traffic right now —
+/// runs `SELECT 1` through the pool and checks for pending migrations,
+/// instead of letting a dead DB only surface as a panic the first time
+/// some handler calls [`cpool`]. Used for readiness (take the instance out
+/// of rotation, don't restart it).
+pub async fn readyz(pool: web::Data<DbPool>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    diesel::sql_query("SELECT 1").execute(&mut conn).map_err(|e| AppError::Internal(format!("database unreachable: {e}")))?;
+
+    let pending_migrations: Vec<String> = conn
+        .pending_migrations(MIGRATIONS)
+        .map(|migrations| migrations.iter().map(|m| m.name().to_string()).collect())
+        .map_err(|e| AppError::Internal(format!("failed to check pending migrations: {e}")))?;
+
+    if !pending_migrations.is_empty() {
+        return Err(AppError::Internal(format!("pending migrations: {}", pending_migrations.join(", "))));
+    }
+
+    Ok(HttpResponse::Ok().json(ReadyBody { database: "ok", pending_migrations }))
+}
@@ -0,0 +1,18 @@
+use actix_web::{web, HttpResponse};
+
+use crate::error::AppError;
+use crate::import::{firefly_csv, gnucash_csv, ynab};
+
+/// `POST /api/import/{format}` — body is the raw export file; returns a
+/// preview only. No sources/categories/entries are created, and no
+/// `?commit=true` step is handled (see [`crate::import`]).
+pub async fn preview_import(format: web::Path<String>, body: String) -> Result<HttpResponse, AppError> {
+    let preview = match format.as_str() {
+        "ynab" => ynab::preview(&body),
+        "firefly" => firefly_csv::preview(&body),
+        "gnucash" => gnucash_csv::preview(&body),
+        other => return Err(AppError::Validation(format!("unsupported import format: {other}"))),
+    };
+
+    Ok(HttpResponse::Ok().json(preview))
+}
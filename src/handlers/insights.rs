@@ -0,0 +1,203 @@
+use std::collections::{HashMap, HashSet};
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::{Datelike, NaiveDate};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::AuthUser;
+use crate::cache::conditional_json;
+use crate::db::{cpool, ReportsPool};
+use crate::error::AppError;
+use crate::models::entry::{Entry, EntryType};
+use crate::schema::{categories, entries, sources};
+
+#[derive(Deserialize)]
+pub struct PatternsQuery {
+    pub user_id: i32,
+}
+
+#[derive(Serialize)]
+pub struct PatternsReport {
+    /// Total spend per weekday, indexed Monday(0)..Sunday(6).
+    pub day_of_week: [f64; 7],
+    /// Total spend per day-of-month, indexed 1st(0)..31st(30).
+    pub day_of_month: [f64; 31],
+    /// Category ids in the order they index `co_occurrence`.
+    pub categories: Vec<i32>,
+    /// Symmetric matrix: `co_occurrence[i][j]` is the number of distinct
+    /// days on which both `categories[i]` and `categories[j]` had a spend.
+    pub co_occurrence: Vec<Vec<u32>>,
+}
+
+/// Server-side spending pattern crunching so the frontend doesn't have to
+/// pull every raw entry to build heatmaps and category-correlation charts.
+pub async fn patterns(
+    req: HttpRequest,
+    auth: AuthUser,
+    pool: web::Data<ReportsPool>,
+    query: web::Query<PatternsQuery>,
+) -> Result<HttpResponse, AppError> {
+    if query.user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    let mut conn = cpool(&pool.0)?;
+
+    let spends: Vec<Entry> = entries::table
+        .filter(entries::user_id.eq(query.user_id))
+        .filter(entries::entry_type.eq(EntryType::Spend))
+        .select(Entry::as_select())
+        .load(&mut conn)?;
+
+    let mut day_of_week = [0.0; 7];
+    let mut day_of_month = [0.0; 31];
+    let mut categories_by_day: HashMap<NaiveDate, HashSet<i32>> = HashMap::new();
+
+    for entry in &spends {
+        let date = entry.entry_date.date_naive();
+        let source_amount = entry.source_amount.to_f64_lossy();
+        day_of_week[date.weekday().num_days_from_monday() as usize] += source_amount;
+        day_of_month[date.day0() as usize] += source_amount;
+
+        if let Some(category_id) = entry.category_id {
+            categories_by_day.entry(date).or_default().insert(category_id);
+        }
+    }
+
+    let mut categories: Vec<i32> = categories_by_day
+        .values()
+        .flat_map(|set| set.iter().copied())
+        .collect::<HashSet<i32>>()
+        .into_iter()
+        .collect();
+    categories.sort_unstable();
+    let index: HashMap<i32, usize> = categories.iter().enumerate().map(|(i, &c)| (c, i)).collect();
+
+    let mut co_occurrence = vec![vec![0u32; categories.len()]; categories.len()];
+    for same_day in categories_by_day.values() {
+        let ids: Vec<i32> = same_day.iter().copied().collect();
+        for a in 0..ids.len() {
+            for b in (a + 1)..ids.len() {
+                let (i, j) = (index[&ids[a]], index[&ids[b]]);
+                co_occurrence[i][j] += 1;
+                co_occurrence[j][i] += 1;
+            }
+        }
+    }
+
+    Ok(conditional_json(&req, &PatternsReport {
+        day_of_week,
+        day_of_month,
+        categories,
+        co_occurrence,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct DuplicateEntitiesQuery {
+    pub user_id: i32,
+}
+
+#[derive(Serialize)]
+pub struct DuplicateCandidate {
+    pub entity_type: &'static str,
+    pub id: i32,
+    pub name: String,
+    pub duplicate_of_id: i32,
+    pub duplicate_of_name: String,
+    /// Dice coefficient over each name's trigram set, same measure
+    /// Postgres's `pg_trgm` `similarity()` uses — 1.0 for an exact
+    /// case-insensitive match, trailing off from there.
+    pub similarity: f64,
+    /// The path segment to `POST` `{"user_id", "into": duplicate_of_name}`
+    /// to, to fold `name` into `duplicate_of_name`.
+    pub merge_endpoint: String,
+}
+
+#[derive(Serialize)]
+pub struct DuplicateEntitiesReport {
+    pub candidates: Vec<DuplicateCandidate>,
+}
+
+/// Similarity threshold above which two names are flagged as likely
+/// duplicates — low enough to catch single-character typos in short
+/// names, high enough to leave genuinely distinct names alone.
+const DUPLICATE_SIMILARITY_THRESHOLD: f64 = 0.5;
+
+fn trigrams(name: &str) -> HashSet<String> {
+    let padded: Vec<char> = format!("  {}  ", name.to_lowercase()).chars().collect();
+    if padded.len() < 3 {
+        return HashSet::from([padded.into_iter().collect()]);
+    }
+    padded.windows(3).map(|w| w.iter().collect()).collect()
+}
+
+fn trigram_similarity(a: &str, b: &str) -> f64 {
+    let (ta, tb) = (trigrams(a), trigrams(b));
+    let intersection = ta.intersection(&tb).count();
+    (2 * intersection) as f64 / (ta.len() + tb.len()) as f64
+}
+
+/// Flags near-duplicate names within one entity's rows, always suggesting
+/// the merge run from the higher id into the lower (older, presumably
+/// canonical) one, so `{"user_id", "into"}` never needs to be resolved by
+/// the caller.
+fn find_duplicates(entity_type: &'static str, rows: &[(i32, String)], merge_path: &str) -> Vec<DuplicateCandidate> {
+    let mut candidates = Vec::new();
+
+    for i in 0..rows.len() {
+        for j in (i + 1)..rows.len() {
+            let (id_a, name_a) = &rows[i];
+            let (id_b, name_b) = &rows[j];
+            let similarity = trigram_similarity(name_a, name_b);
+            if similarity < DUPLICATE_SIMILARITY_THRESHOLD {
+                continue;
+            }
+
+            let (canonical, duplicate) = if id_a < id_b { ((id_a, name_a), (id_b, name_b)) } else { ((id_b, name_b), (id_a, name_a)) };
+
+            candidates.push(DuplicateCandidate {
+                entity_type,
+                id: *duplicate.0,
+                name: duplicate.1.clone(),
+                duplicate_of_id: *canonical.0,
+                duplicate_of_name: canonical.1.clone(),
+                similarity,
+                merge_endpoint: format!("{merge_path}/{}/merge", duplicate.1),
+            });
+        }
+    }
+
+    candidates
+}
+
+/// `GET /api/insights/duplicate-entities`: flags likely duplicate
+/// categories and sources (case differences, typos) via trigram name
+/// similarity, each paired with the [`crate::handlers::sources::merge_source`]
+/// / [`crate::handlers::categories::merge_category`] call that would fold
+/// them together.
+pub async fn duplicate_entities(
+    auth: AuthUser,
+    pool: web::Data<ReportsPool>,
+    query: web::Query<DuplicateEntitiesQuery>,
+) -> Result<HttpResponse, AppError> {
+    if query.user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    let mut conn = cpool(&pool.0)?;
+
+    let category_rows: Vec<(i32, String)> = categories::table
+        .filter(categories::user_id.eq(query.user_id))
+        .select((categories::id, categories::name))
+        .load(&mut conn)?;
+    let source_rows: Vec<(i32, String)> = sources::table
+        .filter(sources::user_id.eq(query.user_id))
+        .select((sources::id, sources::name))
+        .load(&mut conn)?;
+
+    let mut candidates = find_duplicates("category", &category_rows, "/api/category");
+    candidates.extend(find_duplicates("source", &source_rows, "/api/source"));
+    candidates.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap());
+
+    Ok(HttpResponse::Ok().json(DuplicateEntitiesReport { candidates }))
+}
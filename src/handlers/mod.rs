@@ -0,0 +1,36 @@
+pub mod admin;
+pub mod alerts;
+pub mod attachments;
+pub mod audit;
+pub mod backup;
+pub mod bank_connections;
+pub mod budgets;
+pub mod categories;
+pub mod changes;
+pub mod counterparties;
+pub mod currencies;
+pub mod custom_fields;
+pub mod email_ingest;
+pub mod entries;
+pub mod export;
+pub mod health;
+pub mod import;
+pub mod insights;
+pub mod networth;
+pub mod oidc;
+pub mod openapi;
+pub mod payers;
+pub mod recurring;
+pub mod report_schedules;
+pub mod reports;
+pub mod rules;
+pub mod saved_queries;
+pub mod search;
+pub mod simulate;
+pub mod share;
+pub mod sources;
+pub mod stats;
+pub mod summary;
+pub mod telegram;
+pub mod users;
+pub mod views;
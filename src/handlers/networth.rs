@@ -0,0 +1,63 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::db::{cpool, DbPool};
+use crate::display_currency;
+use crate::error::AppError;
+use crate::jobs::networth;
+use crate::models::networth_snapshot::NetworthSnapshot;
+use crate::schema::networth_snapshots;
+
+#[derive(Deserialize)]
+pub struct NetworthQuery {
+    pub user_id: i32,
+    /// See [`crate::display_currency`]. Falls back to an
+    /// `X-Display-Currency` header, then the user's `fixed_currency_id`.
+    pub display_currency: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct NetworthResponse {
+    pub currency_id: i32,
+    pub amount: f64,
+}
+
+/// `GET /api/networth`: live sum of every non-archived source balance,
+/// normalized into the resolved display currency. Unlike
+/// [`crate::handlers::stats::stats`], there's no "native currency"
+/// fallback here — summing balances across currencies without converting
+/// them first would be meaningless, so a request that can't resolve any
+/// display currency is rejected instead of silently adding incompatible
+/// totals together.
+pub async fn networth(req: HttpRequest, pool: web::Data<DbPool>, query: web::Query<NetworthQuery>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let display_currency_override = query.display_currency.clone().or_else(|| display_currency::header_override(&req));
+    let target = display_currency::resolve(&mut conn, query.user_id, display_currency_override.as_deref())?
+        .ok_or_else(|| AppError::Validation("no display currency resolved: set display_currency, X-Display-Currency, or the user's fixed_currency_id".into()))?;
+
+    let amount = networth::current_networth(&mut conn, query.user_id, &target)?;
+
+    Ok(HttpResponse::Ok().json(NetworthResponse { currency_id: target.id, amount }))
+}
+
+#[derive(Deserialize)]
+pub struct NetworthHistoryQuery {
+    pub user_id: i32,
+}
+
+/// `GET /api/networth/history`: every [`NetworthSnapshot`] recorded for
+/// the user, oldest first, already normalized to whatever their
+/// `fixed_currency_id` was on the day each snapshot was taken.
+pub async fn networth_history(pool: web::Data<DbPool>, query: web::Query<NetworthHistoryQuery>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let snapshots = networth_snapshots::table
+        .filter(networth_snapshots::user_id.eq(query.user_id))
+        .order(networth_snapshots::snapshot_date.asc())
+        .select(NetworthSnapshot::as_select())
+        .load::<NetworthSnapshot>(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(snapshots))
+}
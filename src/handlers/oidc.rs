@@ -0,0 +1,123 @@
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use rand::RngCore;
+use serde::Deserialize;
+
+use crate::config::AppConfig;
+use crate::db::{cpool, DbPool};
+use crate::dto::user::{LoginResponse, UserResponse};
+use crate::error::AppError;
+use crate::models::oidc_login_state;
+use crate::models::session::{self, NewSession};
+use crate::models::user::{NewUser, User};
+use crate::oidc;
+use crate::password;
+use crate::schema::{sessions, users};
+
+fn require_configured(config: &AppConfig) -> Result<&str, AppError> {
+    config.oidc_issuer.as_deref().ok_or_else(|| AppError::Validation("OIDC login is not configured".into()))
+}
+
+/// `GET /login/oidc/start`: redirects the browser to the provider's
+/// authorization endpoint with a freshly minted, single-use `state` (see
+/// [`oidc_login_state`]) for [`oidc_callback`] to check.
+pub async fn oidc_start(pool: web::Data<DbPool>, config: web::Data<AppConfig>) -> Result<HttpResponse, AppError> {
+    let issuer = require_configured(&config)?;
+    let mut conn = cpool(&pool)?;
+
+    let discovery = oidc::discover(issuer).map_err(AppError::Internal)?;
+
+    let state = oidc_login_state::generate();
+    oidc_login_state::record(&mut conn, &state)?;
+
+    let url = oidc::authorization_url(&discovery, &config, &state);
+    Ok(HttpResponse::Found().append_header(("Location", url)).finish())
+}
+
+#[derive(Deserialize)]
+pub struct OidcCallbackQuery {
+    pub code: String,
+    pub state: String,
+}
+
+/// `GET /login/oidc/callback`: exchanges `code` for an access token,
+/// resolves the caller's local account (matching `oidc_subject`, then
+/// falling back to linking an existing account by email, then creating a
+/// new one), and issues a session token the same way [`crate::handlers::users::login`]
+/// does.
+pub async fn oidc_callback(
+    pool: web::Data<DbPool>,
+    config: web::Data<AppConfig>,
+    query: web::Query<OidcCallbackQuery>,
+) -> Result<HttpResponse, AppError> {
+    let issuer = require_configured(&config)?;
+    let mut conn = cpool(&pool)?;
+
+    if !oidc_login_state::consume(&mut conn, &query.state)? {
+        return Err(AppError::Unauthorized("OIDC login state is invalid, expired, or already used".into()));
+    }
+
+    let discovery = oidc::discover(issuer).map_err(AppError::Internal)?;
+    let access_token = oidc::exchange_code(&discovery, &config, &query.code).map_err(AppError::Internal)?;
+    let info = oidc::fetch_userinfo(&discovery, &access_token).map_err(AppError::Internal)?;
+
+    let user = conn.transaction::<_, AppError, _>(|conn| resolve_or_create_user(conn, &info))?;
+
+    if user.disabled {
+        return Err(AppError::Unauthorized(format!("account {} is disabled", user.id)));
+    }
+
+    let token = session::generate_token();
+    diesel::insert_into(sessions::table)
+        .values(&NewSession { user_id: user.id, token: token.clone(), device_name: Some("OIDC".into()) })
+        .execute(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(LoginResponse { user: UserResponse::from(&user), token }))
+}
+
+fn resolve_or_create_user(conn: &mut diesel::pg::PgConnection, info: &oidc::OidcUserInfo) -> Result<User, AppError> {
+    if let Some(user) = users::table
+        .filter(users::oidc_subject.eq(&info.sub))
+        .select(User::as_select())
+        .first::<User>(conn)
+        .optional()?
+    {
+        return Ok(user);
+    }
+
+    // Only a verified email is proof the provider vouches for ownership; an
+    // unverified one is just a claim, and auto-linking `sub` onto whichever
+    // local account happens to have that address would let a malicious or
+    // misconfigured provider take over an existing password-based account.
+    if info.email_verified {
+        if let Some(email) = &info.email {
+            if let Some(user) = users::table
+                .filter(users::email.eq(email))
+                .select(User::as_select())
+                .first::<User>(conn)
+                .optional()?
+            {
+                return Ok(diesel::update(users::table.find(user.id))
+                    .set(users::oidc_subject.eq(&info.sub))
+                    .get_result::<User>(conn)?);
+            }
+        }
+    }
+
+    let email = info
+        .email
+        .clone()
+        .ok_or_else(|| AppError::Validation("provider did not return an email to create an account with".into()))?;
+
+    // The account is only ever reachable via the OIDC flow, so the password
+    // hash just needs to never validate against anything a caller could
+    // supply — same idea as `models::session::generate_token` not needing
+    // to be derived from anything guessable.
+    let mut random_password = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut random_password);
+    let unusable_hash = password::hash(&hex::encode(random_password));
+
+    Ok(diesel::insert_into(users::table)
+        .values(&NewUser { email, password_hash: unusable_hash, oidc_subject: Some(info.sub.clone()) })
+        .get_result::<User>(conn)?)
+}
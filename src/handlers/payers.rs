@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::AuthUser;
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::models::entry::{Entry, EntryType};
+use crate::models::payer::{NewPayer, Payer};
+use crate::schema::{entries, payers};
+use crate::validation::{Validator, NAME_MAX_LEN};
+
+pub async fn create_payer(pool: web::Data<DbPool>, body: web::Json<NewPayer>) -> Result<HttpResponse, AppError> {
+    Validator::new().require_non_empty("name", &body.name).require_max_len("name", &body.name, NAME_MAX_LEN).finish()?;
+
+    let mut conn = cpool(&pool)?;
+
+    let payer = diesel::insert_into(payers::table)
+        .values(&body.into_inner())
+        .get_result::<Payer>(&mut conn)?;
+
+    Ok(HttpResponse::Created().json(payer))
+}
+
+pub async fn list_payers(pool: web::Data<DbPool>, auth: AuthUser, user_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let user_id = user_id.into_inner();
+    if user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    let mut conn = cpool(&pool)?;
+
+    let results = payers::table
+        .filter(payers::user_id.eq(user_id))
+        .select(Payer::as_select())
+        .load(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(results))
+}
+
+#[derive(Deserialize)]
+pub struct IncomeByPayerQuery {
+    pub user_id: i32,
+}
+
+#[derive(Serialize)]
+pub struct PayerIncome {
+    pub payer_id: i32,
+    pub payer_name: String,
+    pub total: f64,
+}
+
+#[derive(Serialize)]
+pub struct IncomeByPayerReport {
+    pub payers: Vec<PayerIncome>,
+}
+
+/// How much of a user's income came from each payer, for freelancers
+/// tracking revenue by client instead of by category.
+pub async fn income_by_payer(
+    pool: web::Data<DbPool>,
+    auth: AuthUser,
+    query: web::Query<IncomeByPayerQuery>,
+) -> Result<HttpResponse, AppError> {
+    if query.user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    let mut conn = cpool(&pool)?;
+
+    let income: Vec<Entry> = entries::table
+        .filter(entries::user_id.eq(query.user_id))
+        .filter(entries::entry_type.eq(EntryType::Income))
+        .filter(entries::payer_id.is_not_null())
+        .select(Entry::as_select())
+        .load(&mut conn)?;
+
+    let all_payers = payers::table
+        .filter(payers::user_id.eq(query.user_id))
+        .select(Payer::as_select())
+        .load::<Payer>(&mut conn)?;
+    let names: HashMap<i32, String> = all_payers.into_iter().map(|p| (p.id, p.name)).collect();
+
+    let mut totals: HashMap<i32, f64> = HashMap::new();
+    for entry in &income {
+        if let Some(payer_id) = entry.payer_id {
+            *totals.entry(payer_id).or_insert(0.0) += entry.source_amount.to_f64_lossy();
+        }
+    }
+
+    let mut payers: Vec<PayerIncome> = totals
+        .into_iter()
+        .map(|(payer_id, total)| PayerIncome {
+            payer_id,
+            payer_name: names.get(&payer_id).cloned().unwrap_or_default(),
+            total,
+        })
+        .collect();
+    payers.sort_by(|a, b| b.total.partial_cmp(&a.total).unwrap());
+
+    Ok(HttpResponse::Ok().json(IncomeByPayerReport { payers }))
+}
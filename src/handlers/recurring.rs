@@ -0,0 +1,50 @@
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::auth::AdminUser;
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::jobs::recurring;
+use crate::models::recurring_entry::{NewRecurringEntry, RecurringEntry};
+use crate::schema::recurring_entries;
+
+pub async fn create_recurring(
+    pool: web::Data<DbPool>,
+    body: web::Json<NewRecurringEntry>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let recurring = diesel::insert_into(recurring_entries::table)
+        .values(&body.into_inner())
+        .get_result::<RecurringEntry>(&mut conn)?;
+
+    Ok(HttpResponse::Created().json(recurring))
+}
+
+pub async fn list_recurring(pool: web::Data<DbPool>, user_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let results = recurring_entries::table
+        .filter(recurring_entries::user_id.eq(user_id.into_inner()))
+        .select(RecurringEntry::as_select())
+        .load(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(results))
+}
+
+#[derive(Serialize)]
+pub struct RecurringRunReport {
+    pub materialized: usize,
+}
+
+/// `POST /api/admin/recurring/run`: runs [`recurring::run_due`] for every
+/// user, for use until this runs on a schedule (see that module's doc
+/// comment).
+pub async fn run_due_recurring(_admin: AdminUser, pool: web::Data<DbPool>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let materialized = recurring::run_due(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(RecurringRunReport { materialized }))
+}
@@ -0,0 +1,64 @@
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::auth::AdminUser;
+use crate::config::AppConfig;
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::jobs::report_schedules;
+use crate::mail;
+use crate::models::report_schedule::{NewReportSchedule, ReportSchedule};
+use crate::schema::report_schedules as report_schedules_table;
+
+pub async fn create_report_schedule(
+    pool: web::Data<DbPool>,
+    body: web::Json<NewReportSchedule>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let schedule = diesel::insert_into(report_schedules_table::table)
+        .values(&body.into_inner())
+        .get_result::<ReportSchedule>(&mut conn)?;
+
+    Ok(HttpResponse::Created().json(schedule))
+}
+
+pub async fn list_report_schedules(pool: web::Data<DbPool>, user_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let results = report_schedules_table::table
+        .filter(report_schedules_table::user_id.eq(user_id.into_inner()))
+        .select(ReportSchedule::as_select())
+        .load(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(results))
+}
+
+pub async fn delete_report_schedule(pool: web::Data<DbPool>, schedule_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let deleted = diesel::delete(report_schedules_table::table.find(schedule_id.into_inner())).execute(&mut conn)?;
+    if deleted == 0 {
+        return Err(AppError::NotFound("report schedule not found".into()));
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Serialize)]
+pub struct ReportSchedulesSentReport {
+    pub sent: usize,
+}
+
+/// `POST /api/admin/report-schedules/run`: runs
+/// [`report_schedules::run_due`] for every user, for use until this runs
+/// on a schedule (see that module's doc comment).
+pub async fn run_due_report_schedules(_admin: AdminUser, pool: web::Data<DbPool>, config: web::Data<AppConfig>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let mailer = mail::build(&config);
+
+    let sent = report_schedules::run_due(&mut conn, mailer.as_ref())?;
+
+    Ok(HttpResponse::Ok().json(ReportSchedulesSentReport { sent }))
+}
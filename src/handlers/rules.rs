@@ -0,0 +1,93 @@
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::models::category::get_or_create_uncategorized;
+use crate::models::entry::Entry;
+use crate::models::rule::{NewRule, Rule};
+use crate::models::tombstone;
+use crate::rules;
+use crate::schema::{entries, rules as rules_table};
+
+pub async fn create_rule(pool: web::Data<DbPool>, body: web::Json<NewRule>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let rule = diesel::insert_into(rules_table::table)
+        .values(&body.into_inner())
+        .get_result::<Rule>(&mut conn)?;
+
+    Ok(HttpResponse::Created().json(rule))
+}
+
+/// Ordered lowest-`priority`-first then lowest-`id`-first — the order
+/// [`crate::rules::find_match`] expects its input in.
+pub async fn list_rules(pool: web::Data<DbPool>, user_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let results = rules_table::table
+        .filter(rules_table::user_id.eq(user_id.into_inner()))
+        .order((rules_table::priority.asc(), rules_table::id.asc()))
+        .select(Rule::as_select())
+        .load(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(results))
+}
+
+pub async fn delete_rule(pool: web::Data<DbPool>, rule_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let rule_id = rule_id.into_inner();
+
+    let deleted = diesel::delete(rules_table::table.find(rule_id)).execute(&mut conn)?;
+    if deleted == 0 {
+        return Err(AppError::NotFound("rule not found".into()));
+    }
+    tombstone::record_deletion(&mut conn, tombstone::RULE, rule_id)?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Serialize)]
+pub struct ApplyRulesResult {
+    pub categorized: usize,
+}
+
+/// `POST /api/rules/apply/{user_id}`: retroactively runs this user's rules
+/// against every entry still sitting in "Uncategorized" — the same
+/// matching [`insert_entry_with_splits`](crate::handlers::entries::insert_entry_with_splits)
+/// runs for newly-created entries, just swept over existing ones. Only
+/// `category_id` is reassigned; a rule's `source_id` is not applied,
+/// here or when new entries are created, so this endpoint moves no money
+/// across sources (doing that retroactively would require unwinding and
+/// reapplying source balance deltas).
+pub async fn apply_rules(pool: web::Data<DbPool>, user_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let user_id = user_id.into_inner();
+
+    let user_rules = rules_table::table
+        .filter(rules_table::user_id.eq(user_id))
+        .order((rules_table::priority.asc(), rules_table::id.asc()))
+        .select(Rule::as_select())
+        .load::<Rule>(&mut conn)?;
+
+    let uncategorized = get_or_create_uncategorized(&mut conn, user_id)?;
+
+    let candidates = entries::table
+        .filter(entries::user_id.eq(user_id))
+        .filter(entries::category_id.eq(uncategorized.id))
+        .select(Entry::as_select())
+        .load::<Entry>(&mut conn)?;
+
+    let mut categorized = 0;
+    for entry in candidates {
+        if let Some(rule) = rules::find_match(&user_rules, entry.description.as_deref(), entry.target.as_deref(), entry.source_amount, entry.entry_type) {
+            diesel::update(entries::table.find(entry.id))
+                .set(entries::category_id.eq(rule.category_id))
+                .execute(&mut conn)?;
+            categorized += 1;
+        }
+    }
+
+    Ok(HttpResponse::Ok().json(ApplyRulesResult { categorized }))
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::{DateTime, Datelike, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::AuthUser;
+use crate::db::{cpool, DbPool};
+use crate::display_currency;
+use crate::error::AppError;
+use crate::models::category::Category;
+use crate::models::currency::Currency;
+use crate::models::entry::{Entry, EntryType};
+use crate::models::saved_query::{GroupBy, NewSavedQuery, SavedQuery};
+use crate::schema::{categories, currencies, entries, saved_queries};
+
+#[derive(Deserialize)]
+pub struct CreateSavedQueryRequest {
+    pub name: String,
+    pub category_id: Option<i32>,
+    pub source_id: Option<i32>,
+    pub entry_type: Option<EntryType>,
+    pub date_from: Option<DateTime<Utc>>,
+    pub date_to: Option<DateTime<Utc>>,
+    pub display_currency: Option<String>,
+    pub group_by: Option<GroupBy>,
+}
+
+/// `POST /api/saved-query`: persists a named entry filter for the caller,
+/// same filter shape as [`crate::handlers::share::create_share`] but kept
+/// private to the owner rather than handed out as a public token.
+pub async fn create_saved_query(pool: web::Data<DbPool>, auth: AuthUser, body: web::Json<CreateSavedQueryRequest>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let body = body.into_inner();
+
+    let saved_query = diesel::insert_into(saved_queries::table)
+        .values(&NewSavedQuery {
+            user_id: auth.0,
+            name: body.name,
+            category_id: body.category_id,
+            source_id: body.source_id,
+            entry_type: body.entry_type,
+            date_from: body.date_from,
+            date_to: body.date_to,
+            display_currency: body.display_currency,
+            group_by: body.group_by,
+        })
+        .get_result::<SavedQuery>(&mut conn)?;
+
+    Ok(HttpResponse::Created().json(saved_query))
+}
+
+pub async fn list_saved_queries(pool: web::Data<DbPool>, auth: AuthUser) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let results = saved_queries::table
+        .filter(saved_queries::user_id.eq(auth.0))
+        .select(SavedQuery::as_select())
+        .load(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(results))
+}
+
+pub async fn delete_saved_query(pool: web::Data<DbPool>, auth: AuthUser, name: web::Path<String>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let deleted = diesel::delete(
+        saved_queries::table
+            .filter(saved_queries::user_id.eq(auth.0))
+            .filter(saved_queries::name.eq(name.into_inner())),
+    )
+    .execute(&mut conn)?;
+    if deleted == 0 {
+        return Err(AppError::NotFound("saved query not found".into()));
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Serialize)]
+pub struct GroupTotal {
+    pub key: String,
+    pub total: f64,
+}
+
+#[derive(Serialize)]
+pub struct RunSavedQueryResponse {
+    pub entries: Vec<Entry>,
+    pub total: f64,
+    /// Present only when the saved query has a `group_by`.
+    pub groups: Option<Vec<GroupTotal>>,
+}
+
+/// `GET /api/saved-query/{name}/run`: re-applies a [`SavedQuery`]'s
+/// filter and, if it has a `group_by`, aggregates the result the same way
+/// [`crate::handlers::reports::category_breakdown`]/[`crate::handlers::reports::monthly`]
+/// do — so "Groceries this year in EUR" is one call instead of re-typing
+/// the filter every time.
+pub async fn run_saved_query(req: HttpRequest, pool: web::Data<DbPool>, auth: AuthUser, name: web::Path<String>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let saved_query = saved_queries::table
+        .filter(saved_queries::user_id.eq(auth.0))
+        .filter(saved_queries::name.eq(name.into_inner()))
+        .select(SavedQuery::as_select())
+        .first::<SavedQuery>(&mut conn)
+        .optional()?
+        .ok_or_else(|| AppError::NotFound("saved query not found".into()))?;
+
+    let mut query = entries::table.filter(entries::user_id.eq(auth.0)).into_boxed();
+    if let Some(category_id) = saved_query.category_id {
+        query = query.filter(entries::category_id.eq(category_id));
+    }
+    if let Some(source_id) = saved_query.source_id {
+        query = query.filter(entries::source_id.eq(source_id));
+    }
+    if let Some(entry_type) = saved_query.entry_type {
+        query = query.filter(entries::entry_type.eq(entry_type));
+    }
+    if let Some(date_from) = saved_query.date_from {
+        query = query.filter(entries::entry_date.ge(date_from));
+    }
+    if let Some(date_to) = saved_query.date_to {
+        query = query.filter(entries::entry_date.le(date_to));
+    }
+
+    let results = query.select(Entry::as_select()).load::<Entry>(&mut conn)?;
+
+    let display_currency_override = saved_query.display_currency.clone().or_else(|| display_currency::header_override(&req));
+    let target = display_currency::resolve(&mut conn, auth.0, display_currency_override.as_deref())?;
+
+    let mut currency_cache: HashMap<i32, Currency> = HashMap::new();
+    let mut amounts: Vec<f64> = Vec::with_capacity(results.len());
+    let mut total = 0.0;
+    for entry in &results {
+        let amount = match &target {
+            Some(target) => {
+                if !currency_cache.contains_key(&entry.currency_id) {
+                    let currency = currencies::table
+                        .find(entry.currency_id)
+                        .select(Currency::as_select())
+                        .first::<Currency>(&mut conn)?;
+                    currency_cache.insert(entry.currency_id, currency);
+                }
+                display_currency::convert(&currency_cache[&entry.currency_id], target, entry.amount)
+            }
+            None => entry.amount.to_f64_lossy(),
+        };
+        amounts.push(amount);
+        total += amount;
+    }
+
+    let groups = match saved_query.group_by {
+        Some(GroupBy::Category) => {
+            let category_ids: Vec<i32> = results.iter().filter_map(|e| e.category_id).collect();
+            let names: HashMap<i32, String> = categories::table
+                .filter(categories::id.eq_any(category_ids))
+                .select(Category::as_select())
+                .load::<Category>(&mut conn)?
+                .into_iter()
+                .map(|c| (c.id, c.name))
+                .collect();
+
+            let mut by_category: HashMap<String, f64> = HashMap::new();
+            for (entry, amount) in results.iter().zip(&amounts) {
+                let key = entry.category_id.and_then(|id| names.get(&id).cloned()).unwrap_or_else(|| "Uncategorized".to_string());
+                *by_category.entry(key).or_insert(0.0) += amount;
+            }
+            Some(by_category.into_iter().map(|(key, total)| GroupTotal { key, total }).collect())
+        }
+        Some(GroupBy::Month) => {
+            let mut by_month: HashMap<u32, f64> = HashMap::new();
+            for (entry, amount) in results.iter().zip(&amounts) {
+                *by_month.entry(entry.entry_date.month()).or_insert(0.0) += amount;
+            }
+            Some(by_month.into_iter().map(|(month, total)| GroupTotal { key: month.to_string(), total }).collect())
+        }
+        None => None,
+    };
+
+    Ok(HttpResponse::Ok().json(RunSavedQueryResponse { entries: results, total, groups }))
+}
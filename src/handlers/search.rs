@@ -0,0 +1,79 @@
+use actix_web::{web, HttpResponse};
+use diesel::pg::PgTextExpressionMethods;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::db::{cpool, ReportsPool};
+use crate::error::AppError;
+use crate::models::category::Category;
+use crate::models::currency::Currency;
+use crate::models::entry::Entry;
+use crate::models::source::Source;
+use crate::schema::{categories, currencies, entries, sources};
+
+#[derive(Deserialize)]
+pub struct SearchQuery {
+    pub user_id: i32,
+    pub q: String,
+}
+
+#[derive(Serialize)]
+pub struct SearchResults {
+    pub entries: Vec<Entry>,
+    pub categories: Vec<Category>,
+    pub sources: Vec<Source>,
+    pub currencies: Vec<Currency>,
+}
+
+/// `GET /api/search?user_id=&q=`: a single search box across the entities
+/// a user would otherwise have to look up one at a time — entries (by
+/// `description`/`target`), categories, sources, and currencies (by
+/// `code`/`name`, unscoped since currencies aren't per-user). Each list is
+/// capped and ordered by recency/name rather than a cross-entity rank,
+/// matching how every other list endpoint in this crate orders results.
+pub async fn search(pool: web::Data<ReportsPool>, query: web::Query<SearchQuery>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool.0)?;
+
+    if query.q.trim().is_empty() {
+        return Err(AppError::Validation("q must not be empty".into()));
+    }
+    let pattern = format!("%{}%", query.q.replace('%', "\\%").replace('_', "\\_"));
+
+    let matched_entries: Vec<Entry> = entries::table
+        .filter(entries::user_id.eq(query.user_id))
+        .filter(entries::description.ilike(pattern.clone()).or(entries::target.ilike(pattern.clone())))
+        .order(entries::entry_date.desc())
+        .limit(20)
+        .select(Entry::as_select())
+        .load(&mut conn)?;
+
+    let matched_categories: Vec<Category> = categories::table
+        .filter(categories::user_id.eq(query.user_id))
+        .filter(categories::name.ilike(pattern.clone()))
+        .order(categories::name.asc())
+        .limit(20)
+        .select(Category::as_select())
+        .load(&mut conn)?;
+
+    let matched_sources: Vec<Source> = sources::table
+        .filter(sources::user_id.eq(query.user_id))
+        .filter(sources::name.ilike(pattern.clone()))
+        .order(sources::name.asc())
+        .limit(20)
+        .select(Source::as_select())
+        .load(&mut conn)?;
+
+    let matched_currencies: Vec<Currency> = currencies::table
+        .filter(currencies::code.ilike(pattern.clone()).or(currencies::name.ilike(pattern.clone())))
+        .order(currencies::code.asc())
+        .limit(20)
+        .select(Currency::as_select())
+        .load(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(SearchResults {
+        entries: matched_entries,
+        categories: matched_categories,
+        sources: matched_sources,
+        currencies: matched_currencies,
+    }))
+}
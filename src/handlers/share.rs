@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use actix_web::{web, HttpResponse};
+use chrono::{DateTime, Duration, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::AuthUser;
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::models::entry::{Entry, EntryType};
+use crate::models::share_link::{self, NewShareLink};
+use crate::schema::{entries, share_links};
+
+/// Longest a share link is allowed to stay valid, so a forgotten link
+/// doesn't leak a live view of someone's finances indefinitely.
+const MAX_TTL_MINUTES: i64 = 30 * 24 * 60;
+const DEFAULT_TTL_MINUTES: i64 = 7 * 24 * 60;
+
+#[derive(Deserialize)]
+pub struct CreateShareRequest {
+    pub category_id: Option<i32>,
+    pub source_id: Option<i32>,
+    pub entry_type: Option<EntryType>,
+    pub date_from: Option<DateTime<Utc>>,
+    pub date_to: Option<DateTime<Utc>>,
+    /// How long the link stays valid, capped at [`MAX_TTL_MINUTES`].
+    /// Defaults to [`DEFAULT_TTL_MINUTES`].
+    pub ttl_minutes: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct CreateShareResponse {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// `POST /api/share`: mints a token bound to the caller's own entries plus
+/// whichever of `body`'s filters are set, for `GET /shared/{token}` to
+/// apply without ever seeing the caller's credentials.
+pub async fn create_share(
+    pool: web::Data<DbPool>,
+    auth: AuthUser,
+    body: web::Json<CreateShareRequest>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let body = body.into_inner();
+
+    let ttl_minutes = body.ttl_minutes.unwrap_or(DEFAULT_TTL_MINUTES).clamp(1, MAX_TTL_MINUTES);
+    let expires_at = Utc::now() + Duration::minutes(ttl_minutes);
+
+    let share = diesel::insert_into(share_links::table)
+        .values(&NewShareLink {
+            user_id: auth.0,
+            token: share_link::generate_token(),
+            category_id: body.category_id,
+            source_id: body.source_id,
+            entry_type: body.entry_type,
+            date_from: body.date_from,
+            date_to: body.date_to,
+            expires_at,
+        })
+        .get_result::<share_link::ShareLink>(&mut conn)?;
+
+    Ok(HttpResponse::Created().json(CreateShareResponse { token: share.token, expires_at: share.expires_at }))
+}
+
+#[derive(Serialize)]
+pub struct CurrencyTotal {
+    pub currency_id: i32,
+    pub total: f64,
+}
+
+#[derive(Serialize)]
+pub struct SharedView {
+    pub entries: Vec<Entry>,
+    pub by_currency: Vec<CurrencyTotal>,
+}
+
+/// `GET /shared/{token}`: the public, unauthenticated counterpart to
+/// [`create_share`] — read-only, and only ever sees the entries the link's
+/// filter admits, never the owning user's other data.
+pub async fn get_shared(pool: web::Data<DbPool>, token: web::Path<String>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let share = share_link::find_active(&mut conn, &token)?.ok_or_else(|| AppError::NotFound("share link not found or expired".into()))?;
+
+    let mut query = entries::table.filter(entries::user_id.eq(share.user_id)).into_boxed();
+    if let Some(category_id) = share.category_id {
+        query = query.filter(entries::category_id.eq(category_id));
+    }
+    if let Some(source_id) = share.source_id {
+        query = query.filter(entries::source_id.eq(source_id));
+    }
+    if let Some(entry_type) = share.entry_type {
+        query = query.filter(entries::entry_type.eq(entry_type));
+    }
+    if let Some(date_from) = share.date_from {
+        query = query.filter(entries::entry_date.ge(date_from));
+    }
+    if let Some(date_to) = share.date_to {
+        query = query.filter(entries::entry_date.le(date_to));
+    }
+
+    let results = query.select(Entry::as_select()).load::<Entry>(&mut conn)?;
+
+    let mut by_currency: HashMap<i32, f64> = HashMap::new();
+    for entry in &results {
+        *by_currency.entry(entry.currency_id).or_insert(0.0) += entry.amount.to_f64_lossy();
+    }
+
+    Ok(HttpResponse::Ok().json(SharedView {
+        entries: results,
+        by_currency: by_currency.into_iter().map(|(currency_id, total)| CurrencyTotal { currency_id, total }).collect(),
+    }))
+}
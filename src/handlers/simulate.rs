@@ -0,0 +1,18 @@
+use actix_web::{web, HttpResponse};
+
+use crate::db::{cpool, DbPool};
+use crate::dto::simulate::{SimulateRequest, SimulationReport};
+use crate::error::AppError;
+use crate::jobs::simulate as simulate_engine;
+
+/// `POST /api/simulate`: projects `body.months` months forward using
+/// [`simulate_engine::project`] — the user's real sources, recurring
+/// entries, and budgets, plus whatever hypothetical entries and budget
+/// changes the caller supplies — without writing anything to the ledger.
+pub async fn simulate(pool: web::Data<DbPool>, body: web::Json<SimulateRequest>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let months = simulate_engine::project(&mut conn, &body)?;
+
+    Ok(HttpResponse::Ok().json(SimulationReport { months }))
+}
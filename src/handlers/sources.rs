@@ -0,0 +1,376 @@
+use actix_web::{web, HttpResponse};
+use diesel::pg::PgTextExpressionMethods;
+use diesel::prelude::*;
+
+use crate::auth::AuthUser;
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::list_query::{ListQuery, Page};
+use crate::models::currency::Currency;
+use crate::models::currency_rate::rate_effective_on;
+use crate::models::entry::{Entry, EntryType};
+use crate::models::source::Source;
+use crate::money::Money;
+use crate::schema::{currencies, entries, sources};
+use crate::validation::Validator;
+use serde::Serialize;
+
+/// `GET /api/sources/user/{user_id}`: searchable (`?q=` matches `name`),
+/// sortable (`?sort=name|amount|created_at`, `-` prefix for descending),
+/// paginated listing — see [`crate::list_query`].
+pub async fn list_sources(
+    pool: web::Data<DbPool>,
+    user_id: web::Path<i32>,
+    query: web::Query<ListQuery>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let user_id = user_id.into_inner();
+    let pagination = query.pagination();
+
+    let count_filter = || {
+        let mut q = sources::table.filter(sources::user_id.eq(user_id)).into_boxed();
+        if let Some(term) = &query.q {
+            q = q.filter(sources::name.ilike(format!("%{term}%")));
+        }
+        q
+    };
+
+    let total = count_filter().count().get_result::<i64>(&mut conn)?;
+
+    let mut selection = count_filter();
+    let (sort_column, ascending) = query.sort_direction("name");
+    selection = match (sort_column, ascending) {
+        ("name", true) => selection.order(sources::name.asc()),
+        ("name", false) => selection.order(sources::name.desc()),
+        ("amount", true) => selection.order(sources::amount.asc()),
+        ("amount", false) => selection.order(sources::amount.desc()),
+        ("created_at", true) => selection.order(sources::created_at.asc()),
+        ("created_at", false) => selection.order(sources::created_at.desc()),
+        _ => return Err(AppError::Validation(format!("cannot sort sources by {sort_column}"))),
+    };
+
+    let items = selection
+        .limit(pagination.limit)
+        .offset(pagination.offset)
+        .select(Source::as_select())
+        .load::<Source>(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(Page {
+        items,
+        page: query.page.max(1),
+        per_page: pagination.limit,
+        total,
+    }))
+}
+
+#[derive(Serialize)]
+pub struct ReconcileReport {
+    pub source_id: i32,
+    pub recorded_amount: Money,
+    pub computed_amount: Money,
+    pub discrepancy: Money,
+}
+
+/// Recomputes a source's balance from the ledger alone (ignoring the
+/// possibly-drifted `sources.amount`) and reports the difference, without
+/// writing anything back.
+pub async fn check_source(pool: web::Data<DbPool>, auth: AuthUser, source_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let source_id = source_id.into_inner();
+
+    let source = sources::table
+        .find(source_id)
+        .select(Source::as_select())
+        .first(&mut conn)
+        .map_err(|_| AppError::NotFound(format!("source {source_id} not found")))?;
+    if source.user_id != auth.0 {
+        return Err(AppError::Unauthorized("source does not belong to the authenticated session".into()));
+    }
+
+    let primary: Vec<Entry> = entries::table
+        .filter(entries::source_id.eq(source_id))
+        .select(Entry::as_select())
+        .load(&mut conn)?;
+    let secondary: Vec<Entry> = entries::table
+        .filter(entries::secondary_source_id.eq(source_id))
+        .select(Entry::as_select())
+        .load(&mut conn)?;
+
+    let mut computed = Money::ZERO;
+    for entry in &primary {
+        computed += match entry.entry_type {
+            EntryType::Spend | EntryType::Lend => -entry.source_amount,
+            EntryType::Income | EntryType::Borrow | EntryType::Adjust => entry.source_amount,
+            EntryType::Convert => -entry.source_amount,
+        };
+    }
+    for entry in &secondary {
+        if matches!(entry.entry_type, EntryType::Convert) {
+            let credit = convert_amount(&mut conn, entry.currency_id, source.currency_id, entry.amount)?;
+            computed += credit;
+        }
+    }
+
+    let discrepancy = source.amount - computed;
+
+    Ok(HttpResponse::Ok().json(ReconcileReport {
+        source_id,
+        recorded_amount: source.amount,
+        computed_amount: computed,
+        discrepancy,
+    }))
+}
+
+#[derive(serde::Deserialize)]
+pub struct ReconcileRequest {
+    pub user_id: i32,
+    pub actual_amount: Money,
+}
+
+/// Brings a source's stored balance in line with a real-world statement:
+/// the difference is booked as an `Adjust` entry (rather than silently
+/// overwriting `amount`) so the ledger still explains every change.
+pub async fn reconcile_source(
+    pool: web::Data<DbPool>,
+    auth: AuthUser,
+    name: web::Path<String>,
+    body: web::Json<ReconcileRequest>,
+) -> Result<HttpResponse, AppError> {
+    if body.user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    let mut conn = cpool(&pool)?;
+    let body = body.into_inner();
+
+    let source = sources::table
+        .filter(sources::name.eq(name.into_inner()))
+        .filter(sources::user_id.eq(body.user_id))
+        .select(Source::as_select())
+        .first::<Source>(&mut conn)
+        .map_err(|_| AppError::NotFound("source not found".into()))?;
+
+    let difference = body.actual_amount - source.amount;
+    let now = chrono::Utc::now();
+
+    let source_currency = currencies::table
+        .find(source.currency_id)
+        .select(Currency::as_select())
+        .first::<Currency>(&mut conn)?;
+    let conversion_rate_to_fixed = rate_effective_on(&mut conn, &source_currency, now.date_naive())?;
+
+    let source = conn.transaction::<_, AppError, _>(|conn| {
+        if difference != Money::ZERO {
+            let adjustment = crate::models::entry::NewEntry {
+                user_id: body.user_id,
+                source_id: source.id,
+                secondary_source_id: None,
+                category_id: None,
+                currency_id: source.currency_id,
+                entry_type: EntryType::Adjust,
+                amount: difference,
+                source_amount: difference,
+                conversion_rate: 1.0,
+                conversion_rate_to_fixed,
+                target: None,
+                description: Some("Reconciliation adjustment".into()),
+                notes: None,
+                entry_date: now,
+                created_by: Some(body.user_id),
+                updated_by: Some(body.user_id),
+                counterparty_id: None,
+                payer_id: None,
+            };
+            diesel::insert_into(entries::table).values(&adjustment).execute(conn)?;
+        }
+
+        let updated = diesel::update(sources::table.find(source.id))
+            .set((
+                sources::amount.eq(body.actual_amount),
+                sources::last_reconciled_at.eq(chrono::Utc::now()),
+                sources::updated_at.eq(chrono::Utc::now()),
+            ))
+            .get_result::<Source>(conn)?;
+
+        Ok(updated)
+    })?;
+
+    Ok(HttpResponse::Ok().json(source))
+}
+
+#[derive(serde::Deserialize)]
+pub struct TransferRequest {
+    pub user_id: i32,
+    pub to: String,
+    pub amount: Money,
+    pub conversion_rate: Option<f64>,
+}
+
+/// Moves money between two of the user's sources as a single `Convert`
+/// entry, instead of leaving the client to hand-craft one and hope the
+/// balances stay consistent.
+pub async fn transfer(
+    pool: web::Data<DbPool>,
+    auth: AuthUser,
+    from_name: web::Path<String>,
+    body: web::Json<TransferRequest>,
+) -> Result<HttpResponse, AppError> {
+    if body.user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    let mut conn = cpool(&pool)?;
+    let body = body.into_inner();
+
+    let from = sources::table
+        .filter(sources::name.eq(from_name.into_inner()))
+        .filter(sources::user_id.eq(body.user_id))
+        .select(Source::as_select())
+        .first::<Source>(&mut conn)
+        .map_err(|_| AppError::NotFound("source not found".into()))?;
+    let to = sources::table
+        .filter(sources::name.eq(&body.to))
+        .filter(sources::user_id.eq(body.user_id))
+        .select(Source::as_select())
+        .first::<Source>(&mut conn)
+        .map_err(|_| AppError::NotFound("destination source not found".into()))?;
+
+    let rate = match body.conversion_rate {
+        Some(rate) => rate,
+        None if from.currency_id == to.currency_id => 1.0,
+        None => return Err(AppError::Validation(
+            "conversion_rate is required when source currencies differ".into(),
+        )),
+    };
+
+    Validator::new()
+        .require_positive("amount", body.amount)
+        .require_finite_positive_rate("conversion_rate", rate)
+        .finish()?;
+
+    let now = chrono::Utc::now();
+    let from_currency = currencies::table
+        .find(from.currency_id)
+        .select(Currency::as_select())
+        .first::<Currency>(&mut conn)?;
+    let conversion_rate_to_fixed = rate_effective_on(&mut conn, &from_currency, now.date_naive())?;
+
+    let entry = conn.transaction::<_, AppError, _>(|conn| {
+        let new_entry = crate::models::entry::NewEntry {
+            user_id: body.user_id,
+            source_id: from.id,
+            secondary_source_id: Some(to.id),
+            category_id: None,
+            currency_id: from.currency_id,
+            entry_type: EntryType::Convert,
+            amount: body.amount,
+            source_amount: body.amount,
+            conversion_rate: rate,
+            conversion_rate_to_fixed,
+            target: Some(body.to.clone()),
+            description: Some("Transfer".into()),
+            notes: None,
+            entry_date: now,
+            created_by: Some(body.user_id),
+            updated_by: Some(body.user_id),
+            counterparty_id: None,
+            payer_id: None,
+        };
+
+        let entry = diesel::insert_into(entries::table)
+            .values(&new_entry)
+            .get_result::<Entry>(conn)?;
+
+        diesel::update(sources::table.find(from.id))
+            .set((sources::amount.eq(sources::amount - body.amount), sources::updated_at.eq(chrono::Utc::now())))
+            .execute(conn)?;
+        diesel::update(sources::table.find(to.id))
+            .set((sources::amount.eq(sources::amount + body.amount * rate), sources::updated_at.eq(chrono::Utc::now())))
+            .execute(conn)?;
+
+        Ok(entry)
+    })?;
+
+    Ok(HttpResponse::Created().json(entry))
+}
+
+#[derive(serde::Deserialize)]
+pub struct MergeRequest {
+    pub user_id: i32,
+    pub into: String,
+}
+
+/// Folds one source into another of the same currency: every entry that
+/// referenced the old source (as either `source_id` or `secondary_source_id`)
+/// is repointed, its remaining balance is added to the target, and it's
+/// archived rather than deleted so history stays intact.
+pub async fn merge_source(
+    pool: web::Data<DbPool>,
+    auth: AuthUser,
+    name: web::Path<String>,
+    body: web::Json<MergeRequest>,
+) -> Result<HttpResponse, AppError> {
+    if body.user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    let mut conn = cpool(&pool)?;
+    let body = body.into_inner();
+
+    let from = sources::table
+        .filter(sources::name.eq(name.into_inner()))
+        .filter(sources::user_id.eq(body.user_id))
+        .select(Source::as_select())
+        .first::<Source>(&mut conn)
+        .map_err(|_| AppError::NotFound("source not found".into()))?;
+    let into = sources::table
+        .filter(sources::name.eq(&body.into))
+        .filter(sources::user_id.eq(body.user_id))
+        .select(Source::as_select())
+        .first::<Source>(&mut conn)
+        .map_err(|_| AppError::NotFound("target source not found".into()))?;
+
+    if from.currency_id != into.currency_id {
+        return Err(AppError::Validation("sources must share a currency to merge".into()));
+    }
+
+    let into = conn.transaction::<_, AppError, _>(|conn| {
+        diesel::update(entries::table.filter(entries::source_id.eq(from.id)))
+            .set(entries::source_id.eq(into.id))
+            .execute(conn)?;
+        diesel::update(entries::table.filter(entries::secondary_source_id.eq(from.id)))
+            .set(entries::secondary_source_id.eq(into.id))
+            .execute(conn)?;
+
+        let into = diesel::update(sources::table.find(into.id))
+            .set((sources::amount.eq(sources::amount + from.amount), sources::updated_at.eq(chrono::Utc::now())))
+            .get_result::<Source>(conn)?;
+
+        diesel::update(sources::table.find(from.id))
+            .set((
+                sources::amount.eq(Money::ZERO),
+                sources::archived.eq(true),
+                sources::updated_at.eq(chrono::Utc::now()),
+            ))
+            .execute(conn)?;
+
+        Ok(into)
+    })?;
+
+    Ok(HttpResponse::Ok().json(into))
+}
+
+fn convert_amount(
+    conn: &mut diesel::pg::PgConnection,
+    from_currency_id: i32,
+    to_currency_id: i32,
+    amount: Money,
+) -> Result<Money, AppError> {
+    let from = currencies::table
+        .find(from_currency_id)
+        .select(Currency::as_select())
+        .first::<Currency>(conn)?;
+    let to = currencies::table
+        .find(to_currency_id)
+        .select(Currency::as_select())
+        .first::<Currency>(conn)?;
+
+    Ok(amount * from.rate_to_fixed / to.rate_to_fixed)
+}
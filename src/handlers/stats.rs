@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::db::{cpool, DbPool};
+use crate::display_currency;
+use crate::error::AppError;
+use crate::models::currency::Currency;
+use crate::models::entry::{Entry, EntryType};
+use crate::schema::{currencies, entries};
+
+#[derive(Deserialize)]
+pub struct StatsQuery {
+    pub user_id: i32,
+    /// See [`crate::display_currency`]. Aggregates are always converted
+    /// into this currency when it resolves to one. Falls back to an
+    /// `X-Display-Currency` header when unset.
+    pub display_currency: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct CategoryTotal {
+    pub category_id: Option<i32>,
+    pub total: f64,
+}
+
+#[derive(Serialize)]
+pub struct CurrencyTotal {
+    pub currency_id: i32,
+    pub total: f64,
+}
+
+#[derive(Serialize)]
+pub struct StatsReport {
+    pub display_currency_id: Option<i32>,
+    pub by_category: Vec<CategoryTotal>,
+    pub by_currency: Vec<CurrencyTotal>,
+}
+
+/// Spend totals grouped by category and by currency, converted into the
+/// resolved display currency when one is available (see
+/// [`crate::display_currency::resolve`]) — otherwise summed within each
+/// entry's own currency, which only makes `by_category` meaningful when a
+/// user sticks to a single currency.
+pub async fn stats(req: HttpRequest, pool: web::Data<DbPool>, query: web::Query<StatsQuery>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let display_currency_override = query.display_currency.clone().or_else(|| display_currency::header_override(&req));
+    let target = display_currency::resolve(&mut conn, query.user_id, display_currency_override.as_deref())?;
+
+    let spends: Vec<Entry> = entries::table
+        .filter(entries::user_id.eq(query.user_id))
+        .filter(entries::entry_type.eq(EntryType::Spend))
+        .select(Entry::as_select())
+        .load(&mut conn)?;
+
+    let mut currency_cache: HashMap<i32, Currency> = HashMap::new();
+    let mut by_category: HashMap<Option<i32>, f64> = HashMap::new();
+    let mut by_currency: HashMap<i32, f64> = HashMap::new();
+
+    for entry in &spends {
+        let amount = match &target {
+            Some(target) => {
+                if !currency_cache.contains_key(&entry.currency_id) {
+                    let currency = currencies::table
+                        .find(entry.currency_id)
+                        .select(Currency::as_select())
+                        .first::<Currency>(&mut conn)?;
+                    currency_cache.insert(entry.currency_id, currency);
+                }
+                display_currency::convert(&currency_cache[&entry.currency_id], target, entry.amount)
+            }
+            None => entry.amount.to_f64_lossy(),
+        };
+
+        *by_category.entry(entry.category_id).or_insert(0.0) += amount;
+        *by_currency.entry(entry.currency_id).or_insert(0.0) += amount;
+    }
+
+    Ok(HttpResponse::Ok().json(StatsReport {
+        display_currency_id: target.map(|c| c.id),
+        by_category: by_category.into_iter().map(|(category_id, total)| CategoryTotal { category_id, total }).collect(),
+        by_currency: by_currency.into_iter().map(|(currency_id, total)| CurrencyTotal { currency_id, total }).collect(),
+    }))
+}
@@ -0,0 +1,40 @@
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::db::{cpool, DbPool};
+use crate::error::AppError;
+use crate::models::category::get_or_create_uncategorized;
+use crate::schema::entries;
+
+#[derive(Deserialize)]
+pub struct SummaryQuery {
+    pub user_id: i32,
+}
+
+#[derive(Serialize)]
+pub struct Summary {
+    pub entry_count: i64,
+    /// Prompts cleanup: entries sitting in the auto-created "Uncategorized"
+    /// bucket (see `models::category::get_or_create_uncategorized`).
+    pub uncategorized_count: i64,
+}
+
+pub async fn summary(pool: web::Data<DbPool>, query: web::Query<SummaryQuery>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let user_id = query.user_id;
+
+    let entry_count = entries::table
+        .filter(entries::user_id.eq(user_id))
+        .count()
+        .get_result::<i64>(&mut conn)?;
+
+    let uncategorized = get_or_create_uncategorized(&mut conn, user_id)?;
+    let uncategorized_count = entries::table
+        .filter(entries::user_id.eq(user_id))
+        .filter(entries::category_id.eq(uncategorized.id))
+        .count()
+        .get_result::<i64>(&mut conn)?;
+
+    Ok(HttpResponse::Ok().json(Summary { entry_count, uncategorized_count }))
+}
@@ -0,0 +1,159 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::config::AppConfig;
+use crate::db::{cpool, DbConn, DbPool};
+use crate::dto::entry::CreateEntryRequest;
+use crate::error::AppError;
+use crate::handlers::entries::{build_entry_draft, insert_entry_with_splits};
+use crate::models::category::get_or_create_uncategorized;
+use crate::models::telegram_link::{NewTelegramLink, TelegramLink};
+use crate::schema::telegram_links;
+use crate::telegram::{self, TelegramClient, Update};
+use chrono::Utc;
+
+#[derive(Serialize)]
+pub struct TelegramLinkCode {
+    pub link_code: String,
+}
+
+/// `POST /api/telegram/link/{user_id}`: issues a one-time code for the
+/// caller to send the bot as `/link <code>`, linking their Telegram chat
+/// to this account. A user can hold any number of unconsumed codes — only
+/// the one actually sent to the bot gets a `chat_id` attached.
+pub async fn create_link_code(pool: web::Data<DbPool>, user_id: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let link_code = telegram::generate_link_code();
+    diesel::insert_into(telegram_links::table)
+        .values(&NewTelegramLink { user_id: user_id.into_inner(), link_code: link_code.clone() })
+        .execute(&mut conn)?;
+
+    Ok(HttpResponse::Created().json(TelegramLinkCode { link_code }))
+}
+
+/// `POST /api/telegram/webhook`: the URL registered with Telegram via
+/// `setWebhook`. Handles `/link <code>` to attach `chat.id` to a pending
+/// [`TelegramLink`], and otherwise treats the message text as a
+/// free-text entry to parse with [`build_entry_draft`] and insert
+/// directly — unlike `POST /api/entry/parse`, which only ever returns a
+/// draft, a chat message has no separate "confirm" step, so a message
+/// that resolves every required field is inserted as-is and a message
+/// that doesn't gets a reply asking for the missing piece.
+pub async fn webhook(
+    req: HttpRequest,
+    pool: web::Data<DbPool>,
+    config: web::Data<AppConfig>,
+    update: web::Json<Update>,
+) -> Result<HttpResponse, AppError> {
+    let Some(client) = TelegramClient::from_config(&config) else {
+        return Err(AppError::Validation("telegram bot is not configured".into()));
+    };
+    if let Some(secret) = &config.telegram_webhook_secret {
+        let header = req.headers().get("X-Telegram-Bot-Api-Secret-Token").and_then(|v| v.to_str().ok());
+        if header != Some(secret.as_str()) {
+            return Err(AppError::Unauthorized("invalid webhook secret token".into()));
+        }
+    }
+    let Some(message) = &update.message else {
+        return Ok(HttpResponse::Ok().finish());
+    };
+    let Some(text) = &message.text else {
+        return Ok(HttpResponse::Ok().finish());
+    };
+    let chat_id = message.chat.id;
+
+    let mut conn = cpool(&pool)?;
+
+    if let Some(code) = text.strip_prefix("/link ").map(str::trim) {
+        return link_chat(&mut conn, &client, chat_id, code);
+    }
+
+    let Some(link) = telegram_links::table
+        .filter(telegram_links::chat_id.eq(chat_id))
+        .select(TelegramLink::as_select())
+        .first::<TelegramLink>(&mut conn)
+        .optional()?
+    else {
+        client.send_message(chat_id, "This chat isn't linked yet. Send /link <code> with the code from your account settings.").map_err(AppError::Internal)?;
+        return Ok(HttpResponse::Ok().finish());
+    };
+
+    ingest_message(&mut conn, &config, &client, link.user_id, chat_id, text)?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+fn link_chat(conn: &mut DbConn, client: &TelegramClient, chat_id: i64, code: &str) -> Result<HttpResponse, AppError> {
+    let updated = diesel::update(telegram_links::table.filter(telegram_links::link_code.eq(code)).filter(telegram_links::chat_id.is_null()))
+        .set((telegram_links::chat_id.eq(chat_id), telegram_links::linked_at.eq(Utc::now())))
+        .execute(conn)?;
+
+    let reply = if updated == 0 {
+        "That code doesn't look right (or was already used). Generate a new one from your account settings."
+    } else {
+        "Linked! Send things like \"spent 20 USD groceries\" and I'll log them."
+    };
+    client.send_message(chat_id, reply).map_err(AppError::Internal)?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+fn ingest_message(
+    conn: &mut DbConn,
+    config: &AppConfig,
+    client: &TelegramClient,
+    user_id: i32,
+    chat_id: i64,
+    text: &str,
+) -> Result<(), AppError> {
+    let draft = build_entry_draft(conn, config, user_id, text)?;
+
+    let (Some(amount), Some(entry_type)) = (draft.amount, draft.entry_type) else {
+        client.send_message(chat_id, "Couldn't tell the amount or whether that was income or spending — try again with an amount, e.g. \"spent 20 USD groceries\".").map_err(AppError::Internal)?;
+        return Ok(());
+    };
+    let Some(source_id) = draft.source_id else {
+        client.send_message(chat_id, "Couldn't tell which source/account that came from — mention it by name, e.g. \"... wallet\".").map_err(AppError::Internal)?;
+        return Ok(());
+    };
+    let currency_id = match draft.currency_id {
+        Some(id) => id,
+        None => {
+            client.send_message(chat_id, "Couldn't tell the currency — include a 3-letter code, e.g. \"20 USD\".").map_err(AppError::Internal)?;
+            return Ok(());
+        }
+    };
+    let category_id = match draft.category_id {
+        Some(id) => Some(id),
+        None if entry_type == crate::models::entry::EntryType::Spend || entry_type == crate::models::entry::EntryType::Income => {
+            Some(get_or_create_uncategorized(conn, user_id)?.id)
+        }
+        None => None,
+    };
+
+    let entry = insert_entry_with_splits(
+        conn,
+        CreateEntryRequest {
+            user_id,
+            source_id,
+            secondary_source_id: None,
+            category_id,
+            currency_id,
+            entry_type,
+            amount,
+            target: None,
+            counterparty_id: None,
+            payer_id: None,
+            description: draft.description.clone(),
+            notes: None,
+            entry_date: draft.entry_date,
+            splits: None,
+            custom: Default::default(),
+        },
+    )?;
+
+    client
+        .send_message(chat_id, &format!("Logged entry #{} for {}.", entry.id, entry.source_amount))
+        .map_err(AppError::Internal)?;
+    Ok(())
+}
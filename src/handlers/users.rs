@@ -0,0 +1,457 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::AuthUser;
+use crate::config::{AppConfig, RegistrationMode};
+use crate::db::{cpool, DbPool};
+use crate::dto::user::{
+    ConfirmPasswordResetRequest, LoginRequest, LoginResponse, RequestPasswordResetRequest, SessionResponse, UpdateProfileRequest,
+    UserResponse,
+};
+use crate::error::AppError;
+use crate::handlers::backup;
+use crate::iso4217;
+use crate::jobs::fixed_currency;
+use crate::jobs::purge;
+use crate::mail;
+use crate::models::currency::{Currency, NewCurrency};
+use crate::models::login_history::NewLoginHistoryEntry;
+use crate::models::password_reset_token::{self, NewPasswordResetToken};
+use crate::models::session::{self, NewSession};
+use crate::models::user::{NewUser, User};
+use crate::password;
+use crate::schema::{currencies, login_history, password_reset_tokens, sessions, users};
+use crate::validation::{require_max_len, EMAIL_MAX_LEN};
+
+#[derive(Deserialize)]
+pub struct ChangeFixedCurrencyRequest {
+    pub user_id: i32,
+    pub new_fixed_currency_id: i32,
+    /// When `true`, returns the recalculation report without writing
+    /// anything — lets a client show "here's what will change" before the
+    /// user commits to it.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// See [`fixed_currency::change_fixed_currency`] for the recalculation
+/// this triggers.
+pub async fn change_fixed_currency(
+    pool: web::Data<DbPool>,
+    auth: AuthUser,
+    body: web::Json<ChangeFixedCurrencyRequest>,
+) -> Result<HttpResponse, AppError> {
+    if body.user_id != auth.0 {
+        return Err(AppError::Unauthorized("user_id does not match the authenticated session".into()));
+    }
+    let mut conn = cpool(&pool)?;
+
+    let report = fixed_currency::change_fixed_currency(
+        &mut conn,
+        body.user_id,
+        body.new_fixed_currency_id,
+        body.dry_run,
+    )?;
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+#[derive(Deserialize)]
+pub struct RegisterRequest {
+    pub email: String,
+    pub password: String,
+    /// Required, and checked against `AppConfig::invite_codes`, only when
+    /// `registration_mode` is [`RegistrationMode::InviteCode`].
+    #[serde(default)]
+    pub invite_code: Option<String>,
+    /// ISO 4217 code (e.g. `"USD"`) to set as the new account's
+    /// `fixed_currency_id`, reviving or creating the currency from the
+    /// bundled [`iso4217`] catalog as needed. Left unset, the account has
+    /// no fixed currency until it picks one via `POST /api/me/fixed-currency`.
+    #[serde(default)]
+    pub fixed_currency_code: Option<String>,
+}
+
+/// `POST /register`: the self-hosted equivalent of whatever seeded the very
+/// first account in a dev/test setup. Gated by `AppConfig::registration_mode`
+/// so a deployment can leave it open, require an invite code, or turn it off
+/// entirely once the operator's own account exists.
+pub async fn register(
+    pool: web::Data<DbPool>,
+    config: web::Data<AppConfig>,
+    body: web::Json<RegisterRequest>,
+) -> Result<HttpResponse, AppError> {
+    let body = body.into_inner();
+    let mut conn = cpool(&pool)?;
+
+    match config.registration_mode {
+        RegistrationMode::Disabled => return Err(AppError::Validation("registration is disabled".into())),
+        RegistrationMode::InviteCode => {
+            let supplied = body.invite_code.as_deref().unwrap_or_default();
+            if !config.invite_codes.iter().any(|code| code == supplied) {
+                return Err(AppError::Unauthorized("invalid invite code".into()));
+            }
+        }
+        RegistrationMode::Open => {}
+    }
+
+    require_max_len("email", &body.email, EMAIL_MAX_LEN)?;
+    if !body.email.contains('@') || body.email.chars().any(char::is_whitespace) {
+        return Err(AppError::Validation("email is not valid".into()));
+    }
+    password::validate_strength(&body.password)?;
+
+    let existing = users::table
+        .filter(users::email.eq(&body.email))
+        .select(users::id)
+        .first::<i32>(&mut conn)
+        .optional()?;
+    if existing.is_some() {
+        return Err(AppError::Conflict("an account with that email already exists".into()));
+    }
+
+    let user = conn.transaction::<_, AppError, _>(|conn| {
+        let user = diesel::insert_into(users::table)
+            .values(&NewUser { email: body.email, password_hash: password::hash(&body.password), oidc_subject: None })
+            .get_result::<User>(conn)?;
+
+        let Some(code) = &body.fixed_currency_code else {
+            return Ok(user);
+        };
+
+        let currency = resolve_currency(conn, code)?;
+        let user = diesel::update(users::table.find(user.id))
+            .set(users::fixed_currency_id.eq(currency.id))
+            .get_result::<User>(conn)?;
+
+        Ok(user)
+    })?;
+
+    Ok(HttpResponse::Created().json(UserResponse::from(&user)))
+}
+
+/// Finds the (non-archived) currency for `code`, reviving an archived row
+/// or creating one from the bundled [`iso4217`] catalog otherwise — same
+/// resolution order as [`crate::handlers::currencies::from_iso`], just for
+/// a single code instead of a batch.
+fn resolve_currency(conn: &mut diesel::pg::PgConnection, code: &str) -> Result<Currency, AppError> {
+    if let Some(active) = currencies::table
+        .filter(currencies::code.eq(code))
+        .filter(currencies::archived.eq(false))
+        .select(Currency::as_select())
+        .first::<Currency>(conn)
+        .optional()?
+    {
+        return Ok(active);
+    }
+
+    let entry = iso4217::lookup(code).ok_or_else(|| AppError::Validation(format!("unknown currency code {code}")))?;
+
+    let archived = currencies::table
+        .filter(currencies::code.eq(entry.code))
+        .filter(currencies::archived.eq(true))
+        .select(Currency::as_select())
+        .first::<Currency>(conn)
+        .optional()?;
+
+    if let Some(archived) = archived {
+        return Ok(diesel::update(currencies::table.find(archived.id))
+            .set(currencies::archived.eq(false))
+            .get_result::<Currency>(conn)?);
+    }
+
+    Ok(diesel::insert_into(currencies::table)
+        .values(&NewCurrency {
+            code: entry.code.to_string(),
+            name: entry.name.to_string(),
+            rate_to_fixed: 1.0,
+            symbol: Some(entry.symbol.to_string()),
+        })
+        .get_result::<Currency>(conn)?)
+}
+
+/// `POST /login`: looks the account up by email and verifies the password
+/// on [`password::verify`]'s bounded worker pool rather than inline, so a
+/// burst of concurrent attempts can't stall every other request this
+/// executor thread would otherwise be serving. Every attempt — matched
+/// account or not, right password or not — is recorded to `login_history`.
+///
+/// A successful login against a hash [`password::verify`] recognizes as
+/// the legacy PBKDF2 format is transparently upgraded to Argon2id in
+/// place, so the user base migrates one login at a time instead of a bulk
+/// rehash job or a forced reset.
+///
+/// On success, issues a [`crate::models::session::Session`] bearer token
+/// (see [`AuthUser`]'s doc comment) that the caller can revoke early with
+/// `POST /logout` instead of waiting it out.
+pub async fn login(req: HttpRequest, pool: web::Data<DbPool>, body: web::Json<LoginRequest>) -> Result<HttpResponse, AppError> {
+    let body = body.into_inner();
+    let mut conn = cpool(&pool)?;
+
+    let user = users::table
+        .filter(users::email.eq(&body.email))
+        .select(User::as_select())
+        .first::<User>(&mut conn)
+        .optional()?;
+
+    let ip_address = req
+        .connection_info()
+        .realip_remote_addr()
+        .unwrap_or("unknown")
+        .to_string();
+    let user_agent = req
+        .headers()
+        .get("User-Agent")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let outcome = match &user {
+        Some(user) => Some(password::verify(body.password, user.password_hash.clone()).await?),
+        None => None,
+    };
+    let success = outcome.as_ref().is_some_and(|o| o.valid);
+
+    if let (Some(user), Some(outcome)) = (&user, &outcome) {
+        if let Some(rehashed) = &outcome.rehashed {
+            diesel::update(users::table.find(user.id))
+                .set(users::password_hash.eq(rehashed))
+                .execute(&mut conn)?;
+        }
+    }
+
+    if let Some(user) = &user {
+        diesel::insert_into(login_history::table)
+            .values(&NewLoginHistoryEntry {
+                user_id: user.id,
+                ip_address,
+                user_agent,
+                success,
+            })
+            .execute(&mut conn)?;
+    }
+
+    match user {
+        Some(user) if success && !user.disabled => {
+            let token = session::generate_token();
+            diesel::insert_into(sessions::table)
+                .values(&NewSession {
+                    user_id: user.id,
+                    token: token.clone(),
+                    device_name: body.device_name,
+                })
+                .execute(&mut conn)?;
+
+            Ok(HttpResponse::Ok().json(LoginResponse { user: UserResponse::from(&user), token }))
+        }
+        Some(user) if success => Err(AppError::Unauthorized(format!("account {} is disabled", user.id))),
+        _ => Err(AppError::Unauthorized("invalid email or password".into())),
+    }
+}
+
+/// `POST /logout`: revokes the session named by the caller's own
+/// `Authorization: Bearer` header, if any. A request authenticated via the
+/// `X-User-Id` placeholder instead has no token to revoke, so it's simply
+/// a no-op rather than an error — there's nothing wrong with logging out
+/// twice, or with a placeholder-auth request that never had a session.
+pub async fn logout(req: HttpRequest, pool: web::Data<DbPool>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    if let Some(token) = req
+        .headers()
+        .get("Authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+    {
+        session::revoke(&mut conn, token)?;
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// `GET /api/me/sessions`: every session ever issued to the caller,
+/// including revoked ones, so a user can confirm a device was actually
+/// logged out. `is_current` marks whichever row matches the bearer token
+/// this very request came in on, if any.
+pub async fn list_sessions(req: HttpRequest, pool: web::Data<DbPool>, auth: AuthUser) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let current_token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+
+    let out: Vec<SessionResponse> = session::list_for_user(&mut conn, auth.0)?
+        .into_iter()
+        .map(|s| SessionResponse {
+            is_current: current_token == Some(s.token.as_str()),
+            id: s.id,
+            device_name: s.device_name,
+            created_at: s.created_at,
+            last_used_at: s.last_used_at,
+            last_used_ip: s.last_used_ip,
+            revoked: s.revoked_at.is_some(),
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(out))
+}
+
+/// `DELETE /api/me/sessions/{session_id}`: revokes one of the caller's own
+/// sessions by id, letting a user kick a lost or stolen device without
+/// waiting on that device to send a `POST /logout` it never will.
+pub async fn revoke_session(pool: web::Data<DbPool>, auth: AuthUser, path: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let session_id = path.into_inner();
+
+    let revoked = session::revoke_for_user(&mut conn, auth.0, session_id)?;
+    if revoked == 0 {
+        return Err(AppError::NotFound(format!("session {session_id} not found")));
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+
+#[derive(Serialize)]
+pub struct RequestPasswordResetReport {
+    pub sent: bool,
+}
+
+/// `POST /password-reset/request`: always reports `sent: true`, whether or
+/// not `email` matches an account, so a caller can't use this endpoint to
+/// enumerate registered addresses. The token itself is only ever delivered
+/// by email — the response never echoes it back.
+pub async fn request_password_reset(
+    pool: web::Data<DbPool>,
+    config: web::Data<AppConfig>,
+    body: web::Json<RequestPasswordResetRequest>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let user = users::table
+        .filter(users::email.eq(&body.email))
+        .select(User::as_select())
+        .first::<User>(&mut conn)
+        .optional()?;
+
+    if let Some(user) = user {
+        let token = password_reset_token::generate_token();
+        diesel::insert_into(password_reset_tokens::table)
+            .values(&NewPasswordResetToken {
+                user_id: user.id,
+                token: token.clone(),
+                expires_at: Utc::now() + Duration::minutes(config.password_reset_ttl_minutes),
+            })
+            .execute(&mut conn)?;
+
+        let body = format!(
+            "Use this token to reset your password: {token}\n\
+             It expires in {} minutes. If you didn't request this, ignore this email.",
+            config.password_reset_ttl_minutes
+        );
+        mail::build(&config)
+            .send(&user.email, "Reset your money-rs password", &body)
+            .map_err(AppError::Internal)?;
+    }
+
+    Ok(HttpResponse::Ok().json(RequestPasswordResetReport { sent: true }))
+}
+
+/// `POST /password-reset/confirm`: consumes a still-valid token from
+/// [`password_reset_token::find_valid`] and overwrites the account's
+/// password. Tokens are single-use — [`password_reset_token::mark_used`]
+/// runs in the same request that consumes it.
+pub async fn confirm_password_reset(
+    pool: web::Data<DbPool>,
+    body: web::Json<ConfirmPasswordResetRequest>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let token = password_reset_token::find_valid(&mut conn, &body.token)?
+        .ok_or_else(|| AppError::Validation("invalid or expired reset token".into()))?;
+
+    let new_hash = password::hash(&body.new_password);
+    let user = diesel::update(users::table.find(token.user_id))
+        .set(users::password_hash.eq(new_hash))
+        .get_result::<User>(&mut conn)?;
+    password_reset_token::mark_used(&mut conn, token.id)?;
+
+    Ok(HttpResponse::Ok().json(UserResponse::from(&user)))
+}
+
+/// `GET /api/me`: the first handler in the codebase to use
+/// [`AuthUser`] instead of taking `user_id` as a plain parameter — asking
+/// a client for its own id to look itself up would defeat the point.
+pub async fn me(pool: web::Data<DbPool>, auth: AuthUser) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let user = users::table
+        .find(auth.0)
+        .select(User::as_select())
+        .first::<User>(&mut conn)
+        .map_err(|_| AppError::NotFound(format!("user {} not found", auth.0)))?;
+
+    Ok(HttpResponse::Ok().json(UserResponse::from(&user)))
+}
+
+/// `PATCH /api/me`: updates whichever of the updatable profile fields are
+/// present in the body, leaving the rest untouched.
+pub async fn update_me(
+    pool: web::Data<DbPool>,
+    auth: AuthUser,
+    body: web::Json<UpdateProfileRequest>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let user = diesel::update(users::table.find(auth.0))
+        .set(&body.into_inner())
+        .get_result::<User>(&mut conn)
+        .map_err(|e| crate::error::map_update_error(e, format!("user {} not found", auth.0)))?;
+
+    Ok(HttpResponse::Ok().json(UserResponse::from(&user)))
+}
+
+#[derive(Deserialize)]
+pub struct DeleteMeRequest {
+    /// Confirms this is really the account owner asking, not just whoever
+    /// currently holds the session bearer token — same reasoning as
+    /// requiring the current password to change it.
+    pub password: String,
+}
+
+/// `DELETE /api/me`: verifies `password`, builds a [`backup::FullBackup`]
+/// of everything the account owns so the caller has something to keep
+/// (GDPR-style data portability), then hard-deletes the account via
+/// [`purge::purge_user`] in the same transaction the export was read from.
+/// See [`purge::purge_user`]'s doc comment for what's deliberately left
+/// behind (`currencies`, `audit_log`).
+pub async fn delete_me(
+    pool: web::Data<DbPool>,
+    config: web::Data<AppConfig>,
+    auth: AuthUser,
+    body: web::Json<DeleteMeRequest>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+
+    let stored_hash = users::table
+        .find(auth.0)
+        .select(users::password_hash)
+        .first::<String>(&mut conn)
+        .map_err(|_| AppError::NotFound(format!("user {} not found", auth.0)))?;
+
+    let outcome = password::verify(body.password.clone(), stored_hash).await?;
+    if !outcome.valid {
+        return Err(AppError::Unauthorized("incorrect password".into()));
+    }
+
+    let export = conn.transaction::<_, AppError, _>(|conn| {
+        let export = backup::build_full_backup(conn, auth.0)?;
+        purge::purge_user(conn, &config, auth.0)?;
+        Ok(export)
+    })?;
+
+    Ok(HttpResponse::Ok().json(export))
+}
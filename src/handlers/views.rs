@@ -0,0 +1,58 @@
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+
+use crate::db::{cpool, DbPool};
+use crate::dto::print_view::PrintViewRequest;
+use crate::error::AppError;
+use crate::models::entry::Entry;
+use crate::schema::entries;
+
+/// Renders a page of the caller's entries as a self-contained, print-ready
+/// HTML table (fixed columns, a totals row) so the frontend doesn't have to
+/// fight the browser's print dialog for pagination and column widths.
+pub async fn print_view(
+    pool: web::Data<DbPool>,
+    body: web::Json<PrintViewRequest>,
+) -> Result<HttpResponse, AppError> {
+    let mut conn = cpool(&pool)?;
+    let body = body.into_inner();
+
+    let offset = (body.page.max(1) - 1) * body.per_page;
+    let rows: Vec<Entry> = entries::table
+        .filter(entries::user_id.eq(body.user_id))
+        .order(entries::entry_date.desc())
+        .limit(body.per_page)
+        .offset(offset)
+        .select(Entry::as_select())
+        .load(&mut conn)?;
+
+    let mut html = String::from("<table><thead><tr>");
+    for column in &body.columns {
+        html.push_str(&format!("<th>{column}</th>"));
+    }
+    html.push_str("</tr></thead><tbody>");
+
+    let mut total = 0.0;
+    for entry in &rows {
+        html.push_str("<tr>");
+        for column in &body.columns {
+            let cell = match column.as_str() {
+                "amount" => format!("{:.2}", entry.amount),
+                "target" => entry.target.clone().unwrap_or_default(),
+                "description" => entry.description.clone().unwrap_or_default(),
+                "entry_date" => entry.entry_date.format("%Y-%m-%d").to_string(),
+                _ => String::new(),
+            };
+            html.push_str(&format!("<td>{cell}</td>"));
+        }
+        html.push_str("</tr>");
+        total += entry.source_amount.to_f64_lossy();
+    }
+    html.push_str(&format!(
+        "</tbody><tfoot><tr><td colspan=\"{}\">Total: {:.2}</td></tr></tfoot></table>",
+        body.columns.len(),
+        total
+    ));
+
+    Ok(HttpResponse::Ok().content_type("text/html; charset=utf-8").body(html))
+}
@@ -0,0 +1,33 @@
+//! Firefly III CSV export: `date,amount,description,source_account,
+//! destination_account,category,budget,tags`. Source/destination accounts
+//! both map to [`crate::models::source::Source`] (a Firefly transfer
+//! becomes a `Convert` entry between the two).
+
+use super::ImportPreview;
+
+pub fn preview(csv: &str) -> ImportPreview {
+    let mut preview = ImportPreview::default();
+
+    for (i, line) in csv.lines().enumerate() {
+        if i == 0 || line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').collect();
+        if fields.len() < 6 {
+            preview.warnings.push(format!("line {}: expected 6+ columns, got {}", i + 1, fields.len()));
+            continue;
+        }
+        for account in [fields[3].trim(), fields[4].trim()] {
+            if !account.is_empty() && !preview.sources.iter().any(|s| s == account) {
+                preview.sources.push(account.to_string());
+            }
+        }
+        let category = fields[5].trim();
+        if !category.is_empty() && !preview.categories.iter().any(|c| c == category) {
+            preview.categories.push(category.to_string());
+        }
+        preview.entry_count += 1;
+    }
+
+    preview
+}
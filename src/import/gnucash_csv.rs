@@ -0,0 +1,29 @@
+//! GnuCash CSV export: `Date,Account,Description,Memo,Full Category,Amount`.
+
+use super::ImportPreview;
+
+pub fn preview(csv: &str) -> ImportPreview {
+    let mut preview = ImportPreview::default();
+
+    for (i, line) in csv.lines().enumerate() {
+        if i == 0 || line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').collect();
+        if fields.len() < 6 {
+            preview.warnings.push(format!("line {}: expected 6+ columns, got {}", i + 1, fields.len()));
+            continue;
+        }
+        let account = fields[1].trim();
+        let category = fields[4].trim();
+        if !account.is_empty() && !preview.sources.iter().any(|s| s == account) {
+            preview.sources.push(account.to_string());
+        }
+        if !category.is_empty() && !preview.categories.iter().any(|c| c == category) {
+            preview.categories.push(category.to_string());
+        }
+        preview.entry_count += 1;
+    }
+
+    preview
+}
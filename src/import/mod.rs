@@ -0,0 +1,27 @@
+//! Importers for common personal-finance export formats. Each importer
+//! parses its source format into a [`ImportPreview`] — sources/categories
+//! it would create plus the entries it would insert — without touching the
+//! database, so `POST /api/import/{format}` can show a preview before the
+//! caller confirms with `?commit=true`.
+//!
+//! The `?commit=true` step that would actually insert entries doesn't
+//! exist yet — `ImportPreview` only carries aggregate counts, not
+//! per-entry rows, so there's nowhere to apply
+//! [`crate::rules::find_match`] until a commit path exists. New entries
+//! created via `POST /api/entries` already run through the rules engine
+//! (see `crate::models::entry::matching_rule_category`); imported entries
+//! will too once this module gains a real commit step.
+
+pub mod firefly_csv;
+pub mod gnucash_csv;
+pub mod ynab;
+
+use serde::Serialize;
+
+#[derive(Serialize, Default, Debug)]
+pub struct ImportPreview {
+    pub sources: Vec<String>,
+    pub categories: Vec<String>,
+    pub entry_count: usize,
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,31 @@
+//! YNAB "Register" CSV export: `Account,Flag,Date,Payee,Category,Memo,Outflow,Inflow`.
+//! Accounts map to [`crate::models::source::Source`], payees to `entries.target`,
+//! and categories map 1:1 to [`crate::models::category::Category`].
+
+use super::ImportPreview;
+
+pub fn preview(csv: &str) -> ImportPreview {
+    let mut preview = ImportPreview::default();
+
+    for (i, line) in csv.lines().enumerate() {
+        if i == 0 || line.trim().is_empty() {
+            continue; // header row
+        }
+        let fields: Vec<&str> = line.split(',').collect();
+        if fields.len() < 7 {
+            preview.warnings.push(format!("line {}: expected 7+ columns, got {}", i + 1, fields.len()));
+            continue;
+        }
+        let account = fields[0].trim();
+        let category = fields[4].trim();
+        if !preview.sources.iter().any(|s| s == account) {
+            preview.sources.push(account.to_string());
+        }
+        if !category.is_empty() && !preview.categories.iter().any(|c| c == category) {
+            preview.categories.push(category.to_string());
+        }
+        preview.entry_count += 1;
+    }
+
+    preview
+}
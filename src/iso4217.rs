@@ -0,0 +1,36 @@
+//! A bundled subset of the ISO 4217 currency catalog, used to pre-fill
+//! `name`/`symbol`/`decimal_places` for `POST /api/currency/from-iso`.
+//!
+//! Deliberately not the full ~180-entry standard — just the currencies
+//! this codebase's users are actually likely to want. Add more rows here
+//! as they come up rather than vendoring the whole ISO table up front.
+
+pub struct CatalogEntry {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub symbol: &'static str,
+    pub decimal_places: i32,
+}
+
+const CATALOG: &[CatalogEntry] = &[
+    CatalogEntry { code: "USD", name: "US Dollar", symbol: "$", decimal_places: 2 },
+    CatalogEntry { code: "EUR", name: "Euro", symbol: "€", decimal_places: 2 },
+    CatalogEntry { code: "GBP", name: "British Pound", symbol: "£", decimal_places: 2 },
+    CatalogEntry { code: "JPY", name: "Japanese Yen", symbol: "¥", decimal_places: 0 },
+    CatalogEntry { code: "EGP", name: "Egyptian Pound", symbol: "E£", decimal_places: 2 },
+    CatalogEntry { code: "CAD", name: "Canadian Dollar", symbol: "$", decimal_places: 2 },
+    CatalogEntry { code: "AUD", name: "Australian Dollar", symbol: "$", decimal_places: 2 },
+    CatalogEntry { code: "CHF", name: "Swiss Franc", symbol: "Fr", decimal_places: 2 },
+    CatalogEntry { code: "CNY", name: "Chinese Yuan", symbol: "¥", decimal_places: 2 },
+    CatalogEntry { code: "INR", name: "Indian Rupee", symbol: "₹", decimal_places: 2 },
+    CatalogEntry { code: "AED", name: "UAE Dirham", symbol: "د.إ", decimal_places: 2 },
+    CatalogEntry { code: "SAR", name: "Saudi Riyal", symbol: "﷼", decimal_places: 2 },
+    CatalogEntry { code: "KWD", name: "Kuwaiti Dinar", symbol: "د.ك", decimal_places: 3 },
+    CatalogEntry { code: "BHD", name: "Bahraini Dinar", symbol: ".د.ب", decimal_places: 3 },
+    CatalogEntry { code: "TRY", name: "Turkish Lira", symbol: "₺", decimal_places: 2 },
+];
+
+/// Looks up a currency by its ISO 4217 code (case-insensitive).
+pub fn lookup(code: &str) -> Option<&'static CatalogEntry> {
+    CATALOG.iter().find(|entry| entry.code.eq_ignore_ascii_case(code))
+}
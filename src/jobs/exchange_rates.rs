@@ -0,0 +1,82 @@
+//! Refreshes `currencies.rate_to_fixed` from an external provider.
+//!
+//! TODO: not on a scheduler yet (same caveat as the other jobs in this
+//! module) — only reachable via the manual
+//! `POST /api/currency/refresh-rates` trigger for now.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use diesel::prelude::*;
+
+use crate::db::DbConn;
+use crate::models::currency::Currency;
+use crate::models::currency_rate::record_rate;
+use crate::schema::currencies;
+
+/// Abstracts the HTTP call so the update logic below can be exercised
+/// against a fake provider without a live network call.
+pub trait RateProvider {
+    /// Returns each currency code's value expressed in `base` (i.e. `1 base
+    /// = rates[code] code`).
+    fn fetch_rates(&self, base: &str) -> Result<HashMap<String, f64>, String>;
+}
+
+pub struct HttpRateProvider {
+    pub base_url: String,
+}
+
+impl RateProvider for HttpRateProvider {
+    fn fetch_rates(&self, base: &str) -> Result<HashMap<String, f64>, String> {
+        let url = format!("{}/latest?base={base}", self.base_url);
+        // TODO: this blocks the async worker thread; fine for the manual
+        // trigger endpoint today, but wrap in `web::block` once this runs
+        // on an actual schedule.
+        let response: HttpRatesResponse = reqwest::blocking::get(&url)
+            .map_err(|e| e.to_string())?
+            .json()
+            .map_err(|e| e.to_string())?;
+        Ok(response.rates)
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct HttpRatesResponse {
+    rates: HashMap<String, f64>,
+}
+
+/// Updates `rate_to_fixed` for every non-archived currency the provider
+/// has a rate for, relative to `fixed_code`. Currencies the provider
+/// doesn't know about are left untouched.
+pub fn refresh_rates(conn: &mut DbConn, provider: &dyn RateProvider, fixed_code: &str) -> Result<usize, String> {
+    let rates = provider.fetch_rates(fixed_code)?;
+
+    let all_currencies = currencies::table
+        .filter(currencies::archived.eq(false))
+        .select(Currency::as_select())
+        .load::<Currency>(conn)
+        .map_err(|e| e.to_string())?;
+
+    let mut updated = 0;
+    for currency in all_currencies {
+        let Some(&rate_per_fixed) = rates.get(&currency.code) else { continue };
+        if rate_per_fixed <= 0.0 {
+            continue;
+        }
+        // `rates` is "1 fixed = rate_per_fixed currency", so a unit of
+        // `currency` expressed in `fixed` is the reciprocal.
+        let now = Utc::now();
+        let rate_to_fixed = 1.0 / rate_per_fixed;
+        diesel::update(currencies::table.find(currency.id))
+            .set((
+                currencies::rate_to_fixed.eq(rate_to_fixed),
+                currencies::rate_updated_at.eq(now),
+            ))
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+        record_rate(conn, currency.id, rate_to_fixed, now.date_naive()).map_err(|e| e.to_string())?;
+        updated += 1;
+    }
+
+    Ok(updated)
+}
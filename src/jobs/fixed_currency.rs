@@ -0,0 +1,110 @@
+//! Rebases every currency's `rate_to_fixed` (and every entry's
+//! `conversion_rate_to_fixed`) onto a new reference currency.
+//!
+//! `currencies.rate_to_fixed` is a single global scale, not one per user —
+//! there's no per-user currency catalog in this schema — so although
+//! `users.fixed_currency_id` is a per-user preference, actually switching
+//! it is necessarily a system-wide recalculation. Whichever user calls
+//! `POST /api/me/fixed-currency` rebases the shared rates for everyone;
+//! this is a known sharp edge to revisit if multi-tenant currency
+//! catalogs are ever added.
+
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::db::DbConn;
+use crate::error::AppError;
+use crate::models::currency::Currency;
+use crate::models::currency_rate::record_rate;
+use crate::schema::{currencies, entries, users};
+
+#[derive(Serialize, Debug)]
+pub struct CurrencyRateChange {
+    pub currency_id: i32,
+    pub old_rate_to_fixed: f64,
+    pub new_rate_to_fixed: f64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FixedCurrencyChangeReport {
+    pub new_fixed_currency_id: i32,
+    pub currencies_changed: Vec<CurrencyRateChange>,
+    pub entries_recalculated: usize,
+    pub dry_run: bool,
+}
+
+/// Rebases every non-archived currency's `rate_to_fixed` so that
+/// `new_fixed_currency_id` becomes 1.0, and scales every entry's
+/// `conversion_rate_to_fixed` by the same factor to keep it consistent
+/// with the new reference. When `dry_run` is `true`, computes and returns
+/// the report without writing anything.
+pub fn change_fixed_currency(
+    conn: &mut DbConn,
+    user_id: i32,
+    new_fixed_currency_id: i32,
+    dry_run: bool,
+) -> Result<FixedCurrencyChangeReport, AppError> {
+    let new_fixed = currencies::table
+        .find(new_fixed_currency_id)
+        .filter(currencies::archived.eq(false))
+        .select(Currency::as_select())
+        .first::<Currency>(conn)
+        .map_err(|_| AppError::NotFound(format!("currency {new_fixed_currency_id} not found")))?;
+
+    if new_fixed.rate_to_fixed <= 0.0 {
+        return Err(AppError::Internal("cannot rebase onto a currency with a non-positive rate".into()));
+    }
+    let scale = new_fixed.rate_to_fixed;
+
+    let all_currencies = currencies::table
+        .filter(currencies::archived.eq(false))
+        .select(Currency::as_select())
+        .load::<Currency>(conn)?;
+
+    let currencies_changed: Vec<CurrencyRateChange> = all_currencies
+        .iter()
+        .map(|currency| CurrencyRateChange {
+            currency_id: currency.id,
+            old_rate_to_fixed: currency.rate_to_fixed,
+            new_rate_to_fixed: currency.rate_to_fixed / scale,
+        })
+        .collect();
+
+    let entries_recalculated = entries::table.count().get_result::<i64>(conn)? as usize;
+
+    if dry_run {
+        return Ok(FixedCurrencyChangeReport {
+            new_fixed_currency_id,
+            currencies_changed,
+            entries_recalculated,
+            dry_run: true,
+        });
+    }
+
+    conn.transaction::<_, AppError, _>(|conn| {
+        let today = chrono::Utc::now().date_naive();
+        for change in &currencies_changed {
+            diesel::update(currencies::table.find(change.currency_id))
+                .set(currencies::rate_to_fixed.eq(change.new_rate_to_fixed))
+                .execute(conn)?;
+            record_rate(conn, change.currency_id, change.new_rate_to_fixed, today)?;
+        }
+
+        diesel::update(entries::table)
+            .set(entries::conversion_rate_to_fixed.eq(entries::conversion_rate_to_fixed / scale))
+            .execute(conn)?;
+
+        diesel::update(users::table.find(user_id))
+            .set(users::fixed_currency_id.eq(new_fixed_currency_id))
+            .execute(conn)?;
+
+        Ok(())
+    })?;
+
+    Ok(FixedCurrencyChangeReport {
+        new_fixed_currency_id,
+        currencies_changed,
+        entries_recalculated,
+        dry_run: false,
+    })
+}
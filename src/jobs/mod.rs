@@ -0,0 +1,11 @@
+pub mod bank_sync;
+pub mod exchange_rates;
+pub mod fixed_currency;
+pub mod networth;
+pub mod purge;
+pub mod rate_alerts;
+pub mod recalculate;
+pub mod recompute_fixed_rates;
+pub mod recurring;
+pub mod report_schedules;
+pub mod simulate;
@@ -0,0 +1,88 @@
+//! Net worth: the sum of a user's non-archived source balances, converted
+//! into a single currency.
+//!
+//! [`current_networth`] computes it live for `GET /api/networth`.
+//! [`record_all_daily_snapshots`] persists a point-in-time reading per
+//! user (see [`crate::models::networth_snapshot`]) so `GET
+//! /api/networth/history` has a series to chart — triggered manually via
+//! `POST /api/admin/networth-snapshot` for now, the same
+//! not-yet-scheduled pattern as [`crate::jobs::exchange_rates::refresh_rates`].
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use diesel::prelude::*;
+
+use crate::db::DbConn;
+use crate::display_currency;
+use crate::error::AppError;
+use crate::models::currency::Currency;
+use crate::models::networth_snapshot::{record_snapshot, NetworthSnapshot, NewNetworthSnapshot};
+use crate::models::source::Source;
+use crate::models::user::User;
+use crate::money::Money;
+use crate::schema::{currencies, sources, users};
+
+/// Sums every non-archived source balance for `user_id`, converted into
+/// `target`.
+pub fn current_networth(conn: &mut DbConn, user_id: i32, target: &Currency) -> Result<f64, diesel::result::Error> {
+    let balances: Vec<Source> = sources::table
+        .filter(sources::user_id.eq(user_id))
+        .filter(sources::archived.eq(false))
+        .select(Source::as_select())
+        .load(conn)?;
+
+    let mut currency_cache: HashMap<i32, Currency> = HashMap::new();
+    let mut total = 0.0;
+    for source in &balances {
+        if !currency_cache.contains_key(&source.currency_id) {
+            let currency = currencies::table.find(source.currency_id).select(Currency::as_select()).first::<Currency>(conn)?;
+            currency_cache.insert(source.currency_id, currency);
+        }
+        total += display_currency::convert(&currency_cache[&source.currency_id], target, source.amount);
+    }
+
+    Ok(total)
+}
+
+/// Records today's net worth for `user_id` in their `fixed_currency_id`,
+/// overwriting any snapshot already taken today. Users without a fixed
+/// currency have nothing to normalize into, so they're skipped rather
+/// than guessing a currency for them.
+pub fn record_daily_snapshot(conn: &mut DbConn, user_id: i32) -> Result<Option<NetworthSnapshot>, AppError> {
+    let user = users::table.find(user_id).select(User::as_select()).first::<User>(conn)?;
+    let Some(fixed_currency_id) = user.fixed_currency_id else {
+        return Ok(None);
+    };
+    let target = currencies::table.find(fixed_currency_id).select(Currency::as_select()).first::<Currency>(conn)?;
+
+    let amount = current_networth(conn, user_id, &target)?;
+
+    let snapshot = record_snapshot(
+        conn,
+        NewNetworthSnapshot {
+            user_id,
+            currency_id: fixed_currency_id,
+            amount: Money::from_f64_lossy(amount),
+            snapshot_date: Utc::now().date_naive(),
+        },
+    )?;
+
+    Ok(Some(snapshot))
+}
+
+/// Runs [`record_daily_snapshot`] for every user, returning how many
+/// snapshots were actually written (skipped users with no fixed currency
+/// don't count).
+pub fn record_all_daily_snapshots(conn: &mut DbConn) -> Result<usize, AppError> {
+    let user_ids: Vec<i32> = users::table.select(users::id).load(conn)?;
+
+    let mut recorded = 0;
+    for user_id in user_ids {
+        if record_daily_snapshot(conn, user_id)?.is_some() {
+            recorded += 1;
+        }
+    }
+
+    Ok(recorded)
+}
@@ -0,0 +1,72 @@
+//! Hard-deletes everything a user owns, for
+//! [`crate::handlers::admin::delete_user`] and
+//! [`crate::handlers::users::delete_me`]. The schema has no `ON DELETE
+//! CASCADE` anywhere (most tables prefer an `archived` flag — see
+//! [`crate::models::currency`]), so this walks the foreign-key graph
+//! child-first by hand instead of relying on the database to do it.
+//!
+//! `currencies` are deliberately left untouched: they aren't scoped to a
+//! user (`currencies` has no `user_id` column), so deleting one on a
+//! single user's behalf could break another user's entries. `audit_log`
+//! is also left untouched — it's an append-only, hash-chained tamper log
+//! (see [`crate::models::audit_log`]), and removing a user's rows out of
+//! the middle of the chain would make [`crate::models::audit_log::verify_chain`]
+//! report tampering that never happened.
+
+use diesel::prelude::*;
+
+use crate::config::AppConfig;
+use crate::db::DbConn;
+use crate::models::attachment::Attachment;
+use crate::schema::{
+    alerts, attachments, budgets, categories, counterparties, custom_field_definitions, entries, entry_custom_field_values, entry_splits,
+    login_history, password_reset_tokens, payers, recurring_entries, sessions, sources, users,
+};
+use crate::storage;
+
+/// How many rows [`purge_user`] removed from each table, mostly so a
+/// caller can log or report on the scale of what just happened.
+pub struct PurgeReport {
+    pub entries_deleted: usize,
+    pub sources_deleted: usize,
+    pub categories_deleted: usize,
+}
+
+/// Deletes every row `user_id` owns, then the `users` row itself, all in
+/// the caller's transaction. Attachment files on disk are best-effort
+/// removed alongside their rows — a failure there shouldn't roll back the
+/// rest of the purge.
+pub fn purge_user(conn: &mut DbConn, config: &AppConfig, user_id: i32) -> Result<PurgeReport, diesel::result::Error> {
+    let entry_ids: Vec<i32> = entries::table.filter(entries::user_id.eq(user_id)).select(entries::id).load(conn)?;
+
+    let user_attachments = attachments::table
+        .filter(attachments::entry_id.eq_any(&entry_ids))
+        .select(Attachment::as_select())
+        .load::<Attachment>(conn)?;
+    diesel::delete(attachments::table.filter(attachments::entry_id.eq_any(&entry_ids))).execute(conn)?;
+    for attachment in &user_attachments {
+        let _ = storage::delete(config, &attachment.storage_key);
+    }
+
+    diesel::delete(entry_custom_field_values::table.filter(entry_custom_field_values::entry_id.eq_any(&entry_ids))).execute(conn)?;
+    diesel::delete(entry_splits::table.filter(entry_splits::entry_id.eq_any(&entry_ids))).execute(conn)?;
+    let entries_deleted = diesel::delete(entries::table.filter(entries::user_id.eq(user_id))).execute(conn)?;
+
+    diesel::delete(custom_field_definitions::table.filter(custom_field_definitions::user_id.eq(user_id))).execute(conn)?;
+    diesel::delete(recurring_entries::table.filter(recurring_entries::user_id.eq(user_id))).execute(conn)?;
+    diesel::delete(budgets::table.filter(budgets::user_id.eq(user_id))).execute(conn)?;
+    diesel::delete(counterparties::table.filter(counterparties::user_id.eq(user_id))).execute(conn)?;
+    diesel::delete(payers::table.filter(payers::user_id.eq(user_id))).execute(conn)?;
+    diesel::delete(alerts::table.filter(alerts::user_id.eq(user_id))).execute(conn)?;
+
+    let sources_deleted = diesel::delete(sources::table.filter(sources::user_id.eq(user_id))).execute(conn)?;
+    let categories_deleted = diesel::delete(categories::table.filter(categories::user_id.eq(user_id))).execute(conn)?;
+
+    diesel::delete(sessions::table.filter(sessions::user_id.eq(user_id))).execute(conn)?;
+    diesel::delete(login_history::table.filter(login_history::user_id.eq(user_id))).execute(conn)?;
+    diesel::delete(password_reset_tokens::table.filter(password_reset_tokens::user_id.eq(user_id))).execute(conn)?;
+
+    diesel::delete(users::table.find(user_id)).execute(conn)?;
+
+    Ok(PurgeReport { entries_deleted, sources_deleted, categories_deleted })
+}
@@ -0,0 +1,52 @@
+//! Evaluated by the (not yet scheduled — see the exchange-rate refresh job)
+//! rate-refresh loop each time `currencies.rate_to_fixed` changes for any
+//! row: any un-triggered [`Alert`] whose condition now holds gets a
+//! notification and its `triggered_at` stamped so it doesn't fire again.
+
+use diesel::prelude::*;
+
+use crate::db::DbConn;
+use crate::models::alert::{Alert, AlertDirection};
+use crate::models::currency::Currency;
+use crate::schema::{alerts, currencies};
+
+/// Cross-rate of `base` expressed in `quote`, both given in the account's
+/// fixed reference currency.
+fn cross_rate(base: &Currency, quote: &Currency) -> f64 {
+    base.rate_to_fixed / quote.rate_to_fixed
+}
+
+pub fn evaluate_and_notify(conn: &mut DbConn) -> Result<usize, diesel::result::Error> {
+    let pending = alerts::table
+        .filter(alerts::triggered_at.is_null())
+        .select(Alert::as_select())
+        .load(conn)?;
+
+    let mut triggered = 0;
+    for alert in pending {
+        let base = currencies::table
+            .find(alert.base_currency_id)
+            .select(Currency::as_select())
+            .first::<Currency>(conn)?;
+        let quote = currencies::table
+            .find(alert.quote_currency_id)
+            .select(Currency::as_select())
+            .first::<Currency>(conn)?;
+
+        let rate = cross_rate(&base, &quote);
+        let crossed = match alert.direction {
+            AlertDirection::Above => rate >= alert.threshold,
+            AlertDirection::Below => rate <= alert.threshold,
+        };
+
+        if crossed {
+            // TODO: wire up an actual notification channel (email/push).
+            diesel::update(alerts::table.find(alert.id))
+                .set(alerts::triggered_at.eq(diesel::dsl::now))
+                .execute(conn)?;
+            triggered += 1;
+        }
+    }
+
+    Ok(triggered)
+}
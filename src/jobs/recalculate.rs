@@ -0,0 +1,87 @@
+//! Rebuilds derived source balances after back-dated entry edits.
+//!
+//! The only derived value this codebase currently caches is
+//! [`crate::models::source::Source::amount`] (a running total folded from
+//! the ledger as entries are created/reconciled/transferred). Editing or
+//! inserting an entry with a past `entry_date` doesn't retroactively touch
+//! that total, so it can drift. This job re-derives it from scratch for
+//! every source touched by an entry on or after `since`, rather than
+//! rebuilding the whole ledger.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+use crate::db::DbConn;
+use crate::models::currency::Currency;
+use crate::models::entry::{Entry, EntryType};
+use crate::models::source::Source;
+use crate::money::Money;
+use crate::schema::{currencies, entries, sources};
+
+fn convert(conn: &mut DbConn, from_currency_id: i32, to_currency_id: i32, amount: Money) -> Result<Money, diesel::result::Error> {
+    if from_currency_id == to_currency_id {
+        return Ok(amount);
+    }
+    let from = currencies::table.find(from_currency_id).select(Currency::as_select()).first::<Currency>(conn)?;
+    let to = currencies::table.find(to_currency_id).select(Currency::as_select()).first::<Currency>(conn)?;
+    Ok(amount * from.rate_to_fixed / to.rate_to_fixed)
+}
+
+fn recompute_balance(conn: &mut DbConn, source: &Source) -> Result<Money, diesel::result::Error> {
+    let primary: Vec<Entry> = entries::table
+        .filter(entries::source_id.eq(source.id))
+        .select(Entry::as_select())
+        .load(conn)?;
+    let secondary: Vec<Entry> = entries::table
+        .filter(entries::secondary_source_id.eq(source.id))
+        .select(Entry::as_select())
+        .load(conn)?;
+
+    let mut balance = Money::ZERO;
+    for entry in &primary {
+        balance += match entry.entry_type {
+            EntryType::Spend | EntryType::Lend | EntryType::Convert => -entry.source_amount,
+            EntryType::Income | EntryType::Borrow | EntryType::Adjust => entry.source_amount,
+        };
+    }
+    for entry in &secondary {
+        if matches!(entry.entry_type, EntryType::Convert) {
+            balance += convert(conn, entry.currency_id, source.currency_id, entry.amount)?;
+        }
+    }
+
+    Ok(balance)
+}
+
+/// Recalculates `sources.amount` for every source with an entry dated on or
+/// after `since` (primary or secondary side of the entry), returning how
+/// many sources were touched.
+pub fn recalculate_since(conn: &mut DbConn, since: DateTime<Utc>) -> Result<usize, diesel::result::Error> {
+    let mut affected: HashSet<i32> = HashSet::new();
+    affected.extend(
+        entries::table
+            .filter(entries::entry_date.ge(since))
+            .select(entries::source_id)
+            .load::<i32>(conn)?,
+    );
+    affected.extend(
+        entries::table
+            .filter(entries::entry_date.ge(since))
+            .select(entries::secondary_source_id)
+            .load::<Option<i32>>(conn)?
+            .into_iter()
+            .flatten(),
+    );
+
+    for source_id in &affected {
+        let source = sources::table.find(source_id).select(Source::as_select()).first::<Source>(conn)?;
+        let balance = recompute_balance(conn, &source)?;
+        diesel::update(sources::table.find(source_id))
+            .set((sources::amount.eq(balance), sources::updated_at.eq(Utc::now())))
+            .execute(conn)?;
+    }
+
+    Ok(affected.len())
+}
@@ -0,0 +1,78 @@
+//! Recomputes every entry's `conversion_rate_to_fixed` from the historical
+//! [`crate::models::currency_rate`] table.
+//!
+//! `conversion_rate_to_fixed` is stamped once, at entry creation, from
+//! whatever rate was effective on the entry's own date (see
+//! [`crate::models::entry::NewEntry::stateful_try_from`]). That stamp goes
+//! stale when historical data is imported before its currency's rate
+//! history exists, or when a rate was entered wrong and later corrected —
+//! this job re-derives the column for every entry in one pass instead of
+//! requiring each affected entry to be edited by hand.
+
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::db::DbConn;
+use crate::models::currency::Currency;
+use crate::models::currency_rate::rate_effective_on;
+use crate::models::entry::Entry;
+use crate::schema::{currencies, entries};
+
+#[derive(Serialize, Debug)]
+pub struct RecomputeFixedRatesReport {
+    pub entries_checked: usize,
+    pub entries_changed: usize,
+    /// Sum, across changed entries, of `(new_rate - old_rate) *
+    /// source_amount` — how the fixed-currency total of the whole ledger
+    /// would move if this report were applied.
+    pub total_fixed_delta: f64,
+    pub dry_run: bool,
+}
+
+/// Walks every entry, looks up the rate that was actually effective on
+/// `entry_date`, and reports (or, unless `dry_run`, applies) the entries
+/// whose `conversion_rate_to_fixed` doesn't match it.
+pub fn recompute_fixed_rates(conn: &mut DbConn, dry_run: bool) -> Result<RecomputeFixedRatesReport, diesel::result::Error> {
+    let all_entries: Vec<Entry> = entries::table.select(Entry::as_select()).load(conn)?;
+
+    let mut currency_cache: HashMap<i32, Currency> = HashMap::new();
+    let mut changes: Vec<(i32, f64)> = Vec::new();
+    let mut total_fixed_delta = 0.0;
+
+    for entry in &all_entries {
+        if !currency_cache.contains_key(&entry.currency_id) {
+            let currency = currencies::table
+                .find(entry.currency_id)
+                .select(Currency::as_select())
+                .first::<Currency>(conn)?;
+            currency_cache.insert(entry.currency_id, currency);
+        }
+        let currency = &currency_cache[&entry.currency_id];
+
+        let new_rate = rate_effective_on(conn, currency, entry.entry_date.date_naive())?;
+        if new_rate != entry.conversion_rate_to_fixed {
+            total_fixed_delta += (new_rate - entry.conversion_rate_to_fixed) * entry.source_amount.to_f64_lossy();
+            changes.push((entry.id, new_rate));
+        }
+    }
+
+    if !dry_run {
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            for (entry_id, new_rate) in &changes {
+                diesel::update(entries::table.find(entry_id))
+                    .set(entries::conversion_rate_to_fixed.eq(*new_rate))
+                    .execute(conn)?;
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(RecomputeFixedRatesReport {
+        entries_checked: all_entries.len(),
+        entries_changed: changes.len(),
+        total_fixed_delta,
+        dry_run,
+    })
+}
@@ -0,0 +1,63 @@
+//! Scheduler tick for [`crate::models::recurring_entry::RecurringEntry`]
+//! templates: materializes any that are due into real entries. Exposed as
+//! a manual trigger at `POST /api/admin/recurring/run` (see
+//! [`crate::handlers::recurring::run_due_recurring`]) until this runs on
+//! an actual schedule, the same way [`crate::jobs::report_schedules`] and
+//! [`crate::jobs::recompute_fixed_rates`] do.
+
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+
+use crate::db::DbConn;
+use crate::dto::entry::CreateEntryRequest;
+use crate::error::AppError;
+use crate::handlers::entries::insert_entry_with_splits;
+use crate::models::recurring_entry::RecurringEntry;
+use crate::schema::recurring_entries;
+
+pub fn run_due(conn: &mut DbConn) -> Result<usize, AppError> {
+    let now = Utc::now();
+    let due = recurring_entries::table
+        .filter(recurring_entries::next_run_at.le(now))
+        .select(RecurringEntry::as_select())
+        .load::<RecurringEntry>(conn)?;
+
+    let mut materialized = 0;
+    for template in due {
+        // Insert and `next_run_at` advance happen in one transaction: a
+        // crash between the two would otherwise re-materialize the same
+        // entry on the next tick.
+        conn.transaction::<_, AppError, _>(|conn| {
+            insert_entry_with_splits(
+                conn,
+                CreateEntryRequest {
+                    user_id: template.user_id,
+                    source_id: template.source_id,
+                    secondary_source_id: None,
+                    category_id: template.category_id,
+                    currency_id: template.currency_id,
+                    entry_type: template.entry_type,
+                    amount: template.amount,
+                    target: template.target.clone(),
+                    counterparty_id: None,
+                    payer_id: None,
+                    description: template.description.clone(),
+                    notes: None,
+                    entry_date: template.next_run_at,
+                    splits: None,
+                    custom: Default::default(),
+                },
+            )?;
+
+            diesel::update(recurring_entries::table.find(template.id))
+                .set(recurring_entries::next_run_at.eq(template.next_run_at + Duration::days(template.interval_days as i64)))
+                .execute(conn)?;
+
+            Ok(())
+        })?;
+
+        materialized += 1;
+    }
+
+    Ok(materialized)
+}
@@ -0,0 +1,123 @@
+//! Scheduler tick for [`ReportSchedule`]: renders the monthly report to
+//! HTML and CSV and emails both to `email` via [`Mailer`], advancing
+//! `next_run_at` by the schedule's cadence. Intended to be called on a
+//! timer (see [`crate::jobs::recurring`] for the same "not actually
+//! scheduled yet" caveat) rather than per-request.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Datelike, Duration, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{Double, Integer, Text};
+
+use crate::db::DbConn;
+use crate::error::AppError;
+use crate::mail::Mailer;
+use crate::models::report_schedule::{ReportCadence, ReportSchedule};
+use crate::schema::report_schedules;
+
+#[derive(QueryableByName)]
+struct MonthlyTypeRow {
+    #[diesel(sql_type = Integer)]
+    month: i32,
+    #[diesel(sql_type = Text)]
+    entry_type: String,
+    #[diesel(sql_type = Double)]
+    total: f64,
+}
+
+struct MonthlyLine {
+    month: i32,
+    income: f64,
+    spend: f64,
+}
+
+/// Same `GROUP BY` shape as [`crate::handlers::reports::monthly`], kept
+/// separate rather than shared because a handler and a background job
+/// have different failure/response contracts.
+fn monthly_lines(conn: &mut DbConn, user_id: i32, year: i32) -> Result<Vec<MonthlyLine>, diesel::result::Error> {
+    let rows = diesel::sql_query(
+        "SELECT EXTRACT(MONTH FROM entry_date)::int AS month, \
+                entry_type, \
+                SUM(source_amount)::float8 AS total \
+         FROM entries \
+         WHERE user_id = $1 AND EXTRACT(YEAR FROM entry_date) = $2 AND entry_type IN ('income', 'spend') \
+         GROUP BY month, entry_type \
+         ORDER BY month",
+    )
+    .bind::<Integer, _>(user_id)
+    .bind::<Integer, _>(year)
+    .load::<MonthlyTypeRow>(conn)?;
+
+    let mut lines: BTreeMap<i32, MonthlyLine> = BTreeMap::new();
+    for row in rows {
+        let line = lines.entry(row.month).or_insert(MonthlyLine { month: row.month, income: 0.0, spend: 0.0 });
+        match row.entry_type.as_str() {
+            "income" => line.income = row.total,
+            "spend" => line.spend = row.total,
+            _ => {}
+        }
+    }
+    Ok(lines.into_values().collect())
+}
+
+fn render_html(year: i32, lines: &[MonthlyLine]) -> String {
+    let mut html = format!("<h1>Monthly report — {year}</h1><table><tr><th>Month</th><th>Income</th><th>Spend</th><th>Net</th></tr>");
+    for line in lines {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{:.2}</td><td>{:.2}</td><td>{:.2}</td></tr>",
+            line.month,
+            line.income,
+            line.spend,
+            line.income - line.spend
+        ));
+    }
+    html.push_str("</table>");
+    html
+}
+
+fn render_csv(lines: &[MonthlyLine]) -> String {
+    let mut csv = String::from("month,income,spend,net\n");
+    for line in lines {
+        csv.push_str(&format!("{},{:.2},{:.2},{:.2}\n", line.month, line.income, line.spend, line.income - line.spend));
+    }
+    csv
+}
+
+fn next_run_after(schedule: &ReportSchedule) -> DateTime<Utc> {
+    match schedule.cadence {
+        ReportCadence::Weekly => schedule.next_run_at + Duration::days(7),
+        ReportCadence::Monthly => schedule.next_run_at + Duration::days(30),
+    }
+}
+
+/// Emails every [`ReportSchedule`] whose `next_run_at` has elapsed and
+/// advances it by its cadence, returning how many were sent.
+pub fn run_due(conn: &mut DbConn, mailer: &dyn Mailer) -> Result<usize, AppError> {
+    let now = Utc::now();
+    let due = report_schedules::table
+        .filter(report_schedules::next_run_at.le(now))
+        .select(ReportSchedule::as_select())
+        .load::<ReportSchedule>(conn)?;
+
+    let mut sent = 0;
+    for schedule in due {
+        let year = now.year();
+        let lines = monthly_lines(conn, schedule.user_id, year)?;
+        let html = render_html(year, &lines);
+        let csv = render_csv(&lines);
+        let body = format!("{html}\n\n--- CSV export ---\n{csv}");
+
+        mailer
+            .send(&schedule.email, &format!("Your {year} monthly report"), &body)
+            .map_err(AppError::Internal)?;
+
+        diesel::update(report_schedules::table.find(schedule.id))
+            .set(report_schedules::next_run_at.eq(next_run_after(&schedule)))
+            .execute(conn)?;
+
+        sent += 1;
+    }
+
+    Ok(sent)
+}
@@ -0,0 +1,146 @@
+//! Pure, no-write projection engine behind `POST /api/simulate`
+//! (`handlers::simulate::simulate`). Reuses the same recurring-entry
+//! materialization rule as [`crate::jobs::recurring::run_due`] and the
+//! same source-delta signs as `handlers::entries::apply_source_deltas`,
+//! but only ever accumulates into an in-memory snapshot — nothing here
+//! touches the database.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+
+use crate::db::DbConn;
+use crate::dto::simulate::{BudgetProjection, MonthProjection, SimulateRequest};
+use crate::models::budget::{Budget, BudgetPeriod};
+use crate::models::entry::EntryType;
+use crate::models::recurring_entry::RecurringEntry;
+use crate::money::Money;
+use crate::schema::{budgets, recurring_entries, sources};
+
+fn signed_delta(entry_type: EntryType, amount: Money) -> Money {
+    match entry_type {
+        EntryType::Spend | EntryType::Lend | EntryType::Convert => -amount,
+        EntryType::Income | EntryType::Borrow | EntryType::Adjust => amount,
+    }
+}
+
+/// The first instant of the calendar month `months_ahead` months after
+/// `from`. Deliberately clamped to the 1st of the month rather than
+/// preserving `from`'s day-of-month, since it's only ever used as a
+/// month-bucket boundary, not a real date.
+fn month_boundary(from: DateTime<Utc>, months_ahead: u32) -> DateTime<Utc> {
+    use chrono::Datelike;
+    let total_months = from.year() as i64 * 12 + (from.month() as i64 - 1) + months_ahead as i64;
+    let year = (total_months.div_euclid(12)) as i32;
+    let month = total_months.rem_euclid(12) as u32 + 1;
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .expect("year/month derived from a valid date stays valid")
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is always a valid time")
+        .and_utc()
+}
+
+/// Projects `request.months` calendar months forward from now, without
+/// persisting anything: recurring templates materialize on schedule same
+/// as [`crate::jobs::recurring::run_due`] would, `request.hypothetical_entries`
+/// layer on top, and `request.budget_changes` override (or add to) the
+/// user's real monthly budgets for comparison.
+pub fn project(conn: &mut DbConn, request: &SimulateRequest) -> Result<Vec<MonthProjection>, diesel::result::Error> {
+    let mut balances: HashMap<i32, Money> = sources::table
+        .filter(sources::user_id.eq(request.user_id))
+        .select((sources::id, sources::amount))
+        .load::<(i32, Money)>(conn)?
+        .into_iter()
+        .collect();
+
+    let mut templates = recurring_entries::table
+        .filter(recurring_entries::user_id.eq(request.user_id))
+        .select(RecurringEntry::as_select())
+        .load::<RecurringEntry>(conn)?;
+
+    let mut monthly_budgets: HashMap<i32, Money> = budgets::table
+        .filter(budgets::user_id.eq(request.user_id))
+        .filter(budgets::period.eq(BudgetPeriod::Monthly))
+        .select(Budget::as_select())
+        .load::<Budget>(conn)?
+        .into_iter()
+        .map(|b| (b.category_id, b.amount))
+        .collect();
+    for change in &request.budget_changes {
+        monthly_budgets.insert(change.category_id, change.new_limit);
+    }
+
+    let now = Utc::now();
+    let mut months = Vec::with_capacity(request.months as usize);
+
+    for month_index in 0..request.months {
+        let month_start = month_boundary(now, month_index);
+        let month_end = month_boundary(now, month_index + 1);
+        let mut category_totals: HashMap<i32, Money> = HashMap::new();
+
+        for template in &mut templates {
+            while template.next_run_at < month_end {
+                if template.next_run_at >= month_start {
+                    apply(&mut balances, &mut category_totals, template.source_id, template.category_id, template.entry_type, template.amount);
+                }
+                template.next_run_at += Duration::days(template.interval_days as i64);
+            }
+        }
+
+        for hyp in &request.hypothetical_entries {
+            let occurs_this_month = if hyp.repeat_monthly {
+                hyp.entry_date < month_end
+            } else {
+                hyp.entry_date >= month_start && hyp.entry_date < month_end
+            };
+            if occurs_this_month {
+                apply(&mut balances, &mut category_totals, hyp.source_id, hyp.category_id, hyp.entry_type, hyp.amount);
+            }
+        }
+
+        let mut budget_categories: Vec<i32> = monthly_budgets.keys().copied().collect();
+        budget_categories.sort_unstable();
+        let budget_projection = budget_categories
+            .into_iter()
+            .map(|category_id| {
+                let limit = monthly_budgets[&category_id];
+                let projected_spent = category_totals.get(&category_id).copied().unwrap_or(Money::ZERO);
+                BudgetProjection {
+                    category_id,
+                    limit,
+                    projected_spent,
+                    remaining: limit - projected_spent,
+                }
+            })
+            .collect();
+
+        months.push(MonthProjection {
+            month_index,
+            month_start,
+            projected_balances: balances.clone(),
+            category_totals,
+            budget_projection,
+        });
+    }
+
+    Ok(months)
+}
+
+/// Applies one entry's (real template or hypothetical) effect to the
+/// running balance snapshot and, if it's a spend with a category, to this
+/// month's category totals.
+fn apply(
+    balances: &mut HashMap<i32, Money>,
+    category_totals: &mut HashMap<i32, Money>,
+    source_id: i32,
+    category_id: Option<i32>,
+    entry_type: EntryType,
+    amount: Money,
+) {
+    *balances.entry(source_id).or_insert(Money::ZERO) += signed_delta(entry_type, amount);
+
+    if let (Some(category_id), EntryType::Spend) = (category_id, entry_type) {
+        *category_totals.entry(category_id).or_insert(Money::ZERO) += amount;
+    }
+}
@@ -0,0 +1,40 @@
+use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+
+/// Shared with `main.rs` so [`handlers::health::readyz`] can check
+/// `conn.pending_migrations(MIGRATIONS)` the same way `money migrate`
+/// does, without embedding the migration set a second time.
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+pub mod auth;
+pub mod cache;
+pub mod config;
+pub mod crypto;
+pub mod db;
+pub mod demo;
+pub mod display_currency;
+pub mod dto;
+pub mod error;
+pub mod export;
+pub mod grpc;
+pub mod handlers;
+pub mod import;
+pub mod iso4217;
+pub mod jobs;
+pub mod list_query;
+pub mod llm;
+pub mod logging;
+pub mod magic_bytes;
+pub mod mail;
+pub mod migration_policy;
+pub mod models;
+pub mod money;
+pub mod oidc;
+pub mod password;
+pub mod rules;
+pub mod scanning;
+pub mod schema;
+pub mod startup;
+pub mod stateful;
+pub mod storage;
+pub mod telegram;
+pub mod validation;
@@ -0,0 +1,63 @@
+//! Shared `?q=`/`?sort=`/pagination query shape for list endpoints. Each
+//! handler still writes its own filter/sort match arms — Diesel's column
+//! types don't erase cleanly enough for a single generic implementation —
+//! but they share this struct so the query-string contract (and its
+//! defaults/limits) stays identical across resources instead of drifting
+//! endpoint by endpoint.
+
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+#[derive(Deserialize, Debug)]
+pub struct ListQuery {
+    /// Case-insensitive substring match against the resource's name column.
+    pub q: Option<String>,
+    /// `field` sorts ascending, `-field` descending. Each handler validates
+    /// `field` against its own whitelist of sortable columns.
+    pub sort: Option<String>,
+    #[serde(default = "default_page")]
+    pub page: i64,
+    pub per_page: Option<i64>,
+}
+
+fn default_page() -> i64 {
+    1
+}
+
+pub struct Pagination {
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl ListQuery {
+    pub fn pagination(&self) -> Pagination {
+        let limit = self.per_page.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let page = self.page.max(1);
+        Pagination {
+            limit,
+            offset: (page - 1) * limit,
+        }
+    }
+
+    /// Splits `sort` into `(column, ascending)`, falling back to
+    /// `(default_column, true)` when unset or empty.
+    pub fn sort_direction<'a>(&'a self, default_column: &'a str) -> (&'a str, bool) {
+        match self.sort.as_deref() {
+            Some(field) if field.starts_with('-') && field.len() > 1 => (&field[1..], false),
+            Some(field) if !field.is_empty() => (field, true),
+            _ => (default_column, true),
+        }
+    }
+}
+
+/// A single page of results, with enough of the pagination state echoed
+/// back that a client doesn't have to remember what it asked for.
+#[derive(Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub page: i64,
+    pub per_page: i64,
+    pub total: i64,
+}
@@ -0,0 +1,66 @@
+//! Proposes entry fields from a free-text line (`POST /api/entry/parse`)
+//! through a pluggable [`EntryParseProvider`], mirroring how
+//! [`crate::jobs::exchange_rates::RateProvider`] abstracts its HTTP call
+//! so the parsing endpoint isn't tied to a live LLM API, and
+//! [`crate::mail::Mailer`] for the "`None` disables the feature"
+//! convention.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::AppConfig;
+
+/// What the provider proposes for fields [`crate::rules`] and the
+/// historical-match heuristic in
+/// [`crate::handlers::entries::parse_entry`] can't resolve on their own.
+/// Every field is optional: the caller already has local regex-extracted
+/// `amount`/`currency_code`/`entry_date`, and a provider may not return an
+/// opinion on every remaining field either.
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct ParsedEntryDraft {
+    pub entry_type: Option<String>,
+    pub category_name: Option<String>,
+    pub source_name: Option<String>,
+    pub description: Option<String>,
+}
+
+pub trait EntryParseProvider {
+    fn parse(&self, text: &str) -> Result<ParsedEntryDraft, String>;
+}
+
+pub struct HttpLlmProvider {
+    pub base_url: String,
+    pub api_key: String,
+}
+
+#[derive(Serialize)]
+struct CompletionRequest<'a> {
+    prompt: &'a str,
+}
+
+impl EntryParseProvider for HttpLlmProvider {
+    fn parse(&self, text: &str) -> Result<ParsedEntryDraft, String> {
+        let url = format!("{}/parse-entry", self.base_url);
+        // TODO: this blocks the async worker thread; fine for the
+        // low-volume manual-entry flow today, same caveat as
+        // `HttpRateProvider::fetch_rates`.
+        let client = reqwest::blocking::Client::new();
+        client
+            .post(&url)
+            .bearer_auth(&self.api_key)
+            .json(&CompletionRequest { prompt: text })
+            .send()
+            .map_err(|e| e.to_string())?
+            .json::<ParsedEntryDraft>()
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// `None` when `AppConfig::llm_provider_url` is unset — the parsing
+/// endpoint then relies on the historical-match heuristic alone, rather
+/// than falling back to a no-op provider the way [`crate::mail::build`]
+/// falls back to [`crate::mail::LoggingMailer`]: there's no useful
+/// default guess for entry fields the way there is for "send an email".
+pub fn build(config: &AppConfig) -> Option<Box<dyn EntryParseProvider>> {
+    let base_url = config.llm_provider_url.clone()?;
+    Some(Box::new(HttpLlmProvider { base_url, api_key: config.llm_api_key.clone() }))
+}
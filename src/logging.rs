@@ -0,0 +1,104 @@
+//! Access + application logging setup for [`crate::main`]. The request
+//! that asked for this (`money-rs#synth-2304`) named the entry point
+//! `env_vars::init_logger`, but neither that module nor any logging
+//! framework existed anywhere in this tree yet — [`init_logger`] and
+//! [`crate::config::AppConfig`]'s `log_*` fields are the equivalent this
+//! codebase's own conventions produce: an `env_logger` setup driven from
+//! `AppConfig`, feeding both `actix_web::middleware::Logger`'s access log
+//! and the rest of the app's `log::info!`/`log::warn!` calls, with an
+//! optional rotating file sink for self-hosted installs that don't have a
+//! log collector sitting in front of stdout.
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use chrono::Utc;
+
+use crate::config::{AppConfig, LogRotation};
+
+/// Sets up `log`/`env_logger` from `config.log_level`, additionally
+/// tee-ing output to a rotating file under `config.log_dir` when one is
+/// configured. Should be called once, before `HttpServer::new`.
+pub fn init_logger(config: &AppConfig) {
+    let mut builder = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(&config.log_level));
+
+    if let Some(dir) = &config.log_dir {
+        let writer = RotatingFileWriter::new(dir.clone(), config.log_rotation);
+        builder.target(env_logger::Target::Pipe(Box::new(writer)));
+    }
+
+    builder.init();
+}
+
+/// A [`Write`] sink that always mirrors to stdout (so `docker logs` and
+/// foreground runs keep working unchanged) and additionally appends to a
+/// file under `dir` that it rolls over according to `rotation`.
+struct RotatingFileWriter {
+    dir: PathBuf,
+    rotation: LogRotation,
+    current_path: Option<PathBuf>,
+    current_bytes: u64,
+    file: Option<std::fs::File>,
+}
+
+impl RotatingFileWriter {
+    fn new(dir: PathBuf, rotation: LogRotation) -> Self {
+        Self {
+            dir,
+            rotation,
+            current_path: None,
+            current_bytes: 0,
+            file: None,
+        }
+    }
+
+    /// The path the current rotation period should be writing to. For
+    /// `Daily`, this changes automatically at midnight since it's derived
+    /// from today's date; for `SizeBytes`, it's a fixed name that gets
+    /// renamed aside (with a timestamp suffix) once it fills up.
+    fn active_path(&self) -> PathBuf {
+        match self.rotation {
+            LogRotation::Daily => self.dir.join(format!("money-rs-{}.log", Utc::now().format("%Y-%m-%d"))),
+            LogRotation::SizeBytes(_) => self.dir.join("money-rs.log"),
+        }
+    }
+
+    fn ensure_open(&mut self) -> io::Result<()> {
+        let target = self.active_path();
+        let over_size_limit = matches!(self.rotation, LogRotation::SizeBytes(max) if self.current_bytes >= max);
+
+        if self.file.is_some() && self.current_path.as_deref() == Some(target.as_path()) && !over_size_limit {
+            return Ok(());
+        }
+
+        if over_size_limit {
+            let rolled = self.dir.join(format!("money-rs-{}.log", Utc::now().format("%Y%m%dT%H%M%S")));
+            let _ = std::fs::rename(&target, rolled);
+        }
+
+        std::fs::create_dir_all(&self.dir)?;
+        let file = OpenOptions::new().create(true).append(true).open(&target)?;
+        self.current_bytes = file.metadata().map(|m| m.len()).unwrap_or(0);
+        self.current_path = Some(target);
+        self.file = Some(file);
+        Ok(())
+    }
+}
+
+impl Write for RotatingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        io::stdout().write_all(buf)?;
+        self.ensure_open()?;
+        let written = self.file.as_mut().expect("ensure_open just opened it").write(buf)?;
+        self.current_bytes += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if let Some(file) = self.file.as_mut() {
+            file.flush()?;
+        }
+        io::stdout().flush()
+    }
+}
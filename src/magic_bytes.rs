@@ -0,0 +1,26 @@
+//! A small bundled allowlist of file-type signatures, checked against the
+//! first bytes of an upload before it's ever passed to
+//! [`crate::scanning`] or written to [`crate::storage`] — a client-supplied
+//! `Content-Type` header is just a claim, this looks at the actual bytes.
+//!
+//! Deliberately just the types a receipt/statement upload plausibly is,
+//! not a general-purpose file-type sniffer. Add more signatures here as
+//! they come up, same as [`crate::iso4217`]'s catalog.
+
+struct Signature {
+    magic: &'static [u8],
+}
+
+const ALLOWED: &[Signature] = &[
+    Signature { magic: b"\xFF\xD8\xFF" },                         // JPEG
+    Signature { magic: b"\x89PNG\r\n\x1a\n" },                    // PNG
+    Signature { magic: b"GIF87a" },                                // GIF
+    Signature { magic: b"GIF89a" },                                // GIF
+    Signature { magic: b"%PDF-" },                                 // PDF
+    Signature { magic: b"RIFF" },                                  // WEBP (RIFF....WEBP)
+];
+
+/// Whether `bytes` starts with one of [`ALLOWED`]'s signatures.
+pub fn is_allowed(bytes: &[u8]) -> bool {
+    ALLOWED.iter().any(|sig| bytes.starts_with(sig.magic))
+}
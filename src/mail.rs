@@ -0,0 +1,73 @@
+//! Sends transactional email (currently just password-reset links) through
+//! a pluggable [`Mailer`], mirroring how
+//! [`crate::jobs::exchange_rates::RateProvider`] abstracts its HTTP call so
+//! the caller isn't tied to a live SMTP relay.
+
+use lettre::message::Message;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{SmtpTransport, Transport};
+
+use crate::config::AppConfig;
+
+pub trait Mailer {
+    fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), String>;
+}
+
+pub struct SmtpMailer {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    pub from: String,
+}
+
+impl Mailer for SmtpMailer {
+    fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), String> {
+        let email = Message::builder()
+            .from(self.from.parse().map_err(|e: lettre::address::AddressError| e.to_string())?)
+            .to(to.parse().map_err(|e: lettre::address::AddressError| e.to_string())?)
+            .subject(subject)
+            .body(body.to_string())
+            .map_err(|e| e.to_string())?;
+
+        let transport = SmtpTransport::relay(&self.host)
+            .map_err(|e| e.to_string())?
+            .port(self.port)
+            .credentials(Credentials::new(self.username.clone(), self.password.clone()))
+            .build();
+
+        // TODO: this blocks the async worker thread; fine for the
+        // low-volume password-reset flow today, same caveat as
+        // `HttpRateProvider::fetch_rates`.
+        transport.send(&email).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
+
+/// Used when `AppConfig::smtp_host` is unset — logs the message instead of
+/// sending it, so the reset flow stays usable in dev/demo environments
+/// without a real mail relay configured.
+pub struct LoggingMailer;
+
+impl Mailer for LoggingMailer {
+    fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), String> {
+        eprintln!("SMTP disabled; would have sent to {to}: {subject}\n{body}");
+        Ok(())
+    }
+}
+
+/// Picks [`SmtpMailer`] or [`LoggingMailer`] depending on whether
+/// `smtp_host` is configured, shared by every caller that needs to send
+/// mail instead of each standing up its own `SmtpTransport`.
+pub fn build(config: &AppConfig) -> Box<dyn Mailer> {
+    match &config.smtp_host {
+        Some(host) => Box::new(SmtpMailer {
+            host: host.clone(),
+            port: config.smtp_port,
+            username: config.smtp_username.clone(),
+            password: config.smtp_password.clone(),
+            from: config.smtp_from.clone(),
+        }),
+        None => Box::new(LoggingMailer),
+    }
+}
@@ -0,0 +1,262 @@
+use actix_web::middleware::Logger;
+use actix_web::{web, App, HttpServer};
+use diesel_migrations::MigrationHarness;
+
+use money_rs::config::AppConfig;
+use money_rs::db::{build_pool, build_reports_pool};
+use money_rs::{demo, grpc, handlers, logging, migration_policy, startup, MIGRATIONS};
+
+pub const BIND_ADDRESS: &str = "127.0.0.1:8080";
+
+/// `money config check`: prints the effective configuration and DB/
+/// migration status (see [`startup`]) and exits — without starting the
+/// HTTP server or applying anything — so an operator can sanity-check a
+/// deployment before `money migrate` and the server itself run for real.
+fn run_config_check_command() -> std::io::Result<()> {
+    let database_url = std::env::var("DATABASE_URL").ok();
+    let config = AppConfig::from_env();
+    logging::init_logger(&config);
+
+    let pending_migrations = match &database_url {
+        Some(database_url) => {
+            let pool = build_pool(database_url);
+            match pool.get() {
+                Ok(mut conn) => conn
+                    .pending_migrations(MIGRATIONS)
+                    .map(|migrations| migrations.iter().map(|m| m.name().to_string()).collect())
+                    .unwrap_or_default(),
+                Err(_) => Vec::new(),
+            }
+        }
+        None => Vec::new(),
+    };
+
+    let report = startup::effective_config(&config, database_url.as_deref(), BIND_ADDRESS, pending_migrations);
+    startup::log_banner(&report);
+
+    if database_url.is_none() {
+        eprintln!("DATABASE_URL is not set");
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// `money migrate [--allow-unsafe]`: runs pending migrations against
+/// `DATABASE_URL` and exits, without starting the HTTP server. Refuses to
+/// proceed if [`migration_policy::assess_all`] flags anything, unless
+/// `--allow-unsafe` is passed — the guardrail self-hosters asked for so a
+/// routine deploy can't accidentally lock up a live instance.
+fn run_migrate_command(allow_unsafe: bool) -> std::io::Result<()> {
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = build_pool(&database_url);
+    let mut conn = pool.get().expect("failed to get connection from pool");
+
+    let unsafe_migrations = migration_policy::assess_all(std::path::Path::new("migrations"))?;
+    if !unsafe_migrations.is_empty() && !allow_unsafe {
+        eprintln!("refusing to run migrations: the following look unsafe to run against a live database:");
+        for migration in &unsafe_migrations {
+            eprintln!("  {}", migration.name);
+            for reason in &migration.reasons {
+                eprintln!("    - {reason}");
+            }
+        }
+        eprintln!("pass --allow-unsafe to run them anyway.");
+        std::process::exit(1);
+    }
+
+    conn.run_pending_migrations(MIGRATIONS)
+        .map(|_| ())
+        .map_err(|e| std::io::Error::other(e.to_string()))
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    if std::env::args().nth(1).as_deref() == Some("migrate") {
+        let allow_unsafe = std::env::args().any(|arg| arg == "--allow-unsafe");
+        return run_migrate_command(allow_unsafe);
+    }
+
+    if std::env::args().nth(1).as_deref() == Some("config") && std::env::args().nth(2).as_deref() == Some("check") {
+        return run_config_check_command();
+    }
+
+    let ephemeral = std::env::args().any(|arg| arg == "--ephemeral");
+
+    let database_url = if ephemeral {
+        // A fresh, throwaway schema per run — dropped by the OS when the
+        // process exits, since it's backed by a temp Postgres instance in
+        // CI/dev rather than a real one.
+        std::env::var("EPHEMERAL_DATABASE_URL")
+            .expect("EPHEMERAL_DATABASE_URL must be set to use --ephemeral")
+    } else {
+        std::env::var("DATABASE_URL").expect("DATABASE_URL must be set")
+    };
+    let pool = build_pool(&database_url);
+    let reports_pool = build_reports_pool(&database_url);
+
+    if ephemeral {
+        let mut conn = pool.get().expect("failed to get connection from pool");
+        conn.run_pending_migrations(MIGRATIONS)
+            .expect("failed to run migrations for ephemeral mode");
+        demo::seed(&mut conn).expect("failed to seed demo data");
+    }
+
+    let config = AppConfig::from_env();
+    logging::init_logger(&config);
+
+    let pending_migrations = {
+        let mut conn = pool.get().expect("failed to get connection from pool");
+        conn.pending_migrations(MIGRATIONS)
+            .map(|migrations| migrations.iter().map(|m| m.name().to_string()).collect())
+            .unwrap_or_default()
+    };
+    let report = startup::effective_config(&config, Some(&database_url), BIND_ADDRESS, pending_migrations);
+    startup::log_banner(&report);
+
+    // Runs alongside the REST server rather than instead of it — same
+    // process, same `DbPool`, just a second listener for clients that
+    // prefer protobuf over JSON. `None` leaves it disabled entirely.
+    if let Some(grpc_bind_address) = config.grpc_bind_address.clone() {
+        match grpc::build(pool.clone(), config.clone(), &grpc_bind_address) {
+            Ok(server) => {
+                tokio::spawn(async move {
+                    if let Err(e) = server.await {
+                        log::error!("grpc server exited: {e}");
+                    }
+                });
+            }
+            Err(e) => log::error!("invalid GRPC_BIND_ADDRESS {grpc_bind_address:?}: {e}"),
+        }
+    }
+
+    HttpServer::new(move || {
+        App::new()
+            .wrap(Logger::default())
+            .app_data(web::Data::new(pool.clone()))
+            .app_data(web::Data::new(reports_pool.clone()))
+            .app_data(web::Data::new(config.clone()))
+            .route("/healthz", web::get().to(handlers::health::healthz))
+            .route("/readyz", web::get().to(handlers::health::readyz))
+            .route("/api/admin/recalculate", web::post().to(handlers::admin::recalculate))
+            .route("/api/admin/recompute-fixed-rates", web::post().to(handlers::admin::recompute_fixed_rates))
+            .route("/api/admin/networth-snapshot", web::post().to(handlers::admin::record_networth_snapshots))
+            .route("/api/networth", web::get().to(handlers::networth::networth))
+            .route("/api/networth/history", web::get().to(handlers::networth::networth_history))
+            .route("/api/admin/integrity/chain", web::get().to(handlers::admin::verify_integrity_chain))
+            .route("/api/admin/stats", web::get().to(handlers::admin::stats))
+            .route("/api/admin/users", web::get().to(handlers::admin::list_users))
+            .route("/api/admin/users/{id}", web::patch().to(handlers::admin::set_user_disabled))
+            .route("/api/admin/users/{id}", web::delete().to(handlers::admin::delete_user))
+            .route("/api/entries", web::post().to(handlers::entries::create_entry))
+            .route("/api/entries", web::get().to(handlers::entries::find_entries))
+            .route("/api/entry/aggregate", web::get().to(handlers::entries::aggregate_entries))
+            .route("/api/entry/distinct", web::get().to(handlers::entries::distinct_values))
+            .route("/api/entry/parse", web::post().to(handlers::entries::parse_entry))
+            .route("/ingest/email/{token}", web::post().to(handlers::email_ingest::receive_email))
+            .route("/api/email-ingest/token/{user_id}", web::post().to(handlers::email_ingest::create_ingest_token))
+            .route("/api/email-receipts/user/{user_id}", web::get().to(handlers::email_ingest::list_pending_receipts))
+            .route("/api/email-receipts/{receipt_id}/confirm", web::post().to(handlers::email_ingest::confirm_receipt))
+            .route("/api/openapi.yaml", web::get().to(handlers::openapi::openapi_spec))
+            .route("/api/search", web::get().to(handlers::search::search))
+            .route("/api/summary", web::get().to(handlers::summary::summary))
+            .route("/api/stats", web::get().to(handlers::stats::stats))
+            .route("/api/changes", web::get().to(handlers::changes::list_changes))
+            .route("/api/changes", web::post().to(handlers::changes::push_changes))
+            .route("/register", web::post().to(handlers::users::register))
+            .route("/login", web::post().to(handlers::users::login))
+            .route("/logout", web::post().to(handlers::users::logout))
+            .route("/password-reset/request", web::post().to(handlers::users::request_password_reset))
+            .route("/password-reset/confirm", web::post().to(handlers::users::confirm_password_reset))
+            .route("/login/oidc/start", web::get().to(handlers::oidc::oidc_start))
+            .route("/login/oidc/callback", web::get().to(handlers::oidc::oidc_callback))
+            .route("/api/me", web::get().to(handlers::users::me))
+            .route("/api/me", web::patch().to(handlers::users::update_me))
+            .route("/api/me", web::delete().to(handlers::users::delete_me))
+            .route("/api/me/sessions", web::get().to(handlers::users::list_sessions))
+            .route("/api/me/sessions/{session_id}", web::delete().to(handlers::users::revoke_session))
+            .route("/api/me/fixed-currency", web::post().to(handlers::users::change_fixed_currency))
+            .route("/api/entry/{entry_id}/duplicate", web::post().to(handlers::entries::duplicate_entry))
+            .route("/api/alerts", web::post().to(handlers::alerts::create_alert))
+            .route("/api/alerts/user/{user_id}", web::get().to(handlers::alerts::list_alerts))
+            .route("/api/alerts/{alert_id}", web::delete().to(handlers::alerts::delete_alert))
+            .route("/api/sources/{source_id}/check", web::get().to(handlers::sources::check_source))
+            .route("/api/reports/household-split", web::get().to(handlers::reports::household_split))
+            .route("/api/reports/monthly", web::get().to(handlers::reports::monthly))
+            .route("/api/reports/monthly/pdf", web::get().to(handlers::reports::monthly_pdf))
+            .route("/api/reports/categories", web::get().to(handlers::reports::category_breakdown))
+            .route("/api/reports/cashflow", web::get().to(handlers::reports::cashflow))
+            .route("/api/reports/budget", web::get().to(handlers::reports::budget_vs_actual))
+            .route("/api/reports/trends", web::get().to(handlers::reports::trends))
+            .route("/api/reports/flows", web::get().to(handlers::reports::flows))
+            .route("/api/reports/targets", web::get().to(handlers::reports::targets))
+            .route("/api/report-schedules", web::post().to(handlers::report_schedules::create_report_schedule))
+            .route("/api/report-schedules/user/{user_id}", web::get().to(handlers::report_schedules::list_report_schedules))
+            .route("/api/report-schedules/{schedule_id}", web::delete().to(handlers::report_schedules::delete_report_schedule))
+            .route("/api/admin/report-schedules/run", web::post().to(handlers::report_schedules::run_due_report_schedules))
+            .route("/api/saved-query", web::post().to(handlers::saved_queries::create_saved_query))
+            .route("/api/saved-queries", web::get().to(handlers::saved_queries::list_saved_queries))
+            .route("/api/saved-query/{name}", web::delete().to(handlers::saved_queries::delete_saved_query))
+            .route("/api/saved-query/{name}/run", web::get().to(handlers::saved_queries::run_saved_query))
+            .route("/api/insights/patterns", web::get().to(handlers::insights::patterns))
+            .route("/api/insights/duplicate-entities", web::get().to(handlers::insights::duplicate_entities))
+            .route("/api/entry/{entry_id}/attachment", web::post().to(handlers::attachments::upload_attachment))
+            .route("/api/entry/{entry_id}/attachment", web::get().to(handlers::attachments::list_attachments))
+            .route("/api/entry/{entry_id}/attachment", web::delete().to(handlers::attachments::delete_attachment))
+            .route("/api/attachments/{attachment_id}", web::get().to(handlers::attachments::download_attachment))
+            .route("/api/source/{name}/reconcile", web::post().to(handlers::sources::reconcile_source))
+            .route("/api/source/{name}/transfer", web::post().to(handlers::sources::transfer))
+            .route("/api/source/{name}/merge", web::post().to(handlers::sources::merge_source))
+            .route("/api/source/{name}/bank-sync", web::post().to(handlers::bank_connections::trigger_bank_sync))
+            .route("/api/bank-connections", web::post().to(handlers::bank_connections::create_bank_connection))
+            .route("/api/bank-connections/user/{user_id}", web::get().to(handlers::bank_connections::list_bank_connections))
+            .route("/api/bank-connections/{connection_id}", web::delete().to(handlers::bank_connections::delete_bank_connection))
+            .route("/api/bank-transactions/connection/{connection_id}", web::get().to(handlers::bank_connections::list_pending_bank_transactions))
+            .route("/api/bank-transactions/{transaction_id}/confirm", web::post().to(handlers::bank_connections::confirm_bank_transaction))
+            .route("/api/category/{name}/merge", web::post().to(handlers::categories::merge_category))
+            .route("/api/budget", web::post().to(handlers::budgets::create_budget))
+            .route("/api/budget/user/{user_id}", web::get().to(handlers::budgets::list_budgets))
+            .route("/api/budget/status/{user_id}", web::get().to(handlers::budgets::budget_status))
+            .route("/api/recurring", web::post().to(handlers::recurring::create_recurring))
+            .route("/api/recurring/user/{user_id}", web::get().to(handlers::recurring::list_recurring))
+            .route("/api/admin/recurring/run", web::post().to(handlers::recurring::run_due_recurring))
+            .route("/api/rules", web::post().to(handlers::rules::create_rule))
+            .route("/api/rules/user/{user_id}", web::get().to(handlers::rules::list_rules))
+            .route("/api/rules/{rule_id}", web::delete().to(handlers::rules::delete_rule))
+            .route("/api/rules/apply/{user_id}", web::post().to(handlers::rules::apply_rules))
+            .route("/api/telegram/link/{user_id}", web::post().to(handlers::telegram::create_link_code))
+            .route("/api/telegram/webhook", web::post().to(handlers::telegram::webhook))
+            .route("/api/currencies", web::post().to(handlers::currencies::create_currency))
+            .route("/api/currencies", web::get().to(handlers::currencies::list_currencies))
+            .route("/api/currencies/{currency_id}", web::delete().to(handlers::currencies::archive_currency))
+            .route("/api/custom-fields", web::post().to(handlers::custom_fields::create_custom_field_definition))
+            .route(
+                "/api/custom-fields/user/{user_id}",
+                web::get().to(handlers::custom_fields::list_custom_field_definitions),
+            )
+            .route("/api/sources/user/{user_id}", web::get().to(handlers::sources::list_sources))
+            .route("/api/categories/user/{user_id}", web::get().to(handlers::categories::list_categories))
+            .route("/api/convert", web::get().to(handlers::currencies::convert))
+            .route("/api/currency/refresh-rates", web::post().to(handlers::currencies::refresh_rates))
+            .route("/api/currency/{name}/rates/backfill", web::post().to(handlers::currencies::backfill_rates))
+            .route("/api/currency/from-iso", web::post().to(handlers::currencies::from_iso))
+            .route("/api/counterparties", web::post().to(handlers::counterparties::create_counterparty))
+            .route("/api/counterparties/user/{user_id}", web::get().to(handlers::counterparties::list_counterparties))
+            .route("/api/payers", web::post().to(handlers::payers::create_payer))
+            .route("/api/payers/user/{user_id}", web::get().to(handlers::payers::list_payers))
+            .route("/api/reports/income-by-payer", web::get().to(handlers::payers::income_by_payer))
+            .route("/api/import/{format}", web::post().to(handlers::import::preview_import))
+            .route("/api/export.beancount", web::get().to(handlers::export::export_beancount))
+            .route("/api/export/ledger", web::get().to(handlers::export::export_ledger))
+            .route("/api/export/full", web::get().to(handlers::backup::export_full))
+            .route("/api/import/full", web::post().to(handlers::backup::import_full))
+            .route("/api/export/audit-log", web::get().to(handlers::audit::export_audit_log))
+            .route("/api/export/login-history", web::get().to(handlers::audit::export_login_history))
+            .route("/api/views/print", web::post().to(handlers::views::print_view))
+            .route("/api/simulate", web::post().to(handlers::simulate::simulate))
+            .route("/api/share", web::post().to(handlers::share::create_share))
+            .route("/shared/{token}", web::get().to(handlers::share::get_shared))
+    })
+    .bind(BIND_ADDRESS)?
+    .run()
+    .await
+}
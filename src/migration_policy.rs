@@ -0,0 +1,85 @@
+//! Safety policy for `money migrate` (see `main.rs`): scans pending
+//! migrations for patterns that lock up a live database rather than
+//! running quietly alongside normal traffic, and refuses to run them
+//! unless the operator passes `--allow-unsafe`.
+
+use std::path::Path;
+
+/// Tables read or written on close to every request. A migration that
+/// rewrites one of these under an `ACCESS EXCLUSIVE` lock stalls the whole
+/// app until it finishes, instead of just slowing one code path down.
+const HOT_TABLES: &[&str] = &["entries", "entry_splits", "audit_log", "sessions", "sync_client_mutations"];
+
+#[derive(Debug, Clone)]
+pub struct UnsafeMigration {
+    pub name: String,
+    pub reasons: Vec<String>,
+}
+
+/// Scans every `up.sql` under `migrations_dir` (in the same lexical order
+/// diesel applies them) and flags any statement this policy considers
+/// dangerous. Deliberately text-based rather than a real SQL parser —
+/// good enough to catch the common cases (`CREATE INDEX` without
+/// `CONCURRENTLY`, a rewriting `ALTER TABLE` on a hot table) without
+/// vendoring a parser for a guardrail that's meant to be conservative,
+/// not exhaustive.
+pub fn assess_all(migrations_dir: &Path) -> std::io::Result<Vec<UnsafeMigration>> {
+    let mut unsafe_migrations = Vec::new();
+    let mut entries: Vec<_> = std::fs::read_dir(migrations_dir)?.filter_map(|e| e.ok()).collect();
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        let Ok(sql) = std::fs::read_to_string(entry.path().join("up.sql")) else {
+            continue;
+        };
+        let reasons = assess(&sql);
+        if !reasons.is_empty() {
+            unsafe_migrations.push(UnsafeMigration {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                reasons,
+            });
+        }
+    }
+
+    Ok(unsafe_migrations)
+}
+
+fn assess(sql: &str) -> Vec<String> {
+    let normalized = sql.to_uppercase();
+    let mut reasons = Vec::new();
+
+    for statement in normalized.split(';') {
+        let statement = statement.trim();
+        if statement.is_empty() {
+            continue;
+        }
+
+        if (statement.starts_with("CREATE INDEX") || statement.starts_with("CREATE UNIQUE INDEX")) && !statement.contains("CONCURRENTLY") {
+            reasons.push(format!(
+                "`{}` builds an index without CONCURRENTLY, holding a write lock on the table for the duration",
+                first_words(statement, 6)
+            ));
+        }
+
+        if let Some(table) = statement.strip_prefix("ALTER TABLE ").and_then(alter_table_name) {
+            let is_hot_table = HOT_TABLES.iter().any(|t| t.eq_ignore_ascii_case(&table));
+            let rewrites_table = statement.contains("ALTER COLUMN") && statement.contains(" TYPE ");
+            if is_hot_table && rewrites_table {
+                reasons.push(format!(
+                    "`{}` changes a column type on hot table `{table}`, which rewrites the whole table",
+                    first_words(statement, 6)
+                ));
+            }
+        }
+    }
+
+    reasons
+}
+
+fn alter_table_name(rest: &str) -> Option<String> {
+    rest.split_whitespace().next().map(|s| s.trim_matches('"').to_lowercase())
+}
+
+fn first_words(s: &str, n: usize) -> String {
+    s.split_whitespace().take(n).collect::<Vec<_>>().join(" ")
+}
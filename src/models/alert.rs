@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::prelude::*;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::alerts;
+
+#[derive(AsExpression, FromSqlRow, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[diesel(sql_type = Text)]
+pub enum AlertDirection {
+    Above,
+    Below,
+}
+
+impl<DB: Backend> ToSql<Text, DB> for AlertDirection
+where
+    str: ToSql<Text, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        match self {
+            AlertDirection::Above => "above",
+            AlertDirection::Below => "below",
+        }
+        .to_sql(out)
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for AlertDirection
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        match String::from_sql(bytes)?.as_str() {
+            "above" => Ok(AlertDirection::Above),
+            "below" => Ok(AlertDirection::Below),
+            other => Err(format!("unrecognized alert direction: {other}").into()),
+        }
+    }
+}
+
+/// A user-defined "notify me when BASE→QUOTE crosses X" rate condition.
+/// Evaluated by the rate-refresh job each time exchange rates update; once
+/// triggered, `triggered_at` is set so it fires only once per crossing.
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = alerts)]
+pub struct Alert {
+    pub id: i32,
+    pub user_id: i32,
+    pub base_currency_id: i32,
+    pub quote_currency_id: i32,
+    pub threshold: f64,
+    pub direction: AlertDirection,
+    pub triggered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = alerts)]
+pub struct NewAlert {
+    pub user_id: i32,
+    pub base_currency_id: i32,
+    pub quote_currency_id: i32,
+    pub threshold: f64,
+    pub direction: AlertDirection,
+}
@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::schema::attachments;
+
+#[derive(Queryable, Identifiable, Selectable, Associations, Serialize, Debug, Clone)]
+#[diesel(table_name = attachments)]
+#[diesel(belongs_to(crate::models::entry::Entry, foreign_key = entry_id))]
+pub struct Attachment {
+    pub id: i32,
+    pub entry_id: i32,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i32,
+    /// Opaque key into [`crate::storage`] — a relative path under the local
+    /// attachments directory today, an object key if/when an S3-compatible
+    /// backend is wired in.
+    pub storage_key: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = attachments)]
+pub struct NewAttachment {
+    pub entry_id: i32,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i32,
+    pub storage_key: String,
+}
@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::schema::audit_log;
+
+/// Append-only record of a mutation, hash-chained via [`record`] so
+/// [`verify_chain`] can detect a row that was edited or deleted out from
+/// under the log after the fact.
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = audit_log)]
+pub struct AuditLogEntry {
+    pub id: i32,
+    pub user_id: i32,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: i32,
+    pub created_at: DateTime<Utc>,
+    pub prev_hash: Option<String>,
+    pub hash: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = audit_log)]
+struct NewAuditLogEntry {
+    user_id: i32,
+    action: String,
+    entity_type: String,
+    entity_id: i32,
+    created_at: DateTime<Utc>,
+    prev_hash: Option<String>,
+    hash: String,
+}
+
+fn chain_hash(prev_hash: &Option<String>, user_id: i32, action: &str, entity_type: &str, entity_id: i32, created_at: DateTime<Utc>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(prev_hash.as_deref().unwrap_or("").as_bytes());
+    hasher.update(user_id.to_le_bytes());
+    hasher.update(action.as_bytes());
+    hasher.update(entity_type.as_bytes());
+    hasher.update(entity_id.to_le_bytes());
+    hasher.update(created_at.to_rfc3339().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Appends a hash-chained record of a mutation. Callers should run this
+/// inside the same transaction as the mutation it describes, since
+/// `.for_update()` on the chain tip is what keeps two concurrent appends
+/// from computing the same `prev_hash` and forking the chain.
+pub fn record(conn: &mut PgConnection, user_id: i32, action: &str, entity_type: &str, entity_id: i32) -> QueryResult<AuditLogEntry> {
+    let prev_hash = audit_log::table
+        .order(audit_log::id.desc())
+        .select(audit_log::hash)
+        .for_update()
+        .first::<String>(conn)
+        .optional()?;
+
+    let created_at = Utc::now();
+    let hash = chain_hash(&prev_hash, user_id, action, entity_type, entity_id, created_at);
+
+    diesel::insert_into(audit_log::table)
+        .values(&NewAuditLogEntry {
+            user_id,
+            action: action.to_string(),
+            entity_type: entity_type.to_string(),
+            entity_id,
+            created_at,
+            prev_hash,
+            hash,
+        })
+        .get_result(conn)
+}
+
+/// The result of walking the chain from the beginning: either it's intact,
+/// or `broken_at` names the first row whose `hash` doesn't match what its
+/// own fields and `prev_hash` recompute to.
+#[derive(Serialize, Debug)]
+pub struct ChainVerification {
+    pub rows_checked: usize,
+    pub intact: bool,
+    pub broken_at: Option<i32>,
+}
+
+/// Walks every `audit_log` row in id order, recomputing each hash from its
+/// own fields and the previous row's `hash`, and reports the id of the
+/// first row that doesn't match — either because a field was edited after
+/// the fact, or because a row was deleted and the chain now skips it.
+pub fn verify_chain(conn: &mut PgConnection) -> QueryResult<ChainVerification> {
+    let rows = audit_log::table
+        .order(audit_log::id.asc())
+        .select(AuditLogEntry::as_select())
+        .load::<AuditLogEntry>(conn)?;
+
+    let mut expected_prev_hash: Option<String> = None;
+    for row in &rows {
+        let expected_hash = chain_hash(&expected_prev_hash, row.user_id, &row.action, &row.entity_type, row.entity_id, row.created_at);
+        if row.prev_hash != expected_prev_hash || row.hash != expected_hash {
+            return Ok(ChainVerification {
+                rows_checked: rows.len(),
+                intact: false,
+                broken_at: Some(row.id),
+            });
+        }
+        expected_prev_hash = Some(row.hash.clone());
+    }
+
+    Ok(ChainVerification {
+        rows_checked: rows.len(),
+        intact: true,
+        broken_at: None,
+    })
+}
@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::bank_connections;
+
+/// A linked bank (via a GoCardless/Nordigen-style account data API) that
+/// [`crate::jobs::bank_sync`] can pull transactions from into
+/// [`crate::models::bank_transaction::BankTransaction`] rows for one
+/// [`crate::models::source::Source`]. `access_token` is stored as the
+/// provider gives it to us — see [`crate::models::session::Session`]'s
+/// doc comment for why this codebase stores bearer-style secrets in plain
+/// columns rather than encrypting them.
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = bank_connections)]
+pub struct BankConnection {
+    pub id: i32,
+    pub user_id: i32,
+    pub source_id: i32,
+    pub provider: String,
+    pub institution_id: String,
+    pub external_account_id: String,
+    #[serde(skip_serializing)]
+    pub access_token: String,
+    pub consent_expires_at: Option<DateTime<Utc>>,
+    pub last_synced_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Deserialize, Debug)]
+#[diesel(table_name = bank_connections)]
+pub struct NewBankConnection {
+    pub user_id: i32,
+    pub source_id: i32,
+    pub provider: String,
+    pub institution_id: String,
+    pub external_account_id: String,
+    pub access_token: String,
+    pub consent_expires_at: Option<DateTime<Utc>>,
+}
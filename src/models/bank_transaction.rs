@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::money::Money;
+use crate::schema::bank_transactions;
+
+/// One transaction pulled from a [`crate::models::bank_connection::BankConnection`].
+/// Stays unattached to any real [`crate::models::entry::Entry`] (`entry_id`
+/// is `None`) until a human reviews and confirms it via
+/// `POST /api/bank-transactions/{id}/confirm` — a bank feed reports
+/// transactions that already happened, so inserting them straight into
+/// the ledger without review would double-count anything the user also
+/// logged by hand. `(bank_connection_id, external_id)` is unique so
+/// re-running a sync never creates duplicates.
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = bank_transactions)]
+pub struct BankTransaction {
+    pub id: i32,
+    pub bank_connection_id: i32,
+    pub external_id: String,
+    pub amount: Money,
+    pub booked_date: DateTime<Utc>,
+    pub description: Option<String>,
+    pub entry_id: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = bank_transactions)]
+pub struct NewBankTransaction {
+    pub bank_connection_id: i32,
+    pub external_id: String,
+    pub amount: Money,
+    pub booked_date: DateTime<Utc>,
+    pub description: Option<String>,
+}
@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::prelude::*;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use serde::{Deserialize, Serialize};
+
+use crate::money::Money;
+use crate::schema::budgets;
+
+#[derive(AsExpression, FromSqlRow, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[diesel(sql_type = Text)]
+pub enum BudgetPeriod {
+    Monthly,
+    Yearly,
+}
+
+impl<DB: Backend> ToSql<Text, DB> for BudgetPeriod
+where
+    str: ToSql<Text, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        match self {
+            BudgetPeriod::Monthly => "monthly",
+            BudgetPeriod::Yearly => "yearly",
+        }
+        .to_sql(out)
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for BudgetPeriod
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        match String::from_sql(bytes)?.as_str() {
+            "monthly" => Ok(BudgetPeriod::Monthly),
+            "yearly" => Ok(BudgetPeriod::Yearly),
+            other => Err(format!("unrecognized budget period: {other}").into()),
+        }
+    }
+}
+
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = budgets)]
+pub struct Budget {
+    pub id: i32,
+    pub user_id: i32,
+    pub category_id: i32,
+    pub currency_id: i32,
+    pub amount: Money,
+    pub period: BudgetPeriod,
+    pub start_date: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Deserialize, Debug)]
+#[diesel(table_name = budgets)]
+pub struct NewBudget {
+    pub user_id: i32,
+    pub category_id: i32,
+    pub currency_id: i32,
+    pub amount: Money,
+    pub period: BudgetPeriod,
+    pub start_date: DateTime<Utc>,
+}
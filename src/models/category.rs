@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::schema::categories;
+
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = categories)]
+pub struct Category {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = categories)]
+pub struct NewCategory {
+    pub user_id: i32,
+    pub name: String,
+}
+
+/// Name given to the auto-created catch-all category returned by
+/// [`get_or_create_uncategorized`].
+pub const UNCATEGORIZED_NAME: &str = "Uncategorized";
+
+/// Finds the user's "Uncategorized" category, creating it on first use.
+/// Entries land here (rather than with a bare `NULL` category) whenever an
+/// import or quick-add can't resolve a category, so `GET
+/// /api/entries?uncategorized=true` has a concrete id to filter on.
+pub fn get_or_create_uncategorized(
+    conn: &mut diesel::pg::PgConnection,
+    user_id: i32,
+) -> Result<Category, diesel::result::Error> {
+    let existing = categories::table
+        .filter(categories::user_id.eq(user_id))
+        .filter(categories::name.eq(UNCATEGORIZED_NAME))
+        .select(Category::as_select())
+        .first::<Category>(conn)
+        .optional()?;
+
+    match existing {
+        Some(category) => Ok(category),
+        None => diesel::insert_into(categories::table)
+            .values(&NewCategory { user_id, name: UNCATEGORIZED_NAME.to_string() })
+            .get_result::<Category>(conn),
+    }
+}
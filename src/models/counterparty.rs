@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::counterparties;
+
+/// Replaces `entries.target` free text with a proper entity so payees can
+/// be renamed, deduplicated, and reported on — the same shape as
+/// [`crate::models::category::Category`].
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = counterparties)]
+pub struct Counterparty {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Deserialize, Debug)]
+#[diesel(table_name = counterparties)]
+pub struct NewCounterparty {
+    pub user_id: i32,
+    pub name: String,
+}
@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::currencies;
+
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = currencies)]
+pub struct Currency {
+    pub id: i32,
+    pub code: String,
+    pub name: String,
+    /// Value of one unit of this currency expressed in the account's fixed
+    /// reference currency. Recomputed whenever exchange rates refresh.
+    pub rate_to_fixed: f64,
+    pub created_at: DateTime<Utc>,
+    /// Soft-deleted. `code` is only unique among non-archived rows (see
+    /// `currencies_code_active_key`), so archiving one frees its code up
+    /// for reuse or revival — see [`crate::handlers::currencies::create_currency`].
+    pub archived: bool,
+    /// Last time [`crate::jobs::exchange_rates::refresh_rates`] updated
+    /// `rate_to_fixed` for this row. `None` for currencies that have only
+    /// ever had their rate set manually.
+    pub rate_updated_at: Option<DateTime<Utc>>,
+    /// Display symbol (e.g. `$`, `€`). `None` for currencies created
+    /// before [`crate::iso4217`] existed or created without one.
+    pub symbol: Option<String>,
+    /// Minor-unit digits (2 for USD, 0 for JPY, ...). Defaults to 2.
+    pub decimal_places: i32,
+}
+
+#[derive(Insertable, Deserialize, Debug)]
+#[diesel(table_name = currencies)]
+pub struct NewCurrency {
+    pub code: String,
+    pub name: String,
+    pub rate_to_fixed: f64,
+    #[serde(default)]
+    pub symbol: Option<String>,
+}
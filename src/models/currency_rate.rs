@@ -0,0 +1,67 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+
+use crate::models::currency::Currency;
+use crate::schema::currency_rates;
+
+/// A currency's `rate_to_fixed` as of a given calendar day. Appended to
+/// (never mutated) whenever [`crate::jobs::exchange_rates::refresh_rates`]
+/// or a manual rate edit runs, so historical statistics can use the rate
+/// that was actually in effect on `entry.entry_date` instead of whatever
+/// `currencies.rate_to_fixed` happens to hold today.
+#[derive(Queryable, Identifiable, Selectable, Associations, Debug, Clone)]
+#[diesel(table_name = currency_rates)]
+#[diesel(belongs_to(Currency))]
+pub struct CurrencyRate {
+    pub id: i32,
+    pub currency_id: i32,
+    pub rate_to_fixed: f64,
+    pub effective_date: NaiveDate,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = currency_rates)]
+pub struct NewCurrencyRate {
+    pub currency_id: i32,
+    pub rate_to_fixed: f64,
+    pub effective_date: NaiveDate,
+}
+
+/// Records `rate_to_fixed` as effective on `effective_date`, overwriting
+/// any rate already recorded for that same currency and day (a currency
+/// can only refresh once per day in practice, but re-running a manual
+/// refresh shouldn't create a duplicate history row).
+pub fn record_rate(
+    conn: &mut diesel::pg::PgConnection,
+    currency_id: i32,
+    rate_to_fixed: f64,
+    effective_date: NaiveDate,
+) -> QueryResult<CurrencyRate> {
+    diesel::insert_into(currency_rates::table)
+        .values(&NewCurrencyRate { currency_id, rate_to_fixed, effective_date })
+        .on_conflict((currency_rates::currency_id, currency_rates::effective_date))
+        .do_update()
+        .set(currency_rates::rate_to_fixed.eq(rate_to_fixed))
+        .get_result::<CurrencyRate>(conn)
+}
+
+/// The rate effective on `date`: the most recent recorded rate on or
+/// before that day, falling back to `currencies.rate_to_fixed` when no
+/// history has been recorded yet (e.g. a currency created before this
+/// table existed, or one whose rate has only ever been set manually).
+pub fn rate_effective_on(
+    conn: &mut diesel::pg::PgConnection,
+    currency: &Currency,
+    date: NaiveDate,
+) -> QueryResult<f64> {
+    let historical = currency_rates::table
+        .filter(currency_rates::currency_id.eq(currency.id))
+        .filter(currency_rates::effective_date.le(date))
+        .order(currency_rates::effective_date.desc())
+        .select(currency_rates::rate_to_fixed)
+        .first::<f64>(conn)
+        .optional()?;
+
+    Ok(historical.unwrap_or(currency.rate_to_fixed))
+}
@@ -0,0 +1,174 @@
+use chrono::{DateTime, Utc};
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::prelude::*;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::{custom_field_definitions, entry_custom_field_values};
+
+/// How a [`CustomFieldDefinition`]'s value is interpreted; the underlying
+/// storage in [`EntryCustomFieldValue::value`] is always text (same choice
+/// as [`crate::models::entry::EntryType`]), so this only governs
+/// validation and how the DTO layer parses/formats the wire value.
+#[derive(AsExpression, FromSqlRow, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[diesel(sql_type = Text)]
+pub enum CustomFieldType {
+    Text,
+    Number,
+    Bool,
+}
+
+impl CustomFieldType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CustomFieldType::Text => "text",
+            CustomFieldType::Number => "number",
+            CustomFieldType::Bool => "bool",
+        }
+    }
+}
+
+impl<DB: Backend> ToSql<Text, DB> for CustomFieldType
+where
+    str: ToSql<Text, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        self.as_str().to_sql(out)
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for CustomFieldType
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        match String::from_sql(bytes)?.as_str() {
+            "text" => Ok(CustomFieldType::Text),
+            "number" => Ok(CustomFieldType::Number),
+            "bool" => Ok(CustomFieldType::Bool),
+            other => Err(format!("unrecognized custom_field type: {other}").into()),
+        }
+    }
+}
+
+/// A user-defined field (e.g. "Project", "Reimbursable"), configured once
+/// and attachable to any of that user's entries via
+/// [`EntryCustomFieldValue`].
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = custom_field_definitions)]
+pub struct CustomFieldDefinition {
+    pub id: i32,
+    pub user_id: i32,
+    pub key: String,
+    pub field_type: CustomFieldType,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = custom_field_definitions)]
+pub struct NewCustomFieldDefinition {
+    pub user_id: i32,
+    pub key: String,
+    pub field_type: CustomFieldType,
+}
+
+/// One entry's value for one [`CustomFieldDefinition`]. Stored as text
+/// regardless of `field_type` and parsed back out at the DTO boundary,
+/// same tradeoff `entry_type` makes for its enum.
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = entry_custom_field_values)]
+pub struct EntryCustomFieldValue {
+    pub id: i32,
+    pub entry_id: i32,
+    pub definition_id: i32,
+    pub value: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, AsChangeset, Debug)]
+#[diesel(table_name = entry_custom_field_values)]
+pub struct NewEntryCustomFieldValue {
+    pub entry_id: i32,
+    pub definition_id: i32,
+    pub value: String,
+}
+
+/// Looks up one of a user's field definitions by its key, e.g. to validate
+/// a `custom: {}` map key on entry create.
+pub fn find_by_key(conn: &mut PgConnection, user_id: i32, key: &str) -> QueryResult<Option<CustomFieldDefinition>> {
+    custom_field_definitions::table
+        .filter(custom_field_definitions::user_id.eq(user_id))
+        .filter(custom_field_definitions::key.eq(key))
+        .select(CustomFieldDefinition::as_select())
+        .first(conn)
+        .optional()
+}
+
+/// Validates `value` against `field_type` and returns its canonical text
+/// form for storage in [`EntryCustomFieldValue::value`].
+pub fn validate_and_stringify(field_type: CustomFieldType, value: &serde_json::Value) -> Result<String, String> {
+    match field_type {
+        CustomFieldType::Text => value.as_str().map(str::to_string).ok_or_else(|| "expected a string".to_string()),
+        CustomFieldType::Number => value.as_f64().map(|n| n.to_string()).ok_or_else(|| "expected a number".to_string()),
+        CustomFieldType::Bool => value.as_bool().map(|b| b.to_string()).ok_or_else(|| "expected a boolean".to_string()),
+    }
+}
+
+/// Reverses [`validate_and_stringify`] for the response DTO.
+pub fn parse(field_type: CustomFieldType, raw: &str) -> serde_json::Value {
+    match field_type {
+        CustomFieldType::Text => serde_json::Value::String(raw.to_string()),
+        CustomFieldType::Number => raw
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        CustomFieldType::Bool => raw.parse::<bool>().map(serde_json::Value::Bool).unwrap_or(serde_json::Value::Null),
+    }
+}
+
+/// Loads every definition a user has configured, keyed by `key`, so
+/// [`crate::dto::entry::CustomFieldsMap`] can validate an incoming
+/// `custom: {}` map against it without a query per key.
+pub fn definitions_for_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+) -> QueryResult<Vec<CustomFieldDefinition>> {
+    custom_field_definitions::table
+        .filter(custom_field_definitions::user_id.eq(user_id))
+        .select(CustomFieldDefinition::as_select())
+        .load(conn)
+}
+
+/// Loads all custom field values for one entry.
+pub fn values_for_entry(conn: &mut PgConnection, entry_id: i32) -> QueryResult<Vec<EntryCustomFieldValue>> {
+    entry_custom_field_values::table
+        .filter(entry_custom_field_values::entry_id.eq(entry_id))
+        .select(EntryCustomFieldValue::as_select())
+        .load(conn)
+}
+
+/// Replaces `entry_id`'s value for `definition_id`, inserting it if this is
+/// the first time this field has been set on this entry.
+pub fn upsert_value(
+    conn: &mut PgConnection,
+    entry_id: i32,
+    definition_id: i32,
+    value: &str,
+) -> QueryResult<EntryCustomFieldValue> {
+    use diesel::upsert::excluded;
+
+    diesel::insert_into(entry_custom_field_values::table)
+        .values(&NewEntryCustomFieldValue {
+            entry_id,
+            definition_id,
+            value: value.to_string(),
+        })
+        .on_conflict((entry_custom_field_values::entry_id, entry_custom_field_values::definition_id))
+        .do_update()
+        .set(entry_custom_field_values::value.eq(excluded(entry_custom_field_values::value)))
+        .get_result(conn)
+}
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use rand::RngCore;
+use serde::Serialize;
+
+use crate::models::entry::EntryType;
+use crate::money::Money;
+use crate::schema::{email_ingest_tokens, email_receipts};
+
+/// A per-user secret that gates `POST /ingest/email/{token}` — the
+/// address a user forwards receipt emails to is nothing more than this
+/// token, checked the same way [`crate::models::share_link::find_active`]
+/// checks a share link's token.
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = email_ingest_tokens)]
+pub struct EmailIngestToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub token: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = email_ingest_tokens)]
+pub struct NewEmailIngestToken {
+    pub user_id: i32,
+    pub token: String,
+}
+
+/// A URL-safe random token; not derived from anything about the user,
+/// same reasoning as [`crate::models::session::generate_token`].
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// A forwarded receipt email, parsed into the same field shape as
+/// [`crate::handlers::entries::EntryDraft`] but persisted — unlike the
+/// `/api/entry/parse` draft, which the caller must act on immediately, an
+/// inbound email has no session attached to confirm it right away, so it
+/// sits here until `POST /api/email-receipts/{id}/confirm` turns it into
+/// a real [`crate::models::entry::Entry`].
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = email_receipts)]
+pub struct EmailReceipt {
+    pub id: i32,
+    pub user_id: i32,
+    pub ingest_token_id: i32,
+    pub subject: Option<String>,
+    pub sender: Option<String>,
+    pub raw_text: String,
+    pub source_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub currency_id: Option<i32>,
+    pub entry_type: Option<EntryType>,
+    pub amount: Option<Money>,
+    pub description: Option<String>,
+    pub entry_date: DateTime<Utc>,
+    pub entry_id: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = email_receipts)]
+pub struct NewEmailReceipt {
+    pub user_id: i32,
+    pub ingest_token_id: i32,
+    pub subject: Option<String>,
+    pub sender: Option<String>,
+    pub raw_text: String,
+    pub source_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub currency_id: Option<i32>,
+    pub entry_type: Option<EntryType>,
+    pub amount: Option<Money>,
+    pub description: Option<String>,
+    pub entry_date: DateTime<Utc>,
+}
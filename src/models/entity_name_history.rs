@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::schema::entity_name_history;
+
+/// Which table a row in [`entity_name_history`] refers to. Kept as a plain
+/// string column (like `entries.entry_type`) rather than three separate
+/// history tables, since the lookup and rename logic is identical for all
+/// three renameable entities.
+pub const SOURCE: &str = "source";
+pub const CATEGORY: &str = "category";
+pub const CURRENCY: &str = "currency";
+
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = entity_name_history)]
+pub struct EntityNameHistory {
+    pub id: i32,
+    pub entity_type: String,
+    pub entity_id: i32,
+    pub old_name: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = entity_name_history)]
+pub struct NewEntityNameHistory {
+    pub entity_type: String,
+    pub entity_id: i32,
+    pub old_name: String,
+}
+
+/// Resolves `name` against the given entity type: first as its current
+/// name, and if that finds nothing, against the rename history — so a
+/// stale bookmark or saved view keeps working after a rename.
+pub fn resolve_source_by_name_or_history(
+    conn: &mut PgConnection,
+    user_id: i32,
+    name: &str,
+) -> QueryResult<Option<crate::models::source::Source>> {
+    use crate::schema::sources;
+
+    if let Some(source) = sources::table
+        .filter(sources::name.eq(name))
+        .filter(sources::user_id.eq(user_id))
+        .select(crate::models::source::Source::as_select())
+        .first(conn)
+        .optional()?
+    {
+        return Ok(Some(source));
+    }
+
+    let redirect = entity_name_history::table
+        .filter(entity_name_history::entity_type.eq(SOURCE))
+        .filter(entity_name_history::old_name.eq(name))
+        .order(entity_name_history::changed_at.desc())
+        .select(EntityNameHistory::as_select())
+        .first::<EntityNameHistory>(conn)
+        .optional()?;
+
+    match redirect {
+        Some(redirect) => sources::table
+            .find(redirect.entity_id)
+            .filter(sources::user_id.eq(user_id))
+            .select(crate::models::source::Source::as_select())
+            .first(conn)
+            .optional(),
+        None => Ok(None),
+    }
+}
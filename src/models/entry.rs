@@ -0,0 +1,233 @@
+use chrono::{DateTime, Utc};
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use diesel::pg::PgConnection;
+use crate::dto::entry::CreateEntryRequest;
+use crate::error::AppError;
+use crate::models::category::get_or_create_uncategorized;
+use crate::models::currency::Currency;
+use crate::models::currency_rate::rate_effective_on;
+use crate::models::source::Source;
+use crate::money::Money;
+use crate::schema::{currencies, entries, sources};
+use crate::stateful::StatefulTryFrom;
+use crate::validation::require_finite_positive_rate;
+
+#[derive(AsExpression, FromSqlRow, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[diesel(sql_type = Text)]
+pub enum EntryType {
+    Spend,
+    Income,
+    Convert,
+    Lend,
+    Borrow,
+    /// System-generated correction produced by the reconciliation workflow
+    /// when a source's real-world balance doesn't match the ledger.
+    Adjust,
+}
+
+impl EntryType {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            EntryType::Spend => "spend",
+            EntryType::Income => "income",
+            EntryType::Convert => "convert",
+            EntryType::Lend => "lend",
+            EntryType::Borrow => "borrow",
+            EntryType::Adjust => "adjust",
+        }
+    }
+}
+
+impl<DB: Backend> ToSql<Text, DB> for EntryType
+where
+    str: ToSql<Text, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        self.as_str().to_sql(out)
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for EntryType
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        match String::from_sql(bytes)?.as_str() {
+            "spend" => Ok(EntryType::Spend),
+            "income" => Ok(EntryType::Income),
+            "convert" => Ok(EntryType::Convert),
+            "lend" => Ok(EntryType::Lend),
+            "borrow" => Ok(EntryType::Borrow),
+            "adjust" => Ok(EntryType::Adjust),
+            other => Err(format!("unrecognized entry_type: {other}").into()),
+        }
+    }
+}
+
+#[derive(Queryable, Identifiable, Selectable, Associations, Serialize, Debug, Clone)]
+#[diesel(table_name = entries)]
+#[diesel(belongs_to(Source, foreign_key = source_id))]
+#[diesel(belongs_to(Currency, foreign_key = currency_id))]
+pub struct Entry {
+    pub id: i32,
+    pub user_id: i32,
+    pub source_id: i32,
+    pub secondary_source_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub currency_id: i32,
+    pub entry_type: EntryType,
+    pub amount: Money,
+    /// `amount` converted into the source's currency at `conversion_rate`.
+    /// Kept alongside the original amount so the ledger is never lossy
+    /// about what the user actually typed in.
+    pub source_amount: Money,
+    pub conversion_rate: f64,
+    pub conversion_rate_to_fixed: f64,
+    pub target: Option<String>,
+    pub description: Option<String>,
+    pub notes: Option<String>,
+    pub entry_date: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub created_by: Option<i32>,
+    pub updated_by: Option<i32>,
+    pub counterparty_id: Option<i32>,
+    pub payer_id: Option<i32>,
+    /// Bumped on every write, including balance-affecting updates that
+    /// touch a row already inserted (there are none yet — entries are
+    /// currently create-only). Exists so [`crate::handlers::changes`] has
+    /// a cursor to hand offline clients once an edit endpoint lands.
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = entries)]
+pub struct NewEntry {
+    pub user_id: i32,
+    pub source_id: i32,
+    pub secondary_source_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub currency_id: i32,
+    pub entry_type: EntryType,
+    pub amount: Money,
+    pub source_amount: Money,
+    pub conversion_rate: f64,
+    pub conversion_rate_to_fixed: f64,
+    pub target: Option<String>,
+    pub description: Option<String>,
+    pub notes: Option<String>,
+    pub entry_date: DateTime<Utc>,
+    pub created_by: Option<i32>,
+    pub updated_by: Option<i32>,
+    pub counterparty_id: Option<i32>,
+    pub payer_id: Option<i32>,
+}
+
+impl StatefulTryFrom<CreateEntryRequest, PgConnection> for NewEntry {
+    type Error = AppError;
+
+    /// `conversion_rate` and `conversion_rate_to_fixed` are never trusted
+    /// from the client: they are derived here from the source's and the
+    /// entry's own currency `rate_to_fixed` at creation time, so a client
+    /// cannot backdate or fabricate a favorable rate.
+    fn stateful_try_from(value: CreateEntryRequest, conn: &mut PgConnection) -> Result<Self, Self::Error> {
+        let source = sources::table
+            .find(value.source_id)
+            .select(Source::as_select())
+            .first(conn)
+            .map_err(|_| AppError::NotFound(format!("source {} not found", value.source_id)))?;
+
+        let entry_currency = currencies::table
+            .find(value.currency_id)
+            .select(Currency::as_select())
+            .first(conn)
+            .map_err(|_| AppError::NotFound(format!("currency {} not found", value.currency_id)))?;
+
+        let source_currency = currencies::table
+            .find(source.currency_id)
+            .select(Currency::as_select())
+            .first(conn)
+            .map_err(|_| AppError::Internal("source currency missing".into()))?;
+
+        // A currency with a zero, negative, NaN, or infinite `rate_to_fixed`
+        // shouldn't exist (see `require_finite_positive_rate` at creation),
+        // but this is the one place that would actually divide by it —
+        // fail loudly here instead of silently zeroing `source_amount` the
+        // way `Money`'s `Mul`/`Div` fall back to `Decimal::default()` on an
+        // unparseable rate.
+        require_finite_positive_rate("entry currency rate_to_fixed", entry_currency.rate_to_fixed)?;
+        require_finite_positive_rate("source currency rate_to_fixed", source_currency.rate_to_fixed)?;
+
+        // Currency mismatch between the entry and its source is the common
+        // case, not an edge case (e.g. a USD source funding an EGP spend):
+        // rather than rejecting it, convert `amount` into the source's
+        // currency and keep both figures on the row.
+        let conversion_rate = entry_currency.rate_to_fixed / source_currency.rate_to_fixed;
+        let source_amount = value.amount * conversion_rate;
+
+        // Categorizable entries with no resolved category first get a
+        // chance to match one of the user's auto-categorization rules
+        // (see `crate::rules`); only entries no rule claims fall back to
+        // the "Uncategorized" bucket instead of a bare NULL, so they're
+        // still reachable via `?uncategorized=true` and the summary count.
+        let category_id = match (value.category_id, value.entry_type) {
+            (None, EntryType::Spend | EntryType::Income) => match matching_rule_category(conn, &value, source_amount)? {
+                Some(category_id) => Some(category_id),
+                None => Some(get_or_create_uncategorized(conn, value.user_id)?.id),
+            },
+            (category_id, _) => category_id,
+        };
+
+        // `conversion_rate_to_fixed` reflects the rate that was actually in
+        // effect on the entry's own date, not today's — otherwise a rate
+        // refresh would silently reshuffle the fixed-currency value of
+        // every past entry the next time it's read.
+        let conversion_rate_to_fixed = rate_effective_on(conn, &entry_currency, value.entry_date.date_naive())?;
+
+        if value.payer_id.is_some() && value.entry_type != EntryType::Income {
+            return Err(AppError::Validation("payer_id is only valid on income entries".into()));
+        }
+
+        Ok(NewEntry {
+            user_id: value.user_id,
+            source_id: value.source_id,
+            secondary_source_id: value.secondary_source_id,
+            category_id,
+            currency_id: value.currency_id,
+            entry_type: value.entry_type,
+            amount: value.amount,
+            source_amount,
+            conversion_rate,
+            conversion_rate_to_fixed,
+            target: value.target,
+            description: value.description,
+            notes: value.notes,
+            entry_date: value.entry_date,
+            created_by: Some(value.user_id),
+            updated_by: Some(value.user_id),
+            counterparty_id: value.counterparty_id,
+            payer_id: value.payer_id,
+        })
+    }
+}
+
+/// Loads `value.user_id`'s rules and returns the `category_id` of the
+/// first one that matches, if any — see [`crate::rules::find_match`].
+fn matching_rule_category(conn: &mut PgConnection, value: &CreateEntryRequest, source_amount: Money) -> Result<Option<i32>, AppError> {
+    use crate::models::rule::Rule;
+    use crate::schema::rules;
+
+    let user_rules = rules::table
+        .filter(rules::user_id.eq(value.user_id))
+        .order((rules::priority.asc(), rules::id.asc()))
+        .select(Rule::as_select())
+        .load::<Rule>(conn)?;
+
+    Ok(crate::rules::find_match(&user_rules, value.description.as_deref(), value.target.as_deref(), source_amount, value.entry_type)
+        .map(|rule| rule.category_id))
+}
@@ -0,0 +1,36 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::money::Money;
+use crate::schema::entry_splits;
+
+/// One category's slice of an [`crate::models::entry::Entry`] whose amount
+/// was allocated across more than one category (e.g. a single grocery
+/// receipt covering both `Food` and `Household`).
+#[derive(Queryable, Identifiable, Selectable, Associations, Serialize, Debug, Clone)]
+#[diesel(table_name = entry_splits)]
+#[diesel(belongs_to(crate::models::entry::Entry, foreign_key = entry_id))]
+#[diesel(belongs_to(crate::models::category::Category, foreign_key = category_id))]
+pub struct EntrySplit {
+    pub id: i32,
+    pub entry_id: i32,
+    pub category_id: i32,
+    pub amount: Money,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = entry_splits)]
+pub struct NewEntrySplit {
+    pub entry_id: i32,
+    pub category_id: i32,
+    pub amount: Money,
+}
+
+/// A single allocation in a [`crate::dto::entry::CreateEntryRequest`]'s
+/// `splits`. Not itself an `Insertable` because `entry_id` isn't known
+/// until the parent entry is inserted.
+#[derive(Deserialize, Debug, Clone)]
+pub struct SplitAllocation {
+    pub category_id: i32,
+    pub amount: Money,
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::schema::login_history;
+
+/// One login attempt, recorded by [`crate::handlers::users::login`] whether
+/// or not the password matched.
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = login_history)]
+pub struct LoginHistoryEntry {
+    pub id: i32,
+    pub user_id: i32,
+    pub ip_address: String,
+    pub user_agent: Option<String>,
+    pub success: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = login_history)]
+pub struct NewLoginHistoryEntry {
+    pub user_id: i32,
+    pub ip_address: String,
+    pub user_agent: Option<String>,
+    pub success: bool,
+}
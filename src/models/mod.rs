@@ -0,0 +1,31 @@
+pub mod alert;
+pub mod attachment;
+pub mod audit_log;
+pub mod bank_connection;
+pub mod bank_transaction;
+pub mod budget;
+pub mod category;
+pub mod counterparty;
+pub mod currency;
+pub mod currency_rate;
+pub mod custom_field;
+pub mod email_ingest;
+pub mod entity_name_history;
+pub mod entry;
+pub mod entry_split;
+pub mod login_history;
+pub mod networth_snapshot;
+pub mod oidc_login_state;
+pub mod password_reset_token;
+pub mod payer;
+pub mod recurring_entry;
+pub mod report_schedule;
+pub mod rule;
+pub mod saved_query;
+pub mod session;
+pub mod share_link;
+pub mod source;
+pub mod sync_mutation;
+pub mod telegram_link;
+pub mod tombstone;
+pub mod user;
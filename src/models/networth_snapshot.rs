@@ -0,0 +1,46 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::money::Money;
+use crate::schema::networth_snapshots;
+
+/// A user's net worth (sum of non-archived source balances, normalized to
+/// their fixed currency) as of a calendar day. Appended to daily by
+/// [`crate::jobs::networth::record_daily_snapshot`] so
+/// `GET /api/networth/history` has a series to chart without recomputing
+/// every past day's source balances on every request.
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = networth_snapshots)]
+pub struct NetworthSnapshot {
+    pub id: i32,
+    pub user_id: i32,
+    pub currency_id: i32,
+    pub amount: Money,
+    pub snapshot_date: NaiveDate,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = networth_snapshots)]
+pub struct NewNetworthSnapshot {
+    pub user_id: i32,
+    pub currency_id: i32,
+    pub amount: Money,
+    pub snapshot_date: NaiveDate,
+}
+
+/// Records today's net worth for `user_id`, overwriting any snapshot
+/// already taken today — re-running the job (e.g. after a retry) updates
+/// today's figure in place instead of leaving a stale duplicate.
+pub fn record_snapshot(conn: &mut PgConnection, snapshot: NewNetworthSnapshot) -> QueryResult<NetworthSnapshot> {
+    diesel::insert_into(networth_snapshots::table)
+        .values(&snapshot)
+        .on_conflict((networth_snapshots::user_id, networth_snapshots::snapshot_date))
+        .do_update()
+        .set((
+            networth_snapshots::currency_id.eq(&snapshot.currency_id),
+            networth_snapshots::amount.eq(&snapshot.amount),
+        ))
+        .get_result::<NetworthSnapshot>(conn)
+}
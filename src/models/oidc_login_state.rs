@@ -0,0 +1,49 @@
+use chrono::{DateTime, Duration, Utc};
+use diesel::prelude::*;
+use rand::RngCore;
+
+use crate::schema::oidc_login_states;
+
+/// A short-lived, single-use CSRF token minted by
+/// [`crate::handlers::oidc::oidc_start`] and checked by
+/// [`crate::handlers::oidc::oidc_callback`], so a forged callback (or a
+/// stale, replayed one) can't complete a login on the caller's behalf.
+#[derive(Queryable, Identifiable, Selectable, Debug, Clone)]
+#[diesel(table_name = oidc_login_states)]
+pub struct OidcLoginState {
+    pub id: i32,
+    pub state: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = oidc_login_states)]
+pub struct NewOidcLoginState {
+    pub state: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+const STATE_TTL_MINUTES: i64 = 10;
+
+/// A random state token; not derived from anything about the caller.
+pub fn generate() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Records a freshly minted state token, valid for [`STATE_TTL_MINUTES`].
+pub fn record(conn: &mut PgConnection, state: &str) -> QueryResult<OidcLoginState> {
+    diesel::insert_into(oidc_login_states::table)
+        .values(&NewOidcLoginState { state: state.to_string(), expires_at: Utc::now() + Duration::minutes(STATE_TTL_MINUTES) })
+        .get_result(conn)
+}
+
+/// Consumes a state token: valid only if it exists, hasn't expired, and
+/// hasn't already been consumed by an earlier callback for the same state.
+pub fn consume(conn: &mut PgConnection, state: &str) -> QueryResult<bool> {
+    let deleted = diesel::delete(oidc_login_states::table.filter(oidc_login_states::state.eq(state)).filter(oidc_login_states::expires_at.gt(Utc::now())))
+        .execute(conn)?;
+    Ok(deleted > 0)
+}
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use rand::RngCore;
+
+use crate::schema::password_reset_tokens;
+
+/// A single-use, expiring link handed out by
+/// `POST /password-reset/request` and consumed by
+/// `POST /password-reset/confirm`.
+#[derive(Queryable, Identifiable, Selectable, Debug, Clone)]
+#[diesel(table_name = password_reset_tokens)]
+pub struct PasswordResetToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = password_reset_tokens)]
+pub struct NewPasswordResetToken {
+    pub user_id: i32,
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A URL-safe random token; not derived from anything about the user, so
+/// leaking one row of this table doesn't help guess another.
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Looks up a token that hasn't been used or expired yet. Used and expired
+/// tokens are left in the table rather than deleted, as a record of the
+/// reset attempt.
+pub fn find_valid(conn: &mut PgConnection, token: &str) -> QueryResult<Option<PasswordResetToken>> {
+    password_reset_tokens::table
+        .filter(password_reset_tokens::token.eq(token))
+        .filter(password_reset_tokens::used_at.is_null())
+        .filter(password_reset_tokens::expires_at.gt(Utc::now()))
+        .select(PasswordResetToken::as_select())
+        .first(conn)
+        .optional()
+}
+
+/// Marks a token consumed so it can't be replayed.
+pub fn mark_used(conn: &mut PgConnection, id: i32) -> QueryResult<usize> {
+    diesel::update(password_reset_tokens::table.find(id))
+        .set(password_reset_tokens::used_at.eq(Utc::now()))
+        .execute(conn)
+}
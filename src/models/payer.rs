@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::payers;
+
+/// Who paid an [`crate::models::entry::EntryType::Income`] entry — an
+/// employer or client. Kept separate from
+/// [`crate::models::counterparty::Counterparty`] (who a `Spend` was paid
+/// to) rather than merging the two: the two concepts are reported on
+/// differently and freelancers in particular want their client list
+/// distinct from their vendor list.
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = payers)]
+pub struct Payer {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Deserialize, Debug)]
+#[diesel(table_name = payers)]
+pub struct NewPayer {
+    pub user_id: i32,
+    pub name: String,
+}
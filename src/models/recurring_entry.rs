@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::models::entry::EntryType;
+use crate::money::Money;
+use crate::schema::recurring_entries;
+
+/// A template for an entry that should be materialized on a fixed cadence
+/// (rent, a subscription, salary). The scheduler in [`crate::jobs::recurring`]
+/// inserts a real [`crate::models::entry::Entry`] each time `next_run_at`
+/// elapses and advances it by `interval_days`.
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = recurring_entries)]
+pub struct RecurringEntry {
+    pub id: i32,
+    pub user_id: i32,
+    pub source_id: i32,
+    pub category_id: Option<i32>,
+    pub currency_id: i32,
+    pub entry_type: EntryType,
+    pub amount: Money,
+    pub target: Option<String>,
+    pub description: Option<String>,
+    pub interval_days: i32,
+    pub next_run_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Deserialize, Debug)]
+#[diesel(table_name = recurring_entries)]
+pub struct NewRecurringEntry {
+    pub user_id: i32,
+    pub source_id: i32,
+    pub category_id: Option<i32>,
+    pub currency_id: i32,
+    pub entry_type: EntryType,
+    pub amount: Money,
+    pub target: Option<String>,
+    pub description: Option<String>,
+    pub interval_days: i32,
+    pub next_run_at: DateTime<Utc>,
+}
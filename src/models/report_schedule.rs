@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::prelude::*;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::report_schedules;
+
+/// Which report to render and email. Only `Monthly` is wired up in
+/// [`crate::jobs::report_schedules`] today; the field exists so more
+/// report types can be added without a schema change.
+#[derive(AsExpression, FromSqlRow, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[diesel(sql_type = Text)]
+pub enum ReportType {
+    Monthly,
+}
+
+impl<DB: Backend> ToSql<Text, DB> for ReportType
+where
+    str: ToSql<Text, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        match self {
+            ReportType::Monthly => "monthly",
+        }
+        .to_sql(out)
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for ReportType
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        match String::from_sql(bytes)?.as_str() {
+            "monthly" => Ok(ReportType::Monthly),
+            other => Err(format!("unrecognized report type: {other}").into()),
+        }
+    }
+}
+
+/// How often [`crate::jobs::report_schedules::run_due`] re-sends the
+/// report, same shape as [`crate::models::budget::BudgetPeriod`].
+#[derive(AsExpression, FromSqlRow, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[diesel(sql_type = Text)]
+pub enum ReportCadence {
+    Weekly,
+    Monthly,
+}
+
+impl<DB: Backend> ToSql<Text, DB> for ReportCadence
+where
+    str: ToSql<Text, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        match self {
+            ReportCadence::Weekly => "weekly",
+            ReportCadence::Monthly => "monthly",
+        }
+        .to_sql(out)
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for ReportCadence
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        match String::from_sql(bytes)?.as_str() {
+            "weekly" => Ok(ReportCadence::Weekly),
+            "monthly" => Ok(ReportCadence::Monthly),
+            other => Err(format!("unrecognized report cadence: {other}").into()),
+        }
+    }
+}
+
+/// A standing request to email a rendered report to `email` on a
+/// recurring `cadence`, so a user can get a statement in their inbox
+/// without logging in — see [`crate::jobs::report_schedules`].
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = report_schedules)]
+pub struct ReportSchedule {
+    pub id: i32,
+    pub user_id: i32,
+    pub report_type: ReportType,
+    pub cadence: ReportCadence,
+    pub email: String,
+    pub next_run_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Deserialize, Debug)]
+#[diesel(table_name = report_schedules)]
+pub struct NewReportSchedule {
+    pub user_id: i32,
+    pub report_type: ReportType,
+    pub cadence: ReportCadence,
+    pub email: String,
+    pub next_run_at: DateTime<Utc>,
+}
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::models::entry::EntryType;
+use crate::money::Money;
+use crate::schema::rules;
+
+/// An auto-categorization rule: an entry matching every `Some` criterion
+/// here gets `category_id` applied; `source_id` is stored with the rule
+/// but is not applied to matching entries. Rules are
+/// evaluated in `priority` order (lowest first, ties broken by `id`) and
+/// the first match wins — see [`crate::rules::find_match`].
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = rules)]
+pub struct Rule {
+    pub id: i32,
+    pub user_id: i32,
+    /// Substring to look for in `entries.description` (case-insensitive).
+    pub description_contains: Option<String>,
+    /// Substring to look for in `entries.target` (case-insensitive).
+    pub target_contains: Option<String>,
+    pub min_amount: Option<Money>,
+    pub max_amount: Option<Money>,
+    /// Restricts the rule to entries of this type; `None` matches any.
+    pub entry_type: Option<EntryType>,
+    pub category_id: i32,
+    pub source_id: Option<i32>,
+    pub priority: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Deserialize, Debug)]
+#[diesel(table_name = rules)]
+pub struct NewRule {
+    pub user_id: i32,
+    pub description_contains: Option<String>,
+    pub target_contains: Option<String>,
+    pub min_amount: Option<Money>,
+    pub max_amount: Option<Money>,
+    pub entry_type: Option<EntryType>,
+    pub category_id: i32,
+    pub source_id: Option<i32>,
+    #[serde(default)]
+    pub priority: i32,
+}
@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::prelude::*;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use serde::{Deserialize, Serialize};
+
+use crate::models::entry::EntryType;
+use crate::schema::saved_queries;
+
+/// How [`crate::handlers::saved_queries::run_saved_query`] aggregates the
+/// filtered entries, same shape as [`crate::models::budget::BudgetPeriod`].
+/// `None` (the query's `group_by` column being unset) returns the raw
+/// entries instead, same as [`crate::handlers::share::get_shared`].
+#[derive(AsExpression, FromSqlRow, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[diesel(sql_type = Text)]
+pub enum GroupBy {
+    Category,
+    Month,
+}
+
+impl<DB: Backend> ToSql<Text, DB> for GroupBy
+where
+    str: ToSql<Text, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        match self {
+            GroupBy::Category => "category",
+            GroupBy::Month => "month",
+        }
+        .to_sql(out)
+    }
+}
+
+impl<DB: Backend> FromSql<Text, DB> for GroupBy
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        match String::from_sql(bytes)?.as_str() {
+            "category" => Ok(GroupBy::Category),
+            "month" => Ok(GroupBy::Month),
+            other => Err(format!("unrecognized group_by: {other}").into()),
+        }
+    }
+}
+
+/// A named [`crate::handlers::entries::FindEntriesQuery`]-style filter the
+/// user doesn't want to re-type — "Groceries this year in EUR" saved once,
+/// re-run any time via `/api/saved-query/{name}/run`. Filter shape mirrors
+/// [`crate::models::share_link::ShareLink`], the other place entry filters
+/// are persisted.
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = saved_queries)]
+pub struct SavedQuery {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub category_id: Option<i32>,
+    pub source_id: Option<i32>,
+    pub entry_type: Option<EntryType>,
+    pub date_from: Option<DateTime<Utc>>,
+    pub date_to: Option<DateTime<Utc>>,
+    pub display_currency: Option<String>,
+    pub group_by: Option<GroupBy>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Deserialize, Debug)]
+#[diesel(table_name = saved_queries)]
+pub struct NewSavedQuery {
+    pub user_id: i32,
+    pub name: String,
+    pub category_id: Option<i32>,
+    pub source_id: Option<i32>,
+    pub entry_type: Option<EntryType>,
+    pub date_from: Option<DateTime<Utc>>,
+    pub date_to: Option<DateTime<Utc>>,
+    pub display_currency: Option<String>,
+    pub group_by: Option<GroupBy>,
+}
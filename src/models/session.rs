@@ -0,0 +1,102 @@
+use chrono::{DateTime, Duration, Utc};
+use diesel::prelude::*;
+use rand::RngCore;
+
+use crate::schema::sessions;
+
+/// An opaque bearer token issued by [`crate::handlers::users::login`] and
+/// checked by [`crate::auth::AuthUser`]. Not a JWT: nothing here needs
+/// offline/stateless verification, so a DB-backed token plus a
+/// `revoked_at` column gives `POST /logout` a place to write without
+/// needing a signed-claims format and a separate denylist to keep in sync
+/// with it.
+#[derive(Queryable, Identifiable, Selectable, Debug, Clone)]
+#[diesel(table_name = sessions)]
+pub struct Session {
+    pub id: i32,
+    pub user_id: i32,
+    pub token: String,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub device_name: Option<String>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub last_used_ip: Option<String>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = sessions)]
+pub struct NewSession {
+    pub user_id: i32,
+    pub token: String,
+    pub device_name: Option<String>,
+}
+
+/// A random bearer token; not derived from anything about the user.
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Looks up a still-valid session by its bearer token: unrevoked, and
+/// issued within the last `ttl_minutes` (see `AppConfig::session_ttl_minutes`).
+/// A session past its TTL is treated the same as a revoked one rather than
+/// deleted outright, so `GET /api/me/sessions` still shows it as
+/// "expired" history instead of it vanishing.
+pub fn find_active(conn: &mut PgConnection, token: &str, ttl_minutes: i64) -> QueryResult<Option<Session>> {
+    sessions::table
+        .filter(sessions::token.eq(token))
+        .filter(sessions::revoked_at.is_null())
+        .filter(sessions::created_at.gt(Utc::now() - Duration::minutes(ttl_minutes)))
+        .select(Session::as_select())
+        .first(conn)
+        .optional()
+}
+
+/// Lists every session a user has ever been issued, most recent first, for
+/// `GET /api/me/sessions` — including revoked ones, so a user can see that
+/// an old device was in fact logged out and not just silently expired.
+pub fn list_for_user(conn: &mut PgConnection, user_id: i32) -> QueryResult<Vec<Session>> {
+    sessions::table
+        .filter(sessions::user_id.eq(user_id))
+        .order(sessions::created_at.desc())
+        .select(Session::as_select())
+        .load(conn)
+}
+
+/// Stamps a session as having just been used, so `GET /api/me/sessions` can
+/// show "last used" instead of just "created". Called from
+/// [`crate::auth::AuthUser`] on every bearer-authenticated request, so this
+/// is deliberately a single cheap `UPDATE` rather than anything that reads
+/// the row back.
+pub fn touch(conn: &mut PgConnection, session_id: i32, ip_address: &str) -> QueryResult<usize> {
+    diesel::update(sessions::table.find(session_id))
+        .set((sessions::last_used_at.eq(Utc::now()), sessions::last_used_ip.eq(ip_address)))
+        .execute(conn)
+}
+
+/// Revokes a session so a future request bearing its token is rejected by
+/// [`crate::auth::AuthUser`], even though the token itself doesn't expire
+/// on its own. A no-op (zero rows updated) if the token doesn't exist or
+/// was already revoked, since `POST /logout` should behave the same either
+/// way from the caller's perspective.
+pub fn revoke(conn: &mut PgConnection, token: &str) -> QueryResult<usize> {
+    diesel::update(sessions::table.filter(sessions::token.eq(token)).filter(sessions::revoked_at.is_null()))
+        .set(sessions::revoked_at.eq(Utc::now()))
+        .execute(conn)
+}
+
+/// Revokes a specific session by id, scoped to `user_id` so one user can't
+/// revoke another's session by guessing an id — the natural check for
+/// `DELETE /api/me/sessions/{id}` since the id itself carries no ownership
+/// information the way a token lookup does.
+pub fn revoke_for_user(conn: &mut PgConnection, user_id: i32, session_id: i32) -> QueryResult<usize> {
+    diesel::update(
+        sessions::table
+            .filter(sessions::id.eq(session_id))
+            .filter(sessions::user_id.eq(user_id))
+            .filter(sessions::revoked_at.is_null()),
+    )
+    .set(sessions::revoked_at.eq(Utc::now()))
+    .execute(conn)
+}
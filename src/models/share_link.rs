@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use rand::RngCore;
+
+use crate::models::entry::EntryType;
+use crate::schema::share_links;
+
+/// An unauthenticated, expiring view onto a filtered slice of one user's
+/// entries — created by `POST /api/share` and read by `GET /shared/{token}`
+/// so a user can show a report to a partner or accountant without handing
+/// over their password or a session token. The filter fields mirror
+/// [`crate::handlers::entries::FindEntriesQuery`]'s shape rather than a
+/// serialized blob, same as every other table in this schema stores its
+/// data in typed columns instead of JSON.
+#[derive(Queryable, Identifiable, Selectable, Debug, Clone)]
+#[diesel(table_name = share_links)]
+pub struct ShareLink {
+    pub id: i32,
+    pub user_id: i32,
+    pub token: String,
+    pub category_id: Option<i32>,
+    pub source_id: Option<i32>,
+    pub entry_type: Option<EntryType>,
+    pub date_from: Option<DateTime<Utc>>,
+    pub date_to: Option<DateTime<Utc>>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = share_links)]
+pub struct NewShareLink {
+    pub user_id: i32,
+    pub token: String,
+    pub category_id: Option<i32>,
+    pub source_id: Option<i32>,
+    pub entry_type: Option<EntryType>,
+    pub date_from: Option<DateTime<Utc>>,
+    pub date_to: Option<DateTime<Utc>>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A URL-safe random token; not derived from anything about the user or
+/// the filter, same reasoning as [`crate::models::session::generate_token`].
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Looks up a share link that hasn't expired yet. Expired links are left
+/// in the table rather than deleted, same as expired password-reset
+/// tokens, as a record of what was shared and when.
+pub fn find_active(conn: &mut PgConnection, token: &str) -> QueryResult<Option<ShareLink>> {
+    share_links::table
+        .filter(share_links::token.eq(token))
+        .filter(share_links::expires_at.gt(Utc::now()))
+        .select(ShareLink::as_select())
+        .first(conn)
+        .optional()
+}
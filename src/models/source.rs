@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::money::Money;
+use crate::schema::sources;
+
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = sources)]
+pub struct Source {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub currency_id: i32,
+    pub amount: Money,
+    pub last_reconciled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub archived: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = sources)]
+pub struct NewSource {
+    pub user_id: i32,
+    pub name: String,
+    pub currency_id: i32,
+    pub amount: Money,
+}
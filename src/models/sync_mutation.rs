@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+
+use crate::schema::sync_client_mutations;
+
+pub const ENTRY: &str = "entry";
+pub const SOURCE_AMOUNT: &str = "source_amount";
+
+/// Records that a client-supplied `client_id` (from a `POST /api/changes`
+/// mutation) was already applied, so a retried submission — the normal
+/// case for an offline client that never saw the first response — is
+/// recognized and answered with the original result instead of creating a
+/// duplicate row. This is the "merge strategy" for create-only entities
+/// like [`crate::models::entry::Entry`]: since there's nothing to merge
+/// field-by-field, idempotency by `client_id` is the whole strategy.
+#[derive(Queryable, Identifiable, Selectable, Debug, Clone)]
+#[diesel(table_name = sync_client_mutations)]
+pub struct SyncClientMutation {
+    pub id: i32,
+    pub client_id: String,
+    pub entity_type: String,
+    pub entity_id: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = sync_client_mutations)]
+pub struct NewSyncClientMutation {
+    pub client_id: String,
+    pub entity_type: String,
+    pub entity_id: i32,
+}
+
+/// Looks up a previously-applied mutation by `client_id`, if any.
+pub fn find_by_client_id(conn: &mut PgConnection, client_id: &str) -> QueryResult<Option<SyncClientMutation>> {
+    sync_client_mutations::table
+        .filter(sync_client_mutations::client_id.eq(client_id))
+        .select(SyncClientMutation::as_select())
+        .first(conn)
+        .optional()
+}
+
+/// Records a freshly-applied mutation so future retries of the same
+/// `client_id` are recognized as duplicates.
+pub fn record(conn: &mut PgConnection, client_id: &str, entity_type: &str, entity_id: i32) -> QueryResult<SyncClientMutation> {
+    diesel::insert_into(sync_client_mutations::table)
+        .values(&NewSyncClientMutation {
+            client_id: client_id.to_string(),
+            entity_type: entity_type.to_string(),
+            entity_id,
+        })
+        .get_result(conn)
+}
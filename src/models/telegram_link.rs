@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::schema::telegram_links;
+
+/// Links a Telegram chat to an account: a user generates a `link_code`
+/// in-app, then sends `/link <code>` to the bot, which fills in `chat_id`
+/// and `linked_at` — see [`crate::telegram`].
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = telegram_links)]
+pub struct TelegramLink {
+    pub id: i32,
+    pub user_id: i32,
+    pub link_code: String,
+    pub chat_id: Option<i64>,
+    pub linked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = telegram_links)]
+pub struct NewTelegramLink {
+    pub user_id: i32,
+    pub link_code: String,
+}
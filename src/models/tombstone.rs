@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::schema::tombstones;
+
+/// Which table a [`Tombstone`] refers to. Only entities with a hard-delete
+/// endpoint need one — most of this codebase prefers `archived` flags
+/// (see [`crate::models::currency`], [`crate::models::source`]), which
+/// don't need tombstoning since the row survives.
+pub const ALERT: &str = "alert";
+pub const ATTACHMENT: &str = "attachment";
+pub const BANK_CONNECTION: &str = "bank_connection";
+pub const RULE: &str = "rule";
+
+/// Marks that an entity was hard-deleted, for `GET /api/changes?since=` to
+/// hand offline clients so they can drop their local copy instead of
+/// re-syncing it forever.
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = tombstones)]
+pub struct Tombstone {
+    pub id: i32,
+    pub entity_type: String,
+    pub entity_id: i32,
+    pub deleted_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = tombstones)]
+pub struct NewTombstone {
+    pub entity_type: String,
+    pub entity_id: i32,
+}
+
+pub fn record_deletion(conn: &mut PgConnection, entity_type: &str, entity_id: i32) -> QueryResult<Tombstone> {
+    diesel::insert_into(tombstones::table)
+        .values(&NewTombstone { entity_type: entity_type.to_string(), entity_id })
+        .get_result::<Tombstone>(conn)
+}
@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::schema::users;
+
+#[derive(Queryable, Identifiable, Selectable, Serialize, Debug, Clone)]
+#[diesel(table_name = users)]
+pub struct User {
+    pub id: i32,
+    pub email: String,
+    #[serde(skip_serializing)]
+    pub password_hash: String,
+    /// When set, `entries.description` / `entries.notes` are stored as
+    /// AES-256-GCM blobs (see [`crate::crypto`]) instead of plain text, and
+    /// only decrypted back for a request carrying [`crate::crypto::UNLOCK_HEADER`].
+    pub privacy_mode: bool,
+    #[serde(skip_serializing)]
+    pub privacy_salt: Option<Vec<u8>>,
+    pub created_at: DateTime<Utc>,
+    /// The user's preferred reference currency, changed via
+    /// `POST /api/me/fixed-currency` (see
+    /// [`crate::jobs::fixed_currency::change_fixed_currency`]). `None`
+    /// until they've set one.
+    pub fixed_currency_id: Option<i32>,
+    /// A disabled account still exists, so its entries and sources remain
+    /// visible to reports, but it's rejected at login (see
+    /// [`crate::handlers::users::login`]) until an admin re-enables it via
+    /// `PATCH /api/admin/users/{id}`.
+    pub disabled: bool,
+    /// Grants access to `/api/admin/*` routes (see
+    /// [`crate::auth::AdminUser`]). Nothing in this API can set its own
+    /// `is_admin` to `true` — the first admin has to be flipped directly in
+    /// the database, same as `disabled` before this.
+    pub is_admin: bool,
+    /// The provider-issued `sub` claim for a user who logged in (or was
+    /// created) via [`crate::handlers::oidc::oidc_callback`]. `None` for
+    /// every account created through `password`-based `register`/`login`.
+    /// Kept separate from `email` for matching an OIDC identity back to a
+    /// local user, since an IdP's `email` can change but its `sub` won't.
+    #[serde(skip_serializing)]
+    pub oidc_subject: Option<String>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = users)]
+pub struct NewUser {
+    pub email: String,
+    pub password_hash: String,
+    pub oidc_subject: Option<String>,
+}
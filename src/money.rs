@@ -0,0 +1,169 @@
+//! [`Money`]: a fixed-point replacement for `f64` on ledger amounts.
+//!
+//! `f64` amounts required an `EPSILON`-style tolerance wherever they were
+//! compared for equality (e.g. validating that entry splits sum to the
+//! parent's `amount`) because binary floating point can't represent most
+//! decimal amounts exactly. `Money` wraps [`rust_decimal::Decimal`],
+//! stored as `NUMERIC` in Postgres, so those comparisons are exact and the
+//! tolerance hack goes away. It serializes to JSON as a string (not a
+//! number) so clients never round-trip it through an IEEE-754 double.
+//!
+//! Exchange rates (`rate_to_fixed`, `conversion_rate*`) are left as `f64`:
+//! they're ratios, not money, and aren't compared for exact equality
+//! anywhere.
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Numeric;
+use rust_decimal::Decimal;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(AsExpression, FromSqlRow, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[diesel(sql_type = Numeric)]
+pub struct Money(pub Decimal);
+
+impl Money {
+    pub const ZERO: Money = Money(Decimal::ZERO);
+
+    pub fn abs(self) -> Money {
+        Money(self.0.abs())
+    }
+
+    /// Lossy: only for display-layer aggregates (reports, stats) that
+    /// were computed in `f64` before this migration and haven't been
+    /// converted yet. Never use this on a value that's about to be
+    /// persisted.
+    pub fn to_f64_lossy(self) -> f64 {
+        use rust_decimal::prelude::ToPrimitive;
+        self.0.to_f64().unwrap_or(0.0)
+    }
+
+    pub fn from_f64_lossy(value: f64) -> Money {
+        Money(Decimal::from_str(&value.to_string()).unwrap_or_default())
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+    fn add(self, rhs: Money) -> Money {
+        Money(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, rhs: Money) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+    fn sub(self, rhs: Money) -> Money {
+        Money(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, rhs: Money) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+/// Converts an entry's `amount` into its source's currency, or applies an
+/// exchange rate to a running balance — both still expressed as `f64`
+/// ratios (see the module doc comment).
+impl std::ops::Mul<f64> for Money {
+    type Output = Money;
+    fn mul(self, rate: f64) -> Money {
+        Money(self.0 * Decimal::from_str(&rate.to_string()).unwrap_or_default())
+    }
+}
+
+impl std::ops::Div<f64> for Money {
+    type Output = Money;
+    fn div(self, rate: f64) -> Money {
+        Money(self.0 / Decimal::from_str(&rate.to_string()).unwrap_or(Decimal::ONE))
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+struct MoneyVisitor;
+
+impl<'de> Visitor<'de> for MoneyVisitor {
+    type Value = Money;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a decimal amount, as a string or number")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Money, E> {
+        Decimal::from_str(value).map(Money).map_err(de::Error::custom)
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Money, E> {
+        Ok(Money::from_f64_lossy(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Money, E> {
+        Ok(Money(Decimal::from(value)))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Money, E> {
+        Ok(Money(Decimal::from(value)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Money, D::Error> {
+        deserializer.deserialize_any(MoneyVisitor)
+    }
+}
+
+impl<DB: Backend> ToSql<Numeric, DB> for Money
+where
+    Decimal: ToSql<Numeric, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        self.0.to_sql(out)
+    }
+}
+
+impl<DB: Backend> FromSql<Numeric, DB> for Money
+where
+    Decimal: FromSql<Numeric, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        Decimal::from_sql(bytes).map(Money)
+    }
+}
@@ -0,0 +1,106 @@
+//! A minimal OpenID Connect authorization-code flow, so a self-hosted
+//! install can delegate login to an external provider (Authelia, Keycloak,
+//! ...) instead of maintaining a second password store. Hand-rolled
+//! against the provider's discovery document plus `reqwest::blocking`
+//! (already a dependency for [`crate::jobs::exchange_rates::HttpRateProvider`])
+//! rather than an OIDC client crate, since this workspace has no
+//! `Cargo.toml` to add one to. Only the pieces `oidc_start`/`oidc_callback`
+//! actually need are implemented — no ID token signature verification, no
+//! refresh tokens; the access token is used once to call `userinfo` and
+//! then discarded, same as this codebase never persists a provider's rate
+//! data beyond what it needs for `currencies.rate_to_fixed`.
+
+use serde::Deserialize;
+
+use crate::config::AppConfig;
+
+/// Percent-encodes a query-string value. Hand-rolled rather than pulling in
+/// a URL-encoding crate, same reasoning as [`crate::scanning::ClamdScanner`]
+/// hand-rolling the clamd wire protocol instead of adding a dependency.
+fn percent_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(byte as char),
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+#[derive(Deserialize)]
+pub struct DiscoveryDocument {
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub userinfo_endpoint: String,
+}
+
+/// Fetches `{issuer}/.well-known/openid-configuration`. Not cached: this
+/// only runs on the two low-frequency OIDC endpoints, not a hot path.
+pub fn discover(issuer: &str) -> Result<DiscoveryDocument, String> {
+    reqwest::blocking::get(format!("{issuer}/.well-known/openid-configuration"))
+        .map_err(|e| e.to_string())?
+        .json::<DiscoveryDocument>()
+        .map_err(|e| e.to_string())
+}
+
+/// Builds the URL `oidc_start` redirects the browser to.
+pub fn authorization_url(discovery: &DiscoveryDocument, config: &AppConfig, state: &str) -> String {
+    format!(
+        "{}?response_type=code&client_id={}&redirect_uri={}&scope=openid%20email&state={}",
+        discovery.authorization_endpoint,
+        percent_encode(&config.oidc_client_id),
+        percent_encode(&config.oidc_redirect_url),
+        percent_encode(state),
+    )
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+/// Exchanges the authorization `code` from the callback for an access
+/// token.
+pub fn exchange_code(discovery: &DiscoveryDocument, config: &AppConfig, code: &str) -> Result<String, String> {
+    let response: TokenResponse = reqwest::blocking::Client::new()
+        .post(&discovery.token_endpoint)
+        .form(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", &config.oidc_redirect_url),
+            ("client_id", &config.oidc_client_id),
+            ("client_secret", &config.oidc_client_secret),
+        ])
+        .send()
+        .map_err(|e| e.to_string())?
+        .json()
+        .map_err(|e| e.to_string())?;
+
+    Ok(response.access_token)
+}
+
+#[derive(Deserialize)]
+pub struct OidcUserInfo {
+    pub sub: String,
+    pub email: Option<String>,
+    /// Must be checked before linking `sub` onto an existing account by
+    /// `email` below — an unverified email is just a claim the provider is
+    /// relaying, not proof of ownership. Defaults to `false` so a provider
+    /// that omits the claim entirely is treated as unverified rather than
+    /// silently trusted.
+    #[serde(default)]
+    pub email_verified: bool,
+}
+
+/// Calls `userinfo_endpoint` with the access token from [`exchange_code`]
+/// to learn who just logged in.
+pub fn fetch_userinfo(discovery: &DiscoveryDocument, access_token: &str) -> Result<OidcUserInfo, String> {
+    reqwest::blocking::Client::new()
+        .get(&discovery.userinfo_endpoint)
+        .bearer_auth(access_token)
+        .send()
+        .map_err(|e| e.to_string())?
+        .json::<OidcUserInfo>()
+        .map_err(|e| e.to_string())
+}
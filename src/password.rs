@@ -0,0 +1,159 @@
+//! Password hashing and verification. Separate from [`crate::crypto`] even
+//! though both lean on similar primitives today — one authenticates a
+//! login, the other derives an at-rest encryption key, and they are free to
+//! diverge without touching each other.
+//!
+//! Argon2id is the only scheme [`hash`] produces, but [`verify`] still
+//! accepts the older PBKDF2-HMAC-SHA256 hashes this module used to write
+//! (recognizable by *not* starting with `$argon2`), and reports a fresh
+//! Argon2id hash back to the caller whenever one of those verifies
+//! successfully — see [`VerifyOutcome::rehashed`] — so the user base
+//! migrates one successful login at a time instead of needing a bulk
+//! rehash job or a forced password reset.
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use pbkdf2::pbkdf2_hmac;
+use rand::RngCore;
+use sha2::Sha256;
+use tokio::sync::Semaphore;
+
+use crate::error::AppError;
+
+const PBKDF2_ROUNDS: u32 = 100_000;
+const PBKDF2_SALT_LEN: usize = 16;
+const PBKDF2_HASH_LEN: usize = 32;
+
+/// Bounds how many hash/verify operations run at once, independent of
+/// however large the runtime's own blocking thread pool is. Argon2id (like
+/// PBKDF2 before it) is deliberately expensive; without this, a burst of
+/// concurrent login attempts could occupy every blocking thread the rest of
+/// the app also relies on (attachment uploads, CSV/JSON exports).
+static WORKER_PERMITS: std::sync::OnceLock<Semaphore> = std::sync::OnceLock::new();
+
+fn worker_permits() -> &'static Semaphore {
+    WORKER_PERMITS.get_or_init(|| Semaphore::new(worker_pool_size()))
+}
+
+fn worker_pool_size() -> usize {
+    std::env::var("PASSWORD_WORKER_CONCURRENCY")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(4)
+}
+
+/// Argon2id cost parameters, tunable per deployment since the right
+/// memory/time tradeoff depends on hardware the app doesn't know about.
+/// Defaults follow OWASP's current minimum recommendation (19 MiB, 2
+/// iterations, 1 lane).
+fn argon2_params() -> Params {
+    let memory_kib = std::env::var("ARGON2_MEMORY_KIB").ok().and_then(|v| v.parse().ok()).unwrap_or(19_456);
+    let iterations = std::env::var("ARGON2_ITERATIONS").ok().and_then(|v| v.parse().ok()).unwrap_or(2);
+    let parallelism = std::env::var("ARGON2_PARALLELISM").ok().and_then(|v| v.parse().ok()).unwrap_or(1);
+    Params::new(memory_kib, iterations, parallelism, None).unwrap_or_default()
+}
+
+/// `pub(crate)` so [`crate::crypto::derive_key`] can derive its at-rest
+/// encryption key under the same Argon2id cost parameters this module
+/// verifies logins against, rather than rolling its own.
+pub(crate) fn argon2() -> Argon2<'static> {
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, argon2_params())
+}
+
+/// Hashes `password` under freshly generated Argon2id parameters,
+/// returning a self-describing PHC string (`$argon2id$v=19$...`) suitable
+/// for `users.password_hash`.
+pub fn hash(password: &str) -> String {
+    let salt = SaltString::generate(&mut rand::rngs::OsRng);
+    argon2()
+        .hash_password(password.as_bytes(), &salt)
+        .expect("argon2id hashing does not fail for well-formed input")
+        .to_string()
+}
+
+fn verify_argon2(password: &str, stored_hash: &str) -> bool {
+    let Ok(parsed) = PasswordHash::new(stored_hash) else {
+        return false;
+    };
+    Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok()
+}
+
+/// Verifies against the hex-encoded `salt || derived_key` format this
+/// module wrote before Argon2id, kept only so accounts created before the
+/// migration can still log in (and get transparently rehashed — see
+/// [`verify`]).
+fn verify_pbkdf2(password: &str, stored_hash: &str) -> bool {
+    let Ok(bytes) = hex::decode(stored_hash) else {
+        return false;
+    };
+    if bytes.len() != PBKDF2_SALT_LEN + PBKDF2_HASH_LEN {
+        return false;
+    }
+    let (salt, expected) = bytes.split_at(PBKDF2_SALT_LEN);
+
+    let mut derived = [0u8; PBKDF2_HASH_LEN];
+    pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, PBKDF2_ROUNDS, &mut derived);
+
+    constant_time_eq(&derived, expected)
+}
+
+/// Avoids a data-dependent early return that would leak how many leading
+/// bytes of the derived key matched.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn is_legacy_pbkdf2(stored_hash: &str) -> bool {
+    !stored_hash.starts_with("$argon2")
+}
+
+fn verify_sync(password: &str, stored_hash: &str) -> bool {
+    if is_legacy_pbkdf2(stored_hash) {
+        verify_pbkdf2(password, stored_hash)
+    } else {
+        verify_argon2(password, stored_hash)
+    }
+}
+
+/// The result of [`verify`]: whether the password matched, and — if it
+/// matched against a legacy PBKDF2 hash — a freshly computed Argon2id hash
+/// of the same password for the caller to persist in its place.
+pub struct VerifyOutcome {
+    pub valid: bool,
+    pub rehashed: Option<String>,
+}
+
+const MIN_PASSWORD_LEN: usize = 8;
+
+/// Rejects passwords too weak to bother hashing: shorter than
+/// [`MIN_PASSWORD_LEN`], or missing either a letter or a digit. Deliberately
+/// not a full entropy estimator — just enough to stop `"password"` and
+/// `"12345678"` at the door on account creation.
+pub fn validate_strength(password: &str) -> Result<(), AppError> {
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        return Err(AppError::Validation(format!("password must be at least {MIN_PASSWORD_LEN} characters")));
+    }
+    if !password.chars().any(|c| c.is_ascii_alphabetic()) || !password.chars().any(|c| c.is_ascii_digit()) {
+        return Err(AppError::Validation("password must contain at least one letter and one digit".into()));
+    }
+    Ok(())
+}
+
+/// Verifies `password` against `stored_hash` on the bounded worker pool
+/// above rather than the caller's async task, so `login` doesn't stall the
+/// executor thread it runs on.
+pub async fn verify(password: String, stored_hash: String) -> Result<VerifyOutcome, AppError> {
+    let _permit = worker_permits()
+        .acquire()
+        .await
+        .map_err(|_| AppError::Internal("password worker pool closed".into()))?;
+
+    actix_web::web::block(move || {
+        let valid = verify_sync(&password, &stored_hash);
+        let rehashed = (valid && is_legacy_pbkdf2(&stored_hash)).then(|| hash(&password));
+        VerifyOutcome { valid, rehashed }
+    })
+    .await
+    .map_err(|_| AppError::Internal("password verification worker panicked".into()))
+}
@@ -0,0 +1,55 @@
+//! Matches auto-categorization [`crate::models::rule::Rule`]s against an
+//! entry's description/target/amount/type. Pure Rust predicate matching
+//! over an already-loaded rule list (same approach
+//! [`crate::handlers::entries::matches_custom_filters`] takes for
+//! `custom.*` filters) rather than pushing the match into SQL — the rule
+//! set per user is small and this runs on every entry creation.
+
+use crate::models::entry::EntryType;
+use crate::models::rule::Rule;
+use crate::money::Money;
+
+/// Finds the first (lowest `priority`, then lowest `id`) rule in `rules`
+/// that matches the given entry fields. Callers are expected to have
+/// already loaded `rules` ordered that way (see
+/// [`crate::handlers::rules::list_rules`]).
+pub fn find_match<'a>(
+    rules: &'a [Rule],
+    description: Option<&str>,
+    target: Option<&str>,
+    amount: Money,
+    entry_type: EntryType,
+) -> Option<&'a Rule> {
+    rules.iter().find(|rule| matches(rule, description, target, amount, entry_type))
+}
+
+fn matches(rule: &Rule, description: Option<&str>, target: Option<&str>, amount: Money, entry_type: EntryType) -> bool {
+    if let Some(needle) = &rule.description_contains {
+        let Some(haystack) = description else { return false };
+        if !haystack.to_lowercase().contains(&needle.to_lowercase()) {
+            return false;
+        }
+    }
+    if let Some(needle) = &rule.target_contains {
+        let Some(haystack) = target else { return false };
+        if !haystack.to_lowercase().contains(&needle.to_lowercase()) {
+            return false;
+        }
+    }
+    if let Some(min_amount) = rule.min_amount {
+        if amount < min_amount {
+            return false;
+        }
+    }
+    if let Some(max_amount) = rule.max_amount {
+        if amount > max_amount {
+            return false;
+        }
+    }
+    if let Some(required_type) = rule.entry_type {
+        if required_type != entry_type {
+            return false;
+        }
+    }
+    true
+}
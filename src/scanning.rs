@@ -0,0 +1,60 @@
+//! Scans an uploaded attachment for malware through a pluggable
+//! [`FileScanner`], mirroring how [`crate::mail::Mailer`] abstracts SMTP so
+//! the upload path isn't tied to a live clamd instance.
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+pub trait FileScanner: Send + Sync {
+    /// Returns `Ok(())` if `bytes` is clean, or `Err` describing why it was
+    /// rejected (the scanner's verdict, not a transport failure — a
+    /// scanner that can't be reached should itself decide whether that's
+    /// fail-open or fail-closed).
+    fn scan(&self, bytes: &[u8]) -> Result<(), String>;
+}
+
+/// Used when `AppConfig::clamd_address` is unset — every upload passes
+/// through unscanned, so attachments stay usable in dev/demo environments
+/// without a real clamd instance configured, same as [`crate::mail::LoggingMailer`].
+pub struct NoopScanner;
+
+impl FileScanner for NoopScanner {
+    fn scan(&self, _bytes: &[u8]) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Talks to a `clamd` daemon over its `INSTREAM` protocol: a stream of
+/// 4-byte-big-endian-length-prefixed chunks terminated by a zero-length
+/// chunk, replied to with a line containing `OK` or `FOUND`.
+pub struct ClamdScanner {
+    pub address: String,
+    pub timeout: Duration,
+}
+
+impl FileScanner for ClamdScanner {
+    fn scan(&self, bytes: &[u8]) -> Result<(), String> {
+        let mut stream = TcpStream::connect(&self.address).map_err(|e| format!("could not reach clamd at {}: {e}", self.address))?;
+        stream.set_read_timeout(Some(self.timeout)).map_err(|e| e.to_string())?;
+        stream.set_write_timeout(Some(self.timeout)).map_err(|e| e.to_string())?;
+
+        stream.write_all(b"zINSTREAM\0").map_err(|e| e.to_string())?;
+        for chunk in bytes.chunks(8192) {
+            stream.write_all(&(chunk.len() as u32).to_be_bytes()).map_err(|e| e.to_string())?;
+            stream.write_all(chunk).map_err(|e| e.to_string())?;
+        }
+        stream.write_all(&0u32.to_be_bytes()).map_err(|e| e.to_string())?;
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).map_err(|e| e.to_string())?;
+
+        if response.contains("FOUND") {
+            return Err(format!("clamd flagged this upload: {}", response.trim()));
+        }
+        if !response.contains("OK") {
+            return Err(format!("unexpected clamd response: {}", response.trim()));
+        }
+        Ok(())
+    }
+}
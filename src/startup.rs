@@ -0,0 +1,76 @@
+//! A structured startup diagnostic: the effective configuration (secrets
+//! redacted), bound address, DB target, which optional features/jobs are
+//! enabled, and pending-migration status. Printed once at normal startup
+//! and by `money config check`, so a misconfigured deployment — a typo'd
+//! `DATABASE_URL`, a migration nobody ran — fails fast and loudly instead
+//! of panicking deep inside an environment-variable getter the first time a request
+//! needs it.
+
+use crate::config::{AppConfig, RegistrationMode};
+
+pub struct StartupReport {
+    pub bind_address: String,
+    pub database_target: String,
+    pub registration_mode: &'static str,
+    pub features: Vec<(&'static str, bool)>,
+    pub pending_migrations: Vec<String>,
+}
+
+/// Strips userinfo (`user:password@`) out of a Postgres connection string,
+/// leaving enough to identify the target (host, port, database) without
+/// leaking the credential that's already sitting in `DATABASE_URL`.
+fn redact_database_url(database_url: &str) -> String {
+    match database_url.find("://").and_then(|scheme_end| {
+        let rest = &database_url[scheme_end + 3..];
+        rest.find('@').map(|at| (scheme_end, at))
+    }) {
+        Some((scheme_end, at)) => {
+            let scheme = &database_url[..scheme_end + 3];
+            let after_at = &database_url[scheme_end + 3 + at + 1..];
+            format!("{scheme}***@{after_at}")
+        }
+        None => database_url.to_string(),
+    }
+}
+
+pub fn effective_config(config: &AppConfig, database_url: Option<&str>, bind_address: &str, pending_migrations: Vec<String>) -> StartupReport {
+    let registration_mode = match config.registration_mode {
+        RegistrationMode::Open => "open",
+        RegistrationMode::InviteCode => "invite-code",
+        RegistrationMode::Disabled => "disabled",
+    };
+
+    StartupReport {
+        bind_address: bind_address.to_string(),
+        database_target: database_url.map(redact_database_url).unwrap_or_else(|| "<unset>".into()),
+        registration_mode,
+        features: vec![
+            ("strict_mode", config.strict_mode),
+            ("file_logging", config.log_dir.is_some()),
+            ("smtp_mail", config.smtp_host.is_some()),
+            ("exchange_rate_refresh", config.rate_provider_url.is_some()),
+            ("attachment_scanning", config.clamd_address.is_some()),
+            ("oidc_login", config.oidc_issuer.is_some()),
+            ("grpc_sync_server", config.grpc_bind_address.is_some()),
+        ],
+        pending_migrations,
+    }
+}
+
+/// Logs `report` at startup via `log::info!`/`log::warn!`, so it lands
+/// wherever [`crate::logging::init_logger`] already routes everything
+/// else (stdout, plus a rotating file when `LOG_DIR` is set).
+pub fn log_banner(report: &StartupReport) {
+    log::info!("money-rs starting");
+    log::info!("  bind address:     {}", report.bind_address);
+    log::info!("  database:         {}", report.database_target);
+    log::info!("  registration:     {}", report.registration_mode);
+    for (name, enabled) in &report.features {
+        log::info!("  feature {name:<22} {}", if *enabled { "enabled" } else { "disabled" });
+    }
+    if report.pending_migrations.is_empty() {
+        log::info!("  migrations:       up to date");
+    } else {
+        log::warn!("  migrations:       {} pending: {}", report.pending_migrations.len(), report.pending_migrations.join(", "));
+    }
+}
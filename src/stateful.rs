@@ -0,0 +1,27 @@
+/// Like `TryFrom`, but the conversion needs access to some external state
+/// (typically a database connection) to complete — e.g. resolving a
+/// `currency_id` into a `rate_to_fixed` before an insertable model can be
+/// built from a client-supplied request DTO.
+pub trait StatefulTryFrom<T, S>: Sized {
+    type Error;
+
+    fn stateful_try_from(value: T, state: &mut S) -> Result<Self, Self::Error>;
+}
+
+/// The reciprocal of [`StatefulTryFrom`], mirroring `TryFrom`/`TryInto`.
+pub trait StatefulTryInto<T, S>: Sized {
+    type Error;
+
+    fn stateful_try_into(self, state: &mut S) -> Result<T, Self::Error>;
+}
+
+impl<T, U, S> StatefulTryInto<U, S> for T
+where
+    U: StatefulTryFrom<T, S>,
+{
+    type Error = U::Error;
+
+    fn stateful_try_into(self, state: &mut S) -> Result<U, Self::Error> {
+        U::stateful_try_from(self, state)
+    }
+}
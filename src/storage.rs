@@ -0,0 +1,30 @@
+//! Local-filesystem-backed blob storage for receipt attachments.
+//!
+//! TODO: swap for an S3-compatible client behind the same three functions
+//! once a bucket is provisioned; every attachment handler already goes
+//! through this module rather than touching `std::fs` directly, so that
+//! should be a drop-in change.
+
+use std::path::PathBuf;
+
+use crate::config::AppConfig;
+
+pub fn save(config: &AppConfig, key: &str, bytes: &[u8]) -> std::io::Result<()> {
+    let path = resolve(config, key);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, bytes)
+}
+
+pub fn read(config: &AppConfig, key: &str) -> std::io::Result<Vec<u8>> {
+    std::fs::read(resolve(config, key))
+}
+
+pub fn delete(config: &AppConfig, key: &str) -> std::io::Result<()> {
+    std::fs::remove_file(resolve(config, key))
+}
+
+fn resolve(config: &AppConfig, key: &str) -> PathBuf {
+    config.attachments_dir.join(key)
+}
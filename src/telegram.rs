@@ -0,0 +1,67 @@
+//! Optional Telegram bot ingestion: a user links a chat to their account
+//! with a one-time code, then free-text messages in that chat become
+//! entries through the same [`crate::handlers::entries::build_entry_draft`]
+//! pipeline `POST /api/entry/parse` uses. Entirely disabled — no webhook
+//! route does anything useful — when `AppConfig::telegram_bot_token` is
+//! unset, same convention as [`crate::mail`]/[`crate::llm`].
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::config::AppConfig;
+
+/// A short, easy-to-type-into-a-chat code, unlike
+/// [`crate::models::session::generate_token`]'s 64-char bearer token which
+/// is only ever copy-pasted by a client, never typed by a human.
+pub fn generate_link_code() -> String {
+    let mut bytes = [0u8; 5];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes).to_uppercase()
+}
+
+pub struct TelegramClient {
+    pub token: String,
+}
+
+impl TelegramClient {
+    /// `None` when `AppConfig::telegram_bot_token` is unset.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        config.telegram_bot_token.clone().map(|token| TelegramClient { token })
+    }
+
+    pub fn send_message(&self, chat_id: i64, text: &str) -> Result<(), String> {
+        let url = format!("https://api.telegram.org/bot{}/sendMessage", self.token);
+        // TODO: this blocks the async worker thread; fine for the
+        // low-volume chat-reply flow today, same caveat as
+        // `HttpRateProvider::fetch_rates`.
+        reqwest::blocking::Client::new()
+            .post(&url)
+            .json(&SendMessageRequest { chat_id, text })
+            .send()
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct SendMessageRequest<'a> {
+    chat_id: i64,
+    text: &'a str,
+}
+
+/// The subset of Telegram's `Update` webhook payload this module reads.
+#[derive(Deserialize, Debug)]
+pub struct Update {
+    pub message: Option<IncomingMessage>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct IncomingMessage {
+    pub chat: Chat,
+    pub text: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Chat {
+    pub id: i64,
+}
@@ -0,0 +1,98 @@
+//! Request-layer mirrors of the varchar limits added in
+//! `2026-01-14-000001_add_varchar_length_limits`, so an overlong field is
+//! rejected with a 400 ([`require_max_len`]) or a 422 ([`Validator`])
+//! before it reaches the database rather than
+//! surfacing as an opaque 500 from a Postgres length violation.
+
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::money::Money;
+
+pub const NAME_MAX_LEN: usize = 127;
+pub const EMAIL_MAX_LEN: usize = 63;
+
+pub fn require_max_len(field: &str, value: &str, max: usize) -> Result<(), AppError> {
+    if value.chars().count() > max {
+        return Err(AppError::Validation(format!("{field} must be at most {max} characters")));
+    }
+    Ok(())
+}
+
+/// Rejects a `rate_to_fixed`-shaped value that can't be divided by safely:
+/// zero, negative, `NaN`, or infinite. Unlike `AppConfig::strict_mode`'s
+/// negative-`amount` check, this isn't a "looks wrong" heuristic gated
+/// behind an opt-in flag — a bad rate here produces silent division-by-zero
+/// or NaN corruption wherever it's later used to convert an amount (see
+/// [`crate::display_currency::convert`], `models::entry::NewEntry`'s
+/// `StatefulTryFrom` impl), so it's rejected unconditionally.
+pub fn require_finite_positive_rate(field: &str, rate: f64) -> Result<(), AppError> {
+    if !rate.is_finite() || rate <= 0.0 {
+        return Err(AppError::Validation(format!("{field} must be a finite, positive number")));
+    }
+    Ok(())
+}
+
+/// One field's validation failure, as reported in an
+/// [`AppError::FieldValidation`] response body.
+#[derive(Serialize, Debug, Clone)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Collects every field problem in a request body instead of failing on
+/// the first one, so a client gets back one 422 listing everything wrong
+/// at once rather than fixing a field, resubmitting, and hitting the next
+/// rejection. The single-field `require_*` functions above stay as they
+/// are for the handful of call sites that only ever check one thing and
+/// already return early for unrelated reasons (e.g. a disabled feature
+/// check before the body is even looked at).
+#[derive(Default)]
+pub struct Validator {
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Validator::default()
+    }
+
+    pub fn require_non_empty(mut self, field: &str, value: &str) -> Self {
+        if value.trim().is_empty() {
+            self.errors.push(FieldError { field: field.into(), message: "must not be empty".into() });
+        }
+        self
+    }
+
+    pub fn require_max_len(mut self, field: &str, value: &str, max: usize) -> Self {
+        if value.chars().count() > max {
+            self.errors.push(FieldError { field: field.into(), message: format!("must be at most {max} characters") });
+        }
+        self
+    }
+
+    pub fn require_positive(mut self, field: &str, value: Money) -> Self {
+        if value <= Money::ZERO {
+            self.errors.push(FieldError { field: field.into(), message: "must be positive".into() });
+        }
+        self
+    }
+
+    pub fn require_finite_positive_rate(mut self, field: &str, rate: f64) -> Self {
+        if !rate.is_finite() || rate <= 0.0 {
+            self.errors.push(FieldError { field: field.into(), message: "must be a finite, positive number".into() });
+        }
+        self
+    }
+
+    /// Returns `Ok(())` if nothing failed, otherwise every collected
+    /// [`FieldError`] as a single [`AppError::FieldValidation`].
+    pub fn finish(self) -> Result<(), AppError> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::FieldValidation(self.errors))
+        }
+    }
+}